@@ -1452,6 +1452,29 @@ class AndroidFsPlugin(private val activity: Activity) : Plugin(activity) {
         }
     }
 
+    @Command
+    fun moveEntry(invoke: Invoke) {
+        @InvokeArg
+        class Args {
+            lateinit var uri: AFUri
+            lateinit var destDir: AFUri
+        }
+
+        scope.launch {
+            try {
+                val args = invoke.parseArgs(Args::class.java)
+                val uri = getFileController(args.uri).move(args.uri, args.destDir)
+
+                val res = JSObject()
+                res.put("uri", uri)
+                invoke.resolve(res)
+            }
+            catch (e: Exception) {
+                invoke.reject(e.message ?: "unknown error: $e")
+            }
+        }
+    }
+
     @Command
     fun shareFiles(invoke: Invoke) {
         @InvokeArg
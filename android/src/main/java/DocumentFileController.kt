@@ -3,6 +3,7 @@ package com.plugin.android_fs
 import android.app.Activity
 import android.content.Context
 import android.net.Uri
+import android.os.Build
 import android.provider.DocumentsContract
 import androidx.core.database.getLongOrNull
 import androidx.core.database.getStringOrNull
@@ -350,6 +351,58 @@ class DocumentFileController(private val activity: Activity): FileController {
         return res
     }
 
+    override fun move(uri: AFUri, destDirUri: AFUri): JSObject? {
+        // findDocumentPath は Android 8 以上
+        if (Build.VERSION.SDK_INT < Build.VERSION_CODES.O) {
+            return null
+        }
+
+        val documentUri = Uri.parse(uri.uri)
+        val destDirDocumentUri = Uri.parse(destDirUri.uri)
+
+        // moveDocument は同じ provider 内でのみ可能
+        if (documentUri.authority != destDirDocumentUri.authority) {
+            return null
+        }
+        if (!DocumentsContract.isDocumentUri(activity, destDirDocumentUri)) {
+            return null
+        }
+
+        var flags = 0
+        activity.contentResolver.query(
+            documentUri,
+            arrayOf(DocumentsContract.Document.COLUMN_FLAGS),
+            null,
+            null,
+            null
+        )?.use {
+
+            if (it.moveToFirst()) {
+                flags = it.getInt(it.getColumnIndexOrThrow(DocumentsContract.Document.COLUMN_FLAGS))
+            }
+        }
+        if (flags and DocumentsContract.Document.FLAG_SUPPORTS_MOVE == 0) {
+            return null
+        }
+
+        // moveDocument には元の親ディレクトリが必要
+        val path = DocumentsContract.findDocumentPath(activity.contentResolver, documentUri)?.path ?: return null
+        val parentId = path.getOrNull(path.size - 2) ?: return null
+        val parentUri = DocumentsContract.buildDocumentUriUsingTree(documentUri, parentId)
+
+        val movedUri = DocumentsContract.moveDocument(
+            activity.contentResolver,
+            documentUri,
+            parentUri,
+            destDirDocumentUri
+        ) ?: return null
+
+        val res = JSObject()
+        res.put("uri", movedUri.toString())
+        res.put("documentTopTreeUri", destDirUri.documentTopTreeUri)
+        return res
+    }
+
     private fun findDirIdFromName(
         activity: Context,
         dir_topTreeUri: Uri,
@@ -30,4 +30,7 @@ interface FileController {
     fun deleteDirAll(uri: AFUri)
 
     fun rename(uri: AFUri, newName: String): JSObject
+
+    // 移動できない組み合わせの場合は null を返す
+    fun move(uri: AFUri, destDirUri: AFUri): JSObject?
 }
\ No newline at end of file
@@ -98,6 +98,10 @@ class MediaFileController(private val activity: Activity): FileController {
         return res
     }
 
+    override fun move(uri: AFUri, destDirUri: AFUri): JSObject? {
+        return null
+    }
+
     override fun createFile(dirUri: AFUri, relativePath: String, mimeType: String): JSObject {
         throw Exception("Unsupported operation for ${dirUri.uri}")
     }
@@ -233,6 +233,30 @@ class RawFileController: FileController {
         return res
     }
 
+    override fun move(uri: AFUri, destDirUri: AFUri): JSObject? {
+        val destDir = Uri.parse(destDirUri.uri)
+        if (destDir.scheme != "file") {
+            return null
+        }
+
+        val file = File(Uri.parse(uri.uri).path!!)
+        val newFile = File(destDir.path!!, file.name)
+
+        if (newFile.exists()) {
+            throw Exception("File already exists: ${newFile.path}")
+        }
+
+        // 異なるファイルシステム間では失敗する
+        if (!file.renameTo(newFile)) {
+            return null
+        }
+
+        val res = JSObject()
+        res.put("uri", Uri.fromFile(newFile).toString())
+        res.put("documentTopTreeUri", null)
+        return res
+    }
+
     private fun deleteRecursive(fileOrDirectory: File): Boolean {
         if (fileOrDirectory.isDirectory) {
             val children = fileOrDirectory.listFiles()
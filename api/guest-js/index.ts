@@ -1,4 +1,4 @@
-import { convertFileSrc, invoke } from '@tauri-apps/api/core'
+import { Channel, convertFileSrc, invoke } from '@tauri-apps/api/core'
 import { createReadableStream, createWritableStream } from 'create-web-stream'
 
 /** @ignore */
@@ -185,6 +185,60 @@ export type AndroidReadDirOptions = {
 	 * If omitted, all available entries starting from `offset` are returned.
 	 */
 	limit?: number,
+
+	/**
+	 * Sort keys in order of priority.  
+	 * Entries that are equal in all keys keep the order of the file provider.  
+	 * `offset` and `limit` are applied after sorting.  
+	 * Note that the whole directory is then read and sorted again for every page, 
+	 * so avoid paging through large directories with sort keys or filters.
+	 * 
+	 * - `"Name"`: Name in natural order, such as `"a2.txt"` before `"a10.txt"`. This is case-insensitive.
+	 * - `"LastModified"`: Last modified time.
+	 * - `"Len"`: Byte length of a file. Directories are treated as smaller than any file.
+	 * - `"DirsFirst"`: Directories before files.
+	 * 
+	 * e.g. `[{ key: "DirsFirst" }, { key: "Name" }]`
+	 */
+	sortBy?: AndroidReadDirSort[],
+
+	/**
+	 * If specified, only entries of this type are returned.  
+	 * One of: `"File"`, `"Dir"`.
+	 */
+	type?: "File" | "Dir",
+
+	/**
+	 * If not empty, only files whose MIME type matches any of these are returned.  
+	 * Wildcards such as `"image/*"` are supported.  
+	 * Since directories have no MIME type, they are not returned if this is not empty.
+	 */
+	mimeTypes?: string[],
+
+	/**
+	 * If specified, only entries whose name contains this are returned.  
+	 * This is case-insensitive.
+	 */
+	nameContains?: string,
+
+	/**
+	 * If specified, only entries whose name matches this glob pattern, such as `"*.txt"`, are returned.
+	 */
+	namePattern?: string,
+}
+
+/**
+ * Sort key of `AndroidReadDirOptions`.
+ */
+export type AndroidReadDirSort = {
+	key: "Name" | "LastModified" | "Len" | "DirsFirst",
+
+	/**
+	 * If true, the order is reversed.
+	 * 
+	 * Defaults to `false`.
+	 */
+	descending?: boolean,
 }
 
 /**
@@ -234,118 +288,280 @@ export type AndroidEntryMetadataWithUri = AndroidEntryMetadata & { uri: AndroidF
 type AndroidEntryMetadataWithUriInner = AndroidEntryMetadataInner & { uri: AndroidFsUri }
 
 /**
- * Options of `AndroidFs.readFileAsDataUrl`
+ * Options of `AndroidFs.walkDir`
  */
-export type AndroidReadFileAsDataUrlOptions = {
+export type AndroidWalkDirOptions = {
 
 	/**
-	 * The MIME type of the file used as the media type of the Data URL.
+	 * Maximum depth to descend.  
+	 * Children of the root directory have depth `1`.
 	 *
-	 * If not specified, the MIME type provided by the file provider will be used.
+	 * If omitted, there is no limit.
 	 */
-	mimeType?: string
+	maxDepth?: number,
+
+	/**
+	 * Order in which directories are passed relative to their contents.  
+	 * One of: `"PreOrder"`, `"PostOrder"`.
+	 *
+	 * Defaults to `"PreOrder"`.
+	 */
+	order?: "PreOrder" | "PostOrder",
+
+	/**
+	 * If specified, only entries of this type are passed.  
+	 * One of: `"File"`, `"Dir"`.  
+	 * Directories are still descended even if they are not passed.
+	 */
+	type?: "File" | "Dir",
+
+	/**
+	 * If not empty, only files whose MIME type matches any of these are passed.  
+	 * Wildcards such as `"image/*"` are supported.  
+	 * Since directories have no MIME type, they are not passed if this is not empty.
+	 */
+	mimeTypes?: string[],
+
+	/**
+	 * If specified, only entries whose name matches this glob pattern, such as `"*.txt"`, are passed.  
+	 * Directories are still descended even if they are not passed.
+	 */
+	namePattern?: string,
+
+	/**
+	 * Number of entries read from a directory at once, 
+	 * and the maximum number of entries passed to `onEntries` at once.
+	 *
+	 * Defaults to `256`.
+	 */
+	batchSize?: number,
 }
 
 /**
- * Options of `AndroidFs.readTextFile`
+ * Entry passed by `AndroidFs.walkDir`.
  */
-export type AndroidReadTextFileOptions = {
+export type AndroidWalkEntry = AndroidEntryMetadataWithUri & {
 
 	/**
-	 * Text encoding for decoder, such as `"utf-8"`, `"shift_jis"`, `"iso-8859-2"`. 
-	 *  
-	 * This is passed to [`TextDecoder constructor`](https://developer.mozilla.org/en-US/docs/Web/API/TextDecoder/TextDecoder).  
-	 * See: [the available encodings](https://developer.mozilla.org/ja/docs/Web/API/Encoding_API/Encodings).  
-	 * 
-	 * Defaults to `"utf-8"`.
+	 * Depth from the root directory.  
+	 * Children of the root directory have depth `1`.
 	 */
-	encoding?: string,
+	depth: number,
 
 	/**
-	 * Indicates whether decoding errors should be treated as fatal.
+	 * Path relative to the root directory, such as `"sub/file.txt"`.
+	 */
+	relativePath: string,
+}
+
+type AndroidWalkEntryInner = AndroidEntryMetadataWithUriInner & { depth: number, relativePath: string }
+
+/**
+ * Options of `AndroidFs.watchDir`
+ */
+export type AndroidWatchDirOptions = {
+
+	/**
+	 * Interval in milliseconds between reads of the directory.  
+	 * This must be greater than `0`.
 	 *
-	 * - `false`: Invalid byte sequences are replaced with U+FFFD (`�`) and decoding continues.
-	 * - `true`: A `TypeError` is thrown when an invalid byte sequence is encountered.
+	 * Defaults to `1000`.
+	 */
+	intervalMs?: number,
+}
+
+/**
+ * Event passed by `AndroidFs.watchDir`.
+ * 
+ * - `Created`: An entry was added to the directory.
+ * - `Removed`: An entry was removed from the directory.
+ * - `Modified`: The byte length or last modified date of an entry changed.
+ * - `Renamed`: An entry was renamed. If the file provider changes the URI on renaming, an entry that was removed and an entry that was added with the same type, byte length and last modified date are treated as renamed.
+ * - `Error`: Reading the directory failed. The watching continues.
+ */
+export type AndroidDirWatchEvent =
+	| { type: "Created", entry: AndroidEntryMetadataWithUri }
+	| { type: "Removed", entry: AndroidEntryMetadataWithUri }
+	| { type: "Modified", old: AndroidEntryMetadataWithUri, new: AndroidEntryMetadataWithUri }
+	| { type: "Renamed", old: AndroidEntryMetadataWithUri, new: AndroidEntryMetadataWithUri }
+	| { type: "Error", message: string }
+
+type AndroidDirWatchEventInner =
+	| { type: "Created", entry: AndroidEntryMetadataWithUriInner }
+	| { type: "Removed", entry: AndroidEntryMetadataWithUriInner }
+	| { type: "Modified", old: AndroidEntryMetadataWithUriInner, new: AndroidEntryMetadataWithUriInner }
+	| { type: "Renamed", old: AndroidEntryMetadataWithUriInner, new: AndroidEntryMetadataWithUriInner }
+	| { type: "Error", message: string }
+
+/**
+ * Options of `AndroidFs.findDuplicates`
+ */
+export type AndroidFindDuplicatesOptions = {
+
+	/**
+	 * Files smaller than this byte length are ignored.
 	 *
-	 * This is passed to [`TextDecoder constructor`](https://developer.mozilla.org/en-US/docs/Web/API/TextDecoder/TextDecoder).
+	 * Defaults to `1`, so empty files are ignored.
+	 */
+	minByteLength?: number,
+
+	/**
+	 * Byte length from the start of a file that is hashed before the entire file is hashed.  
+	 * Files that differ in this part are excluded without reading the rest.
 	 *
-	 * Defaults to `false`.
+	 * Defaults to `4096`.
 	 */
-	fatal?: boolean,
+	partialHashByteLength?: number,
 
 	/**
-	 * Indicates whether to ignore a leading BOM (Byte Order Mark).
+	 * Callback called after each file is hashed.
+	 */
+	onProgress?: (progress: AndroidFindDuplicatesProgress) => void,
+}
+
+/**
+ * Progress of `AndroidFs.findDuplicates`
+ */
+export type AndroidFindDuplicatesProgress = {
+
+	/**
+	 * Current phase.  
+	 * `"PartialHash"` hashes the start of the files with the same byte length, 
+	 * and `"FullHash"` hashes the entire files with the same partial hash.
+	 */
+	phase: "PartialHash" | "FullHash",
+
+	/**
+	 * Number of the hashed files in the phase.
+	 */
+	done: number,
+
+	/**
+	 * Total number of the files to be hashed in the phase.
+	 */
+	total: number,
+}
+
+/**
+ * Group of files with the same contents returned by `AndroidFs.findDuplicates`
+ */
+export type AndroidDuplicateGroup = {
+
+	/**
+	 * Byte length of each file.
+	 */
+	byteLength: number,
+
+	/**
+	 * Files with the same contents.  
+	 * This has two or more entries.
+	 */
+	entries: (AndroidEntryMetadataWithUri & { type: "File" })[],
+}
+
+type AndroidDuplicateGroupInner = {
+	byteLength: number,
+	entries: (AndroidEntryMetadataWithUriInner & { type: "File" })[],
+}
+
+/**
+ * Entry returned by `AndroidFs.find`
+ */
+export type AndroidFoundEntry = AndroidEntryMetadataWithUri & {
+
+	/**
+	 * Path relative to the searched directory, such as `"photos/2024/a.jpg"`.
+	 */
+	relativePath: string,
+}
+
+type AndroidFoundEntryInner = AndroidEntryMetadataWithUriInner & { relativePath: string }
+
+/**
+ * Options of `AndroidFs.syncDir`
+ */
+export type AndroidSyncDirOptions = {
+
+	/**
+	 * If `true`, files with the same byte length are compared by their contents instead of the last modified date.  
+	 * This is accurate but reads both files.
 	 *
-	 * - `false`: A leading BOM is automatically stripped from the decoded result.
-	 * - `true`: A leading BOM is preserved and treated as a normal character.
+	 * Defaults to `false`.
+	 */
+	compareContents?: boolean,
+
+	/**
+	 * If `true`, entries in the destination that do not exist in the source are removed.  
+	 * This also allows replacing a file with a directory of the same name and vice versa.
 	 *
-	 * This is passed to [`TextDecoder constructor`](https://developer.mozilla.org/en-US/docs/Web/API/TextDecoder/TextDecoder).
+	 * Defaults to `false`.
+	 */
+	deleteExtras?: boolean,
+
+	/**
+	 * If `true`, nothing is changed and only the planned actions are returned.
 	 *
 	 * Defaults to `false`.
 	 */
-	ignoreBOM?: boolean,
+	dryRun?: boolean,
+
+	/**
+	 * Callback called after each action is performed.  
+	 * This is not called if `dryRun` is `true`.
+	 */
+	onProgress?: (progress: AndroidSyncDirProgress) => void,
 }
 
 /**
- * Options of `AndroidFs.writeFile`
+ * Action of `AndroidFs.syncDir`
  */
-export type AndroidWriteFileOptions = {
+export type AndroidSyncDirAction = {
 
 	/**
-	 * Indicates whether a new file should be created if it does not exist 
-	 * when a path is specified.
-	 *
-	 * Defaults to `true`.
+	 * Type of the action.
 	 */
-	create?: boolean,
+	type: "CreateDir" | "CreateFile" | "UpdateFile" | "RemoveFile" | "RemoveDir",
 
 	/**
-	 * Configuration for the system progress notification.
-	 * 
-	 * If this option is omitted, the operation will be performed silently without any notification.  
-	 * If specified, a progress notification will be displayed in the Android status bar during the operation. 
-	 * 
-	 * You can provide a custom configuration object or use one of the predefined presets:
-	 * - `AndroidProgressNotificationTemplate.Default`
-	 * - `AndroidProgressNotificationTemplate.DefaultDownload`
-	 * - `AndroidProgressNotificationTemplate.DefaultUpload`
-	 * - `AndroidProgressNotificationTemplate.DefaultSave`
+	 * Path relative to both the source and destination directories, such as `"sub/file.txt"`.
 	 */
-	notification?: AndroidProgressNotificationTemplate,
+	relativePath: string,
 }
 
 /**
- * Options of `AndroidFs.writeTextFile`
+ * Progress of `AndroidFs.syncDir`
  */
-export type AndroidWriteTextFileOptions = {
+export type AndroidSyncDirProgress = {
 
 	/**
-	 * Indicates whether a new file should be created if it does not exist 
-	 * when a path is specified.
-	 *
-	 * Defaults to `true`.
+	 * The performed action.
 	 */
-	create?: boolean,
+	action: AndroidSyncDirAction,
 
 	/**
-	 * Configuration for the system progress notification.
-	 * 
-	 * If this option is omitted, the operation will be performed silently without any notification.  
-	 * If specified, a progress notification will be displayed in the Android status bar during the operation. 
-	 * 
-	 * You can provide a custom configuration object or use one of the predefined presets:
-	 * - `AndroidProgressNotificationTemplate.Default`
-	 * - `AndroidProgressNotificationTemplate.DefaultDownload`
-	 * - `AndroidProgressNotificationTemplate.DefaultUpload`
-	 * - `AndroidProgressNotificationTemplate.DefaultSave`
+	 * Number of the performed actions.
 	 */
-	notification?: AndroidProgressNotificationTemplate,
+	done: number,
+
+	/**
+	 * Total number of the actions.
+	 */
+	total: number,
 }
 
 /**
- * Options of `AndroidFs.copyFile`
+ * Options of `AndroidFs.createZip`
  */
-export type AndroidCopyFileOptions = {
+export type AndroidCreateZipOptions = {
+
+	/**
+	 * Compression method of files.  
+	 * `"Stored"` is suitable for files that are already compressed, such as images and videos.  
+	 * Directories are always stored.
+	 *
+	 * Defaults to `"Deflated"`.
+	 */
+	compression?: "Stored" | "Deflated",
 
 	/**
 	 * Indicates whether a new file should be created if it does not exist 
@@ -360,111 +576,838 @@ export type AndroidCopyFileOptions = {
 	 * 
 	 * If this option is omitted, the operation will be performed silently without any notification.  
 	 * If specified, a progress notification will be displayed in the Android status bar during the operation. 
+	 * The progress is the number of archived bytes of the source files.
 	 * 
 	 * You can provide a custom configuration object or use one of the predefined presets:
 	 * - `AndroidProgressNotificationTemplate.Default`
-	 * - `AndroidProgressNotificationTemplate.DefaultDownload`
-	 * - `AndroidProgressNotificationTemplate.DefaultUpload`
 	 * - `AndroidProgressNotificationTemplate.DefaultSave`
 	 */
 	notification?: AndroidProgressNotificationTemplate,
 }
 
 /**
- * Options of `AndroidFs.openWriteFileStream`
+ * Options of `AndroidFs.extractZip`
  */
-export type AndroidOpenWriteFileStreamOptions = {
+export type AndroidExtractZipOptions = {
 
 	/**
-	 * The buffer size, in bytes, used when sending data from the frontend to the backend.
-	 * 
-	 * IPC calls are relatively expensive, 
-	 * so larger buffer sizes are generally more efficient. 
-	 * But if it is too large, the UI may freeze or run out of memory.
-	 * 
-	 * Defaults to `524288` (512 KiB).
+	 * Glob patterns of paths in the archive, such as `"*.txt"` and `"photos/**\/*.jpg"`.  
+	 * `*` does not match `/`, and `**` matches zero or more directories.  
+	 * If not empty, only entries that match any of them are extracted.
+	 *
+	 * Defaults to `[]`.
 	 */
-	bufferByteLength?: number,
+	include?: string[],
 
 	/**
-	 * An `AbortSignal` that allows the write operation to be aborted.
-	 * 
-	 * When aborted, the stream enters an errored state, all subsequent write operations fail,
-	 * and the underlying file resources are released instantly.
+	 * Glob patterns of paths in the archive, in the same format as `include`.  
+	 * Entries that match any of them are not extracted.
+	 *
+	 * Defaults to `[]`.
 	 */
-	signal?: AbortSignal,
+	exclude?: string[],
 
 	/**
-	 * Indicates whether a new file should be created if it does not exist 
-	 * when a path is specified.
+	 * Maximum total uncompressed bytes of the entries to extract, as a protection against zip bombs.  
+	 * If the sizes recorded in the archive exceed this, nothing is extracted.  
+	 * `null` means no limit.
 	 *
-	 * Defaults to `true`.
+	 * Defaults to `4 * 1024 * 1024 * 1024` (4 GiB).
 	 */
-	create?: boolean,
+	maxTotalLen?: number | null,
 
 	/**
 	 * Configuration for the system progress notification.
 	 * 
 	 * If this option is omitted, the operation will be performed silently without any notification.  
 	 * If specified, a progress notification will be displayed in the Android status bar during the operation. 
+	 * The progress is the number of extracted bytes.
 	 * 
 	 * You can provide a custom configuration object or use one of the predefined presets:
-	 * - `AndroidProgressNotificationContent.Default`
-	 * - `AndroidProgressNotificationContent.DefaultDownload`
-	 * - `AndroidProgressNotificationContent.DefaultUpload`
-	 * - `AndroidProgressNotificationContent.DefaultSave`
+	 * - `AndroidProgressNotificationTemplate.Default`
+	 * - `AndroidProgressNotificationTemplate.DefaultSave`
 	 */
 	notification?: AndroidProgressNotificationTemplate,
 }
 
 /**
- * Options of `AndroidFs.openReadFileStream`
+ * Result of an entry of `AndroidFs.extractZip`
  */
-export type AndroidOpenReadFileStreamOptions = {
+export type AndroidExtractZipEntry = {
 
 	/**
-	 * The buffer size, in bytes, used when sending data from the backend to the frontend.
-	 * 
-	 * IPC calls are relatively expensive, 
-	 * so larger buffer sizes are generally more efficient. 
-	 * But if it is too large, the UI may freeze or run out of memory.
-	 * 
-	 * Defaults to `524288` (512 KiB).
+	 * Path of the entry in the archive, such as `"sub/file.txt"`.  
+	 * A directory ends with `/`.
 	 */
-	bufferByteLength?: number,
+	name: string,
 
 	/**
-	 * An `AbortSignal` that allows the read operation to be aborted.
-	 * 
-	 * When aborted, the stream enters an errored state, all subsequent read operations fail,
-	 * and the underlying file resources are released instantly.
+	 * Whether the entry is a directory.
 	 */
-	signal?: AbortSignal,
-}
+	isDir: boolean,
 
-export type AndroidOpenReadTextFileLinesStreamItem = {
+	/**
+	 * Uncompressed byte length recorded in the archive.
+	 */
+	byteLength: number,
 
 	/**
-	 * A text of the current line, excluding line break characters.
-	 * 
-	 * If you need it, use `lineBreak`.
+	 * `"Extracted"` if created in the destination,
+	 * `"Skipped"` if excluded by `include` or `exclude`,
+	 * and `"Failed"` if an error occurred, such as a path outside the destination directory or a CRC mismatch.
 	 */
-	line: string,
+	status: "Extracted" | "Skipped" | "Failed",
 
 	/**
-	 * Line break characters used at the end of the current line.  
-	 * One of: `"\n"`, `"\r\n"`, `null`.
-	 * 
-	 * This value is `null`
-	 * if the current line is last and the file does not end with a line break.
+	 * URI of the created entry, if extracted.
 	 */
-	lineBreak: "\n" | "\r\n" | null
+	uri: AndroidFsUri | null,
+
+	/**
+	 * Path of the created entry relative to the destination directory, if extracted.  
+	 * This may differ from `name`, since the file provider may sanitize names or append a sequential number to avoid conflicts.
+	 */
+	relativePath: string | null,
+
+	/**
+	 * Error message, if failed.
+	 */
+	error: string | null,
 }
 
 /**
- * Options of `AndroidFs.openReadTextFileLinesStream`
+ * Options of `AndroidFs.getDirStats`
  */
-export type AndroidOpenReadTextFileLinesStreamOptions = {
+export type AndroidGetDirStatsOptions = {
+
+	/**
+	 * Maximum depth to descend.  
+	 * Children of the directory have depth `1`.
+	 *
+	 * If omitted, there is no limit.
+	 */
+	maxDepth?: number,
+}
+
+/**
+ * Usage statistics of a directory returned by `AndroidFs.getDirStats`.  
+ * The directory itself is not counted.
+ */
+export type AndroidDirStats = {
+
+	/**
+	 * Total length of all files in bytes.
+	 */
+	byteLength: number,
+
+	/**
+	 * Number of files.
+	 */
+	fileCount: number,
+
+	/**
+	 * Number of directories.
+	 */
+	dirCount: number,
+
+	/**
+	 * Statistics of files for each MIME type, such as `"image/png"`.
+	 */
+	mimeTypes: { [mimeType: string]: { byteLength: number, fileCount: number } },
+}
+
+/**
+ * Options of `AndroidFs.readFileAsDataUrl`
+ */
+export type AndroidReadFileAsDataUrlOptions = {
+
+	/**
+	 * The MIME type of the file used as the media type of the Data URL.
+	 *
+	 * If not specified, the MIME type provided by the file provider will be used.
+	 */
+	mimeType?: string
+}
+
+/**
+ * Options of `AndroidFs.readTextFile`
+ */
+export type AndroidReadTextFileOptions = {
+
+	/**
+	 * Text encoding for decoder, such as `"utf-8"`, `"shift_jis"`, `"iso-8859-2"`. 
+	 *  
+	 * This is passed to [`TextDecoder constructor`](https://developer.mozilla.org/en-US/docs/Web/API/TextDecoder/TextDecoder).  
+	 * See: [the available encodings](https://developer.mozilla.org/ja/docs/Web/API/Encoding_API/Encodings).  
+	 * 
+	 * Defaults to `"utf-8"`.
+	 */
+	encoding?: string,
+
+	/**
+	 * Indicates whether decoding errors should be treated as fatal.
+	 *
+	 * - `false`: Invalid byte sequences are replaced with U+FFFD (`�`) and decoding continues.
+	 * - `true`: A `TypeError` is thrown when an invalid byte sequence is encountered.
+	 *
+	 * This is passed to [`TextDecoder constructor`](https://developer.mozilla.org/en-US/docs/Web/API/TextDecoder/TextDecoder).
+	 *
+	 * Defaults to `false`.
+	 */
+	fatal?: boolean,
+
+	/**
+	 * Indicates whether to ignore a leading BOM (Byte Order Mark).
+	 *
+	 * - `false`: A leading BOM is automatically stripped from the decoded result.
+	 * - `true`: A leading BOM is preserved and treated as a normal character.
+	 *
+	 * This is passed to [`TextDecoder constructor`](https://developer.mozilla.org/en-US/docs/Web/API/TextDecoder/TextDecoder).
+	 *
+	 * Defaults to `false`.
+	 */
+	ignoreBOM?: boolean,
+}
+
+/**
+ * Options of `AndroidFs.readLastLines`
+ */
+export type AndroidReadLastLinesOptions = {
+
+	/**
+	 * Text encoding label for decoder, such as `"utf-8"`, `"shift_jis"`, `"utf-16le"`. 
+	 *  
+	 * This is also used in the backend to find line breaks, 
+	 * so UTF-16LE/BE files can be read correctly.  
+	 * This is passed to [`TextDecoder constructor`](https://developer.mozilla.org/en-US/docs/Web/API/TextDecoder/TextDecoder).
+	 * See: [the available encodings](https://developer.mozilla.org/ja/docs/Web/API/Encoding_API/Encodings).
+	 * 
+	 * Defaults to `"utf-8"`.
+	 */
+	encoding?: string,
+
+	/**
+	 * Indicates whether decoding errors should be treated as fatal.
+	 *
+	 * - `false`: Invalid byte sequences are replaced with U+FFFD (`�`) and decoding continues.
+	 * - `true`: A `TypeError` is thrown when an invalid byte sequence is encountered.
+	 *
+	 * This is passed to [`TextDecoder constructor`](https://developer.mozilla.org/en-US/docs/Web/API/TextDecoder/TextDecoder).
+	 *
+	 * Defaults to `false`.
+	 */
+	fatal?: boolean,
+
+	/**
+	 * Indicates whether to ignore a leading BOM (Byte Order Mark).
+	 *
+	 * - `false`: A leading BOM is automatically stripped, if the first line of the file is included.
+	 * - `true`: A leading BOM is preserved and treated as a normal character.
+	 *
+	 * Defaults to `false`.
+	 */
+	ignoreBOM?: boolean,
+}
+
+export type AndroidBuildLineIndexOptions = {
+
+	/**
+	 * The number of lines between the recorded byte offsets.  
+	 * Smaller values make seeking faster but the index larger.  
+	 * This must be greater than `0`.
+	 * 
+	 * Defaults to `1000`.
+	 */
+	interval?: number,
+
+	/**
+	 * Text encoding label of the file, such as `"utf-8"`, `"shift_jis"`, `"utf-16le"`. 
+	 *  
+	 * This is used in the backend to find line breaks.  
+	 * The index is used by `AndroidFs.openReadTextFileLinesStream` only when it is opened with the same encoding.
+	 * See: [the available encodings](https://developer.mozilla.org/ja/docs/Web/API/Encoding_API/Encodings).
+	 * 
+	 * Defaults to `"utf-8"`.
+	 */
+	encoding?: string,
+}
+
+/**
+ * Hash algorithm of `AndroidFs.hashFile`
+ * 
+ * `"SHA-1"` and `"MD5"` are not collision resistant, so use them only for compatibility.  
+ * `"CRC32"` is the same as ZIP and gzip, and `"XXH64"` is XXH64 with seed 0.
+ */
+export type AndroidHashAlgorithm = "SHA-256" | "SHA-1" | "MD5" | "CRC32" | "XXH64"
+
+/**
+ * Options of `AndroidFs.hashFile`
+ */
+export type AndroidHashFileOptions = {
+
+	/**
+	 * Configuration for the system progress notification.
+	 * 
+	 * If this option is omitted, the operation will be performed silently without any notification.  
+	 * If specified, a progress notification will be displayed in the Android status bar during the operation. 
+	 * 
+	 * You can provide a custom configuration object or use one of the predefined presets:
+	 * - `AndroidProgressNotificationTemplate.Default`
+	 * - `AndroidProgressNotificationTemplate.DefaultUpload`
+	 */
+	notification?: AndroidProgressNotificationTemplate,
+}
+
+/**
+ * Options of `AndroidFs.writeFile`
+ */
+export type AndroidWriteFileOptions = {
+
+	/**
+	 * Indicates whether a new file should be created if it does not exist 
+	 * when a path is specified.
+	 *
+	 * Defaults to `true`.
+	 */
+	create?: boolean,
+
+	/**
+	 * Configuration for the system progress notification.
+	 * 
+	 * If this option is omitted, the operation will be performed silently without any notification.  
+	 * If specified, a progress notification will be displayed in the Android status bar during the operation. 
+	 * 
+	 * You can provide a custom configuration object or use one of the predefined presets:
+	 * - `AndroidProgressNotificationTemplate.Default`
+	 * - `AndroidProgressNotificationTemplate.DefaultDownload`
+	 * - `AndroidProgressNotificationTemplate.DefaultUpload`
+	 * - `AndroidProgressNotificationTemplate.DefaultSave`
+	 */
+	notification?: AndroidProgressNotificationTemplate,
+
+	/**
+	 * If `true`, the data is written to a temporary file first, 
+	 * and the file is replaced with it only after all data has been written successfully, 
+	 * so the file is not left truncated even if the app dies while writing.  
+	 * If the file cannot be replaced atomically, such as a file on cloud storage, 
+	 * the temporary file is copied into the file instead.
+	 * 
+	 * See [AndroidFs::write_atomic](https://docs.rs/tauri-plugin-android-fs/latest/tauri_plugin_android_fs/api/api_async/struct.AndroidFs.html#method.write_atomic) for details.
+	 *
+	 * Defaults to `false`.
+	 */
+	atomic?: boolean,
+
+	/**
+	 * If `true`, the data is appended to the end of the file and the existing content is preserved.  
+	 * If the file provider does not support the append mode, 
+	 * the file is opened in read-write mode and the data is written after seeking to the end.  
+	 * 
+	 * This cannot be used together with `atomic`.
+	 * 
+	 * See [AndroidFs::append](https://docs.rs/tauri-plugin-android-fs/latest/tauri_plugin_android_fs/api/api_async/struct.AndroidFs.html#method.append) for details.
+	 *
+	 * Defaults to `false`.
+	 */
+	append?: boolean,
+}
+
+/**
+ * Options of `AndroidFs.writeTextFile`
+ */
+export type AndroidWriteTextFileOptions = {
+
+	/**
+	 * Indicates whether a new file should be created if it does not exist 
+	 * when a path is specified.
+	 *
+	 * Defaults to `true`.
+	 */
+	create?: boolean,
+
+	/**
+	 * Configuration for the system progress notification.
+	 * 
+	 * If this option is omitted, the operation will be performed silently without any notification.  
+	 * If specified, a progress notification will be displayed in the Android status bar during the operation. 
+	 * 
+	 * You can provide a custom configuration object or use one of the predefined presets:
+	 * - `AndroidProgressNotificationTemplate.Default`
+	 * - `AndroidProgressNotificationTemplate.DefaultDownload`
+	 * - `AndroidProgressNotificationTemplate.DefaultUpload`
+	 * - `AndroidProgressNotificationTemplate.DefaultSave`
+	 */
+	notification?: AndroidProgressNotificationTemplate,
+
+	/**
+	 * If `true`, the data is written to a temporary file first, 
+	 * and the file is replaced with it only after all data has been written successfully, 
+	 * so the file is not left truncated even if the app dies while writing.  
+	 * If the file cannot be replaced atomically, such as a file on cloud storage, 
+	 * the temporary file is copied into the file instead.
+	 * 
+	 * See [AndroidFs::write_atomic](https://docs.rs/tauri-plugin-android-fs/latest/tauri_plugin_android_fs/api/api_async/struct.AndroidFs.html#method.write_atomic) for details.
+	 *
+	 * Defaults to `false`.
+	 */
+	atomic?: boolean,
+
+	/**
+	 * If `true`, the data is appended to the end of the file and the existing content is preserved.  
+	 * If the file provider does not support the append mode, 
+	 * the file is opened in read-write mode and the data is written after seeking to the end.  
+	 * 
+	 * This cannot be used together with `atomic`.
+	 * 
+	 * See [AndroidFs::append](https://docs.rs/tauri-plugin-android-fs/latest/tauri_plugin_android_fs/api/api_async/struct.AndroidFs.html#method.append) for details.
+	 *
+	 * Defaults to `false`.
+	 */
+	append?: boolean,
+
+	/**
+	 * The encoding of the text written to the file, as a [WHATWG encoding label](https://encoding.spec.whatwg.org/#names-and-labels) such as `"shift_jis"`, `"utf-16le"` or `"windows-1252"`.  
+	 * Unlike `TextEncoder`, which supports only UTF-8, UTF-16LE/BE and legacy encodings such as Shift_JIS are also supported.  
+	 * The conversion is done in the backend, 
+	 * and if the text contains a character that cannot be represented in the encoding, the write fails.
+	 *
+	 * Defaults to `"utf-8"`.
+	 */
+	encoding?: string,
+
+	/**
+	 * If `true`, the byte order mark (BOM) of the encoding is written at the beginning of the file.  
+	 * Only UTF-8 and UTF-16LE/BE have a BOM, so this fails with other encodings.  
+	 * When appending to a non-empty file, the BOM is not written.
+	 *
+	 * Defaults to `false`.
+	 */
+	bom?: boolean,
+
+	/**
+	 * If specified, line breaks (`"\n"` and `"\r\n"`) in the text are converted to this: `"lf"` for `"\n"` and `"crlf"` for `"\r\n"`.  
+	 * A lone `"\r"` is not treated as a line break and is written as is.
+	 *
+	 * Defaults to none, which writes line breaks as they are.
+	 */
+	lineEnding?: "lf" | "crlf",
+}
+
+/**
+ * Options of `AndroidFs.copyFile`
+ */
+export type AndroidCopyFileOptions = {
+
+	/**
+	 * Indicates whether a new file should be created if it does not exist 
+	 * when a path is specified.
+	 *
+	 * Defaults to `true`.
+	 */
+	create?: boolean,
+
+	/**
+	 * Configuration for the system progress notification.
+	 * 
+	 * If this option is omitted, the operation will be performed silently without any notification.  
+	 * If specified, a progress notification will be displayed in the Android status bar during the operation. 
+	 * 
+	 * You can provide a custom configuration object or use one of the predefined presets:
+	 * - `AndroidProgressNotificationTemplate.Default`
+	 * - `AndroidProgressNotificationTemplate.DefaultDownload`
+	 * - `AndroidProgressNotificationTemplate.DefaultUpload`
+	 * - `AndroidProgressNotificationTemplate.DefaultSave`
+	 */
+	notification?: AndroidProgressNotificationTemplate,
+}
+
+/**
+ * Options of `AndroidFs.openWriteFileStream`
+ */
+export type AndroidOpenWriteFileStreamOptions = {
+
+	/**
+	 * The buffer size, in bytes, used when sending data from the frontend to the backend.
+	 * 
+	 * IPC calls are relatively expensive, 
+	 * so larger buffer sizes are generally more efficient. 
+	 * But if it is too large, the UI may freeze or run out of memory.
+	 * 
+	 * Defaults to `524288` (512 KiB).
+	 */
+	bufferByteLength?: number,
+
+	/**
+	 * An `AbortSignal` that allows the write operation to be aborted.
+	 * 
+	 * When aborted, the stream enters an errored state, all subsequent write operations fail,
+	 * and the underlying file resources are released instantly.
+	 */
+	signal?: AbortSignal,
+
+	/**
+	 * Indicates whether a new file should be created if it does not exist 
+	 * when a path is specified.
+	 *
+	 * Defaults to `true`.
+	 */
+	create?: boolean,
+
+	/**
+	 * Configuration for the system progress notification.
+	 * 
+	 * If this option is omitted, the operation will be performed silently without any notification.  
+	 * If specified, a progress notification will be displayed in the Android status bar during the operation. 
+	 * 
+	 * You can provide a custom configuration object or use one of the predefined presets:
+	 * - `AndroidProgressNotificationContent.Default`
+	 * - `AndroidProgressNotificationContent.DefaultDownload`
+	 * - `AndroidProgressNotificationContent.DefaultUpload`
+	 * - `AndroidProgressNotificationContent.DefaultSave`
+	 */
+	notification?: AndroidProgressNotificationTemplate,
+
+	/**
+	 * If `true`, the data is written to a temporary file first, 
+	 * and the file is replaced with it only after all data has been written successfully, 
+	 * so the file is not left truncated even if the app dies while writing.  
+	 * If the file cannot be replaced atomically, such as a file on cloud storage, 
+	 * the temporary file is copied into the file instead.
+	 * 
+	 * See [AndroidFs::write_atomic](https://docs.rs/tauri-plugin-android-fs/latest/tauri_plugin_android_fs/api/api_async/struct.AndroidFs.html#method.write_atomic) for details.
+	 *
+	 * Defaults to `false`.
+	 */
+	atomic?: boolean,
+
+	/**
+	 * If `true`, the data is appended to the end of the file and the existing content is preserved.  
+	 * If the file provider does not support the append mode, 
+	 * the file is opened in read-write mode and the data is written after seeking to the end.  
+	 * 
+	 * This cannot be used together with `atomic`.
+	 * 
+	 * See [AndroidFs::append](https://docs.rs/tauri-plugin-android-fs/latest/tauri_plugin_android_fs/api/api_async/struct.AndroidFs.html#method.append) for details.
+	 *
+	 * Defaults to `false`.
+	 */
+	append?: boolean,
+}
+
+/**
+ * Options of `AndroidFs.openWriteTextFileStream`
+ */
+export type AndroidOpenWriteTextFileStreamOptions = {
+
+	/**
+	 * The buffer size, in UTF-16 code units (`string.length`), used when sending text from the frontend to the backend.
+	 * 
+	 * IPC calls are relatively expensive, 
+	 * so larger buffer sizes are generally more efficient. 
+	 * But if it is too large, the UI may freeze or run out of memory.
+	 * 
+	 * Defaults to `262144`.
+	 */
+	bufferLength?: number,
+
+	/**
+	 * An `AbortSignal` that allows the write operation to be aborted.
+	 * 
+	 * When aborted, the stream enters an errored state, all subsequent write operations fail,
+	 * and the underlying file resources are released instantly.
+	 */
+	signal?: AbortSignal,
+
+	/**
+	 * Indicates whether a new file should be created if it does not exist 
+	 * when a path is specified.
+	 *
+	 * Defaults to `true`.
+	 */
+	create?: boolean,
+
+	/**
+	 * Configuration for the system progress notification.
+	 * 
+	 * If this option is omitted, the operation will be performed silently without any notification.  
+	 * If specified, a progress notification will be displayed in the Android status bar during the operation. 
+	 * 
+	 * You can provide a custom configuration object or use one of the predefined presets:
+	 * - `AndroidProgressNotificationContent.Default`
+	 * - `AndroidProgressNotificationContent.DefaultDownload`
+	 * - `AndroidProgressNotificationContent.DefaultUpload`
+	 * - `AndroidProgressNotificationContent.DefaultSave`
+	 */
+	notification?: AndroidProgressNotificationTemplate,
+
+	/**
+	 * If `true`, the data is written to a temporary file first, 
+	 * and the file is replaced with it only after all data has been written successfully, 
+	 * so the file is not left truncated even if the app dies while writing.  
+	 * If the file cannot be replaced atomically, such as a file on cloud storage, 
+	 * the temporary file is copied into the file instead.
+	 * 
+	 * See [AndroidFs::write_atomic](https://docs.rs/tauri-plugin-android-fs/latest/tauri_plugin_android_fs/api/api_async/struct.AndroidFs.html#method.write_atomic) for details.
+	 *
+	 * Defaults to `false`.
+	 */
+	atomic?: boolean,
+
+	/**
+	 * If `true`, the data is appended to the end of the file and the existing content is preserved.  
+	 * If the file provider does not support the append mode, 
+	 * the file is opened in read-write mode and the data is written after seeking to the end.  
+	 * 
+	 * This cannot be used together with `atomic`.
+	 * 
+	 * See [AndroidFs::append](https://docs.rs/tauri-plugin-android-fs/latest/tauri_plugin_android_fs/api/api_async/struct.AndroidFs.html#method.append) for details.
+	 *
+	 * Defaults to `false`.
+	 */
+	append?: boolean,
+
+	/**
+	 * The encoding of the text written to the file, as a [WHATWG encoding label](https://encoding.spec.whatwg.org/#names-and-labels) such as `"shift_jis"`, `"utf-16le"` or `"windows-1252"`.  
+	 * Unlike `TextEncoder`, which supports only UTF-8, UTF-16LE/BE and legacy encodings such as Shift_JIS are also supported.  
+	 * The conversion is done in the backend, 
+	 * and if the text contains a character that cannot be represented in the encoding, the write fails.
+	 *
+	 * Defaults to `"utf-8"`.
+	 */
+	encoding?: string,
+
+	/**
+	 * If `true`, the byte order mark (BOM) of the encoding is written at the beginning of the file.  
+	 * Only UTF-8 and UTF-16LE/BE have a BOM, so this fails with other encodings.  
+	 * When appending to a non-empty file, the BOM is not written.
+	 *
+	 * Defaults to `false`.
+	 */
+	bom?: boolean,
+
+	/**
+	 * If specified, line breaks (`"\n"` and `"\r\n"`) in the text are converted to this: `"lf"` for `"\n"` and `"crlf"` for `"\r\n"`.  
+	 * A lone `"\r"` is not treated as a line break and is written as is.
+	 *
+	 * Defaults to none, which writes line breaks as they are.
+	 */
+	lineEnding?: "lf" | "crlf",
+}
+
+/**
+ * Options of `AndroidFs.openWriteFileHandle`
+ */
+export type AndroidOpenWriteFileHandleOptions = {
+
+	/**
+	 * Indicates whether a new file should be created if it does not exist 
+	 * when a path is specified.
+	 *
+	 * Defaults to `true`.
+	 */
+	create?: boolean,
+
+	/**
+	 * Configuration for the system progress notification.
+	 * 
+	 * If this option is omitted, the operation will be performed silently without any notification.  
+	 * If specified, a progress notification will be displayed in the Android status bar during the operation. 
+	 * The progress is the total number of bytes written by `write` and `writeAt`.
+	 * 
+	 * You can provide a custom configuration object or use one of the predefined presets:
+	 * - `AndroidProgressNotificationContent.Default`
+	 * - `AndroidProgressNotificationContent.DefaultDownload`
+	 * - `AndroidProgressNotificationContent.DefaultSave`
+	 */
+	notification?: AndroidProgressNotificationTemplate,
+
+	/**
+	 * If `true`, the data is written to a temporary file first, 
+	 * and the file is replaced with it only when the handle is closed by `close`, 
+	 * so the file is not left half-written even if the app dies while writing.  
+	 * If the file cannot be replaced atomically, such as a file on cloud storage, 
+	 * the temporary file is copied into the file instead.
+	 * 
+	 * See [AndroidFs::write_atomic](https://docs.rs/tauri-plugin-android-fs/latest/tauri_plugin_android_fs/api/api_async/struct.AndroidFs.html#method.write_atomic) for details.
+	 *
+	 * Defaults to `false`.
+	 */
+	atomic?: boolean,
+}
+
+/**
+ * File opened with write mode by `AndroidFs.openWriteFileHandle`
+ */
+export type AndroidWriteFileHandle = {
+
+	/**
+	 * The current position in bytes, which is advanced by `write` and changed by `seek`.
+	 */
+	readonly position: number,
+
+	/**
+	 * Writes all bytes at the current position and advances the position.
+	 */
+	write: (data: Uint8Array<ArrayBufferLike>) => Promise<void>,
+
+	/**
+	 * Writes all bytes at `offset` without changing the current position.  
+	 * If `offset` is beyond the end of the file, the gap is filled with zeros.  
+	 * This fails if the `append` option was `true`.
+	 */
+	writeAt: (offset: number, data: Uint8Array<ArrayBufferLike>) => Promise<void>,
+
+	/**
+	 * Changes the current position and resolves to the new position.  
+	 * `whence` defaults to `"start"`.  
+	 * This fails if the file is not seekable, such as a pipe, or if the `append` option was `true`.
+	 */
+	seek: (offset: number, whence?: AndroidSeekWhence) => Promise<number>,
+
+	/**
+	 * Truncates or extends the file to `len` bytes.  
+	 * Extended bytes are zeros. The current position is not changed.  
+	 * This can also be used to preallocate the output file.
+	 */
+	setLen: (len: number) => Promise<void>,
+
+	/**
+	 * Releases the file resource as succeeded.  
+	 * If the `atomic` option was `true`, the file is replaced with the written data at this point.
+	 */
+	close: () => Promise<void>,
+
+	/**
+	 * Releases the file resource as failed.  
+	 * If the `atomic` option was `true`, the written data is discarded and the file is not changed.
+	 */
+	abort: () => Promise<void>,
+}
+
+/**
+ * Options of `AndroidFs.openReadFileStream`
+ */
+export type AndroidOpenReadFileStreamOptions = {
+
+	/**
+	 * The buffer size, in bytes, used when sending data from the backend to the frontend.
+	 * 
+	 * IPC calls are relatively expensive, 
+	 * so larger buffer sizes are generally more efficient. 
+	 * But if it is too large, the UI may freeze or run out of memory.
+	 * 
+	 * Defaults to `524288` (512 KiB).
+	 */
+	bufferByteLength?: number,
+
+	/**
+	 * An `AbortSignal` that allows the read operation to be aborted.
+	 * 
+	 * When aborted, the stream enters an errored state, all subsequent read operations fail,
+	 * and the underlying file resources are released instantly.
+	 */
+	signal?: AbortSignal,
+}
+
+/**
+ * Origin of the offset for `AndroidReadFileHandle.seek`
+ * 
+ * - `"start"`: The offset is from the start of the file and must not be negative.
+ * - `"current"`: The offset is from the current position.
+ * - `"end"`: The offset is from the end of the file.
+ */
+export type AndroidSeekWhence = "start" | "current" | "end"
+
+/**
+ * File opened with read-only mode by `AndroidFs.openReadFileHandle`
+ */
+export type AndroidReadFileHandle = {
+
+	/**
+	 * The length of the file in bytes when it was opened.  
+	 * This is `null` if it is unknown, such as when the file provider returns a pipe.
+	 */
+	readonly byteLength: number | null,
+
+	/**
+	 * The current position in bytes, which is advanced by `read` and changed by `seek`.
+	 */
+	readonly position: number,
+
+	/**
+	 * Reads up to `len` bytes from the current position and advances the position.  
+	 * Resolves to `null` at the end of the file.
+	 */
+	read: (len: number) => Promise<Uint8Array<ArrayBuffer> | null>,
+
+	/**
+	 * Reads up to `len` bytes from `offset` without changing the current position.  
+	 * The result is shorter than `len` only if the end of the file is reached.
+	 */
+	readAt: (offset: number, len: number) => Promise<Uint8Array<ArrayBuffer>>,
+
+	/**
+	 * Changes the current position and resolves to the new position.  
+	 * `whence` defaults to `"start"`.  
+	 * This fails if the file is not seekable, such as a pipe.
+	 */
+	seek: (offset: number, whence?: AndroidSeekWhence) => Promise<number>,
+
+	/**
+	 * Releases the file resource.  
+	 * This must always be called.
+	 */
+	close: () => Promise<void>,
+}
+
+/**
+ * Options of `AndroidFs.openReadDirStream`
+ */
+export type AndroidOpenReadDirStreamOptions = {
+
+	/**
+	 * Number of entries to be read from the directory at once.
+	 * 
+	 * IPC calls are relatively expensive, 
+	 * so larger page sizes are generally more efficient. 
+	 * But if it is too large, the UI may freeze or run out of memory.
+	 * 
+	 * Defaults to `256`.
+	 */
+	pageSize?: number,
+
+	/**
+	 * An `AbortSignal` that allows the read operation to be aborted.
+	 * 
+	 * When aborted, the stream enters an errored state, all subsequent read operations fail,
+	 * and the underlying resources are released instantly.
+	 */
+	signal?: AbortSignal,
+}
+
+export type AndroidOpenReadTextFileLinesStreamItem = {
+
+	/**
+	 * A text of the current line, excluding line break characters.
+	 * 
+	 * If you need it, use `lineBreak`.
+	 */
+	line: string,
+
+	/**
+	 * Line break characters used at the end of the current line.  
+	 * One of: `"\n"`, `"\r\n"`, `null`.
+	 * 
+	 * This value is `null`
+	 * if the current line is last and the file does not end with a line break.
+	 */
+	lineBreak: "\n" | "\r\n" | null
+}
+
+/**
+ * Options of `AndroidFs.openReadTextFileLinesStream`
+ */
+export type AndroidOpenReadTextFileLinesStreamOptions = {
 
 	/**
 	 * Text encoding label for decoder, such as `"utf-8"`, `"shift_jis"`, `"iso-8859-2"`. 
@@ -506,7 +1449,158 @@ export type AndroidOpenReadTextFileLinesStreamOptions = {
 	 * But if it is too large, the UI may freeze or run out of memory.
 	 * 
 	 * This value is not guaranteed to be strictly respected. 
-	 * If a single line exceeds this size, 
+	 * If a single line exceeds this size, 
+	 * more bytes may be sent in a single IPC transmission.
+	 * 
+	 * Defaults to `524288` (512 KiB).
+	 */
+	bufferByteLength?: number,
+
+	/**
+	 * The maximum byte length of a line before decoding, excluding line break characters and an initial BOM (if present). 
+	 * If a line exceeds this limit, an error is thrown. 
+	 * This prevents OOM errors when reading minified files or binaries.
+	 * 
+	 * Defaults to `0` (unlimited).
+	 */
+	maxLineByteLength?: number,
+
+	/**
+	 * The 0-based line number to start reading from.
+	 * 
+	 * If an index built by `AndroidFs.buildLineIndex` is available for the file, 
+	 * the backend seeks to the nearest recorded line and skips only the remaining lines. 
+	 * Otherwise, the lines are skipped from the beginning of the file.  
+	 * The index is not used if the file has been modified since it was built, or if the encoding differs.
+	 * 
+	 * Defaults to `0`.
+	 */
+	startLine?: number,
+
+	/**
+	 * Indicates whether to keep the stream open at the end of the file and wait for appended lines, like `tail -f`.
+	 * 
+	 * If `true`, the stream does not end at EOF. 
+	 * It checks the file every `followIntervalMs` and yields newly appended lines as the file grows.
+	 * A last line without a line break is treated as still being written and is not yielded until the line break is appended.  
+	 * If the file becomes shorter than the position already read, it is regarded as truncated, 
+	 * and reading restarts from the beginning of the file.  
+	 * If the file is replaced with another file, such as by log rotation, reading restarts from the beginning of the new file.
+	 * Replacement is checked less often than appended lines, at most every `followIntervalMs` or 5 seconds, whichever is longer.
+	 * 
+	 * The stream should be ended by canceling it or by the `signal`.
+	 * 
+	 * Defaults to `false`.
+	 */
+	follow?: boolean,
+
+	/**
+	 * The interval, in milliseconds, to check the file for appended lines when `follow` is `true`.  
+	 * This must be greater than `0`.
+	 * 
+	 * Defaults to `1000`.
+	 */
+	followIntervalMs?: number,
+
+	/**
+	 * An `AbortSignal` that allows the read operation to be aborted.
+	 * 
+	 * When aborted, the stream enters an errored state, all subsequent read operations fail,
+	 * and the underlying file resources are released instantly.
+	 */
+	signal?: AbortSignal,
+}
+
+export type AndroidOpenReadDelimitedRecordsStreamItem = {
+
+	/**
+	 * The decoded fields of the current record.
+	 * 
+	 * If the `csv` option is `false`, this always contains exactly one element, the whole record excluding the delimiter.  
+	 * If the `csv` option is `true`, quoted fields are unquoted and `""` in them is unescaped to `"`.
+	 */
+	fields: string[],
+
+	/**
+	 * Indicates whether the current record ends with the delimiter, or a line break (`"\n"` or `"\r\n"`) if the `csv` option is `true`.
+	 * 
+	 * This value is `false`
+	 * if the current record is last and the file does not end with them.
+	 */
+	terminated: boolean,
+}
+
+/**
+ * Options of `AndroidFs.openReadDelimitedRecordsStream`
+ */
+export type AndroidOpenReadDelimitedRecordsStreamOptions = {
+
+	/**
+	 * The delimiter that separates records.  
+	 * If the `csv` option is `true`, this instead separates fields, and records are separated by line breaks.
+	 * 
+	 * A string is converted into the bytes of the `encoding` in the backend, 
+	 * and a `Uint8Array` or an array of bytes is used as is, such as `[0x00]` for NUL or `[0x1E]` for the record separator.  
+	 * If the `csv` option is `true`, this must be a single ASCII character such as `","`, `"\t"` or `";"`.
+	 * 
+	 * Defaults to `","` if the `csv` option is `true`, otherwise `"\n"`.
+	 */
+	delimiter?: string | Uint8Array | number[],
+
+	/**
+	 * If `true`, each record is parsed as [RFC 4180](https://www.rfc-editor.org/rfc/rfc4180) CSV into fields.  
+	 * Fields may be quoted with `"`, and quoted fields may contain delimiters and line breaks.  
+	 * As extensions to RFC 4180, `"\n"` is also accepted as a line break, 
+	 * and characters following a closing quote are kept in the field as is.  
+	 * An unterminated quoted field throws an error.
+	 * 
+	 * This is not supported for `"iso-2022-jp"`.
+	 * 
+	 * Defaults to `false`.
+	 */
+	csv?: boolean,
+
+	/**
+	 * Text encoding label for decoder, such as `"utf-8"`, `"shift_jis"`, `"utf-16le"`. 
+	 *  
+	 * This is passed to [`TextDecoder constructor`](https://developer.mozilla.org/en-US/docs/Web/API/TextDecoder/TextDecoder).
+	 * See: [the available encodings](https://developer.mozilla.org/ja/docs/Web/API/Encoding_API/Encodings).
+	 * 
+	 * Defaults to `"utf-8"`.
+	 */
+	encoding?: string,
+
+	/**
+	 * Indicates whether decoding errors should be treated as fatal.
+	 *
+	 * - `false`: Invalid byte sequences are replaced with U+FFFD (`�`) and decoding continues.
+	 * - `true`: A `TypeError` is thrown when an invalid byte sequence is encountered.
+	 *
+	 * This is passed to [`TextDecoder constructor`](https://developer.mozilla.org/en-US/docs/Web/API/TextDecoder/TextDecoder).
+	 *
+	 * Defaults to `false`.
+	 */
+	fatal?: boolean,
+
+	/**
+	 * Indicates whether to ignore a leading BOM (Byte Order Mark).
+	 *
+	 * - `false`: A leading BOM is automatically stripped from the decoded result.
+	 * - `true`: A leading BOM is preserved and treated as a normal character.
+	 *
+	 * Defaults to `false`.
+	 */
+	ignoreBOM?: boolean,
+
+	/**
+	 * The buffer size, in bytes, used when sending data from the backend to the frontend.
+	 * 
+	 * IPC calls are relatively expensive, 
+	 * so larger buffer sizes are generally more efficient. 
+	 * But if it is too large, the UI may freeze or run out of memory.
+	 * 
+	 * This value is not guaranteed to be strictly respected. 
+	 * If a single record exceeds this size, 
 	 * more bytes may be sent in a single IPC transmission.
 	 * 
 	 * Defaults to `524288` (512 KiB).
@@ -514,13 +1608,13 @@ export type AndroidOpenReadTextFileLinesStreamOptions = {
 	bufferByteLength?: number,
 
 	/**
-	 * The maximum byte length of a line before decoding, excluding line break characters and an initial BOM (if present). 
-	 * If a line exceeds this limit, an error is thrown. 
-	 * This prevents OOM errors when reading minified files or binaries.
+	 * The maximum byte length of a record before decoding, excluding the terminating delimiter or line break and an initial BOM (if present). 
+	 * If a record exceeds this limit, an error is thrown. 
+	 * This prevents OOM errors when reading files without the delimiter or with an unterminated quoted field.
 	 * 
 	 * Defaults to `0` (unlimited).
 	 */
-	maxLineByteLength?: number,
+	maxRecordByteLength?: number,
 
 	/**
 	 * An `AbortSignal` that allows the read operation to be aborted.
@@ -1557,6 +2651,36 @@ export class AndroidFs {
 		})
 	}
 
+	/**
+	 * Gets the total byte length, the number of files and directories, 
+	 * and the breakdown by MIME type of the files under the specified directory.  
+	 * The directory itself is not counted.
+	 * 
+	 * The directory tree is traversed on the native side page by page, 
+	 * so only the result is passed over IPC.
+	 * 
+	 * @param uri - The URI or path of the target directory.
+	 * @param options - Optional settings: `maxDepth`. See `AndroidGetDirStatsOptions` for detailed descriptions of each item.
+	 * 
+	 * @returns A Promise that resolves to the statistics of the directory.
+	 * @throws The Promise will be rejected with an error, if the specified entry does not exist, if the entry is not a directory, or if the read permission is missing.
+	 * 
+	 * @see [AndroidFs::dir_stats](https://docs.rs/tauri-plugin-android-fs/latest/tauri_plugin_android_fs/api/api_async/struct.AndroidFs.html#method.dir_stats)
+	 * @since 28.2.0
+	 */
+	public static async getDirStats(
+		uri: AndroidFsUri | FsPath,
+		options?: AndroidGetDirStatsOptions
+	): Promise<AndroidDirStats> {
+
+		return await invoke('plugin:android-fs|get_dir_stats', {
+			uri: mapFsPathForInput(uri),
+			options: {
+				maxDepth: options?.maxDepth ?? null,
+			},
+		})
+	}
+
 	/**
 	 * Gets metadata of the specified file or directory.  
 	 * 
@@ -2334,6 +3458,64 @@ export class AndroidFs {
 		}
 	}
 
+	/**
+	 * Opens the file with read-only mode and resolves to a handle that can read from any position.  
+	 * 
+	 * Unlike `AndroidFs.openReadFileStream`, this can jump around the file without reopening it, 
+	 * such as for media parsers and archive readers.
+	 * 
+	 * The returned handle must always be released by the caller with `close`.
+	 * Failure to do so may cause file resource leaks.
+	 * 
+	 * @param uri - The URI or path of the file to read. 
+	 * 
+	 * @returns A Promise that resolves to an `AndroidReadFileHandle` backed by the file opened in read-only mode. This handle has a one-to-one correspondence with the file descriptor.
+	 * @throws The Promise will be rejected with an error, if the specified entry does not exist, if the entry is a directory, or if the read permission is missing.
+	 * 
+	 * @see [AndroidFs::open_file_readable](https://docs.rs/tauri-plugin-android-fs/latest/tauri_plugin_android_fs/api/api_async/struct.AndroidFs.html#method.open_file_readable)
+	 * @since 28.2.0
+	 */
+	public static async openReadFileHandle(
+		uri: AndroidFsUri | FsPath,
+	): Promise<AndroidReadFileHandle> {
+
+		const { open, read, readAt, seek, close } = await resolveReadFileStreamEvents(
+			"plugin:android-fs|open_read_file_stream",
+			mapFsPathForInput(uri),
+		)
+
+		try {
+			// [position (8 bytes)][has byteLength flag (1 byte)][byteLength (8 bytes)]
+			const info = await open()
+			let position = trySafeU64FromBytes(info.subarray(0, 8), "bigEndian")
+			const byteLength = numToFlag(info[8])
+				? trySafeU64FromBytes(info.subarray(9, 17), "bigEndian")
+				: null
+
+			return {
+				byteLength,
+				get position() {
+					return position
+				},
+				read: async (len) => {
+					const data = await read(len)
+					position += data?.byteLength ?? 0
+					return data
+				},
+				readAt: (offset, len) => readAt(offset, len),
+				seek: async (offset, whence) => {
+					position = await seek(offset, whence ?? "start")
+					return position
+				},
+				close: () => close(),
+			}
+		}
+		catch (e) {
+			await close().catch(() => { })
+			throw e
+		}
+	}
+
 	/**
 	 * Opens the file with read-only mode and resolves to a `ReadableStream` of text lines. 
 	 *  
@@ -2349,10 +3531,10 @@ export class AndroidFs {
 	 * - When the provided AbortSignal fires an abort event.
 	 * 
 	 * @param uri - The URI or path of the file to read. 
-	 * @param options - Optional settings: `encoding`, `fatal`, `ignoreBOM`, `maxLineByteLength`, `bufferByteLength`, `signal`. See `AndroidOpenReadTextFileLinesStreamOptions` for detailed descriptions of each item.
+	 * @param options - Optional settings: `encoding`, `fatal`, `ignoreBOM`, `maxLineByteLength`, `bufferByteLength`, `startLine`, `follow`, `followIntervalMs`, `signal`. See `AndroidOpenReadTextFileLinesStreamOptions` for detailed descriptions of each item.
 	 * 
 	 * @returns A Promise that resolves to a `ReadableStream<AndroidOpenReadTextFileLinesStreamItem>` backed by the file opened in read-only mode. This stream has a one-to-one correspondence with the file descriptor.
-	 * @throws The Promise will be rejected with an error, if the specified entry does not exist, if the entry is a directory, or if the read permission is missing.
+	 * @throws The Promise will be rejected with an error, if the specified entry does not exist, if the entry is a directory, if the read permission is missing, or if `followIntervalMs` is `0` while `follow` is `true`.
 	 * 
 	 * @see [AndroidFs::open_file_readable](https://docs.rs/tauri-plugin-android-fs/latest/tauri_plugin_android_fs/api/api_async/struct.AndroidFs.html#method.open_file_readable)
 	 * @since 25.1.0
@@ -2368,6 +3550,11 @@ export class AndroidFs {
 		const label = mapEncodingLabelForInput(options?.encoding)
 		const fatal = options?.fatal ?? false
 		const ignoreBOM = options?.ignoreBOM ?? false
+		const followIntervalMs = (options?.follow ?? false) ? (options?.followIntervalMs ?? 1000) : null
+		const startLine = options?.startLine ?? 0
+		if (!Number.isSafeInteger(startLine) || startLine < 0) {
+			throw new Error("Invalid startLine: expected a safe unsigned integer")
+		}
 		const { open, read, close } = await resolveReadFileStreamEvents(
 			"plugin:android-fs|open_read_text_file_lines_stream",
 			mapFsPathForInput(uri),
@@ -2375,7 +3562,7 @@ export class AndroidFs {
 		throwIfAborted(options?.signal)
 
 		try {
-			await open({ label, maxLineByteLength, ignoreBOM })
+			await open({ label, maxLineByteLength, ignoreBOM, followIntervalMs, startLine })
 			return createTextLinesReadableStream(
 				{
 					read: () => read(bufferByteLength),
@@ -2391,9 +3578,69 @@ export class AndroidFs {
 		}
 	}
 
+	/**
+	 * Opens the file with read-only mode and resolves to a `ReadableStream` of records separated by the delimiter,
+	 * such as NUL-separated text or CSV.
+	 *  
+	 * The stream yields decoded records one by one.   
+	 * See: `AndroidOpenReadDelimitedRecordsStreamItem`.
+	 * 
+	 * The returned `ReadableStream` must always be released by the caller.
+	 * Failure to do so may cause file resource leaks.
+	 * The returned ReadableStream is released in the following cases:
+	 * - When the ReadableStream or its Reader is canceled. 
+	 * - When the ReadableStream's Reader has been fully read. 
+	 * - When the ReadableStream's Reader's read operation ends with an error. 
+	 * - When the provided AbortSignal fires an abort event.
+	 * 
+	 * @param uri - The URI or path of the file to read. 
+	 * @param options - Optional settings: `delimiter`, `csv`, `encoding`, `fatal`, `ignoreBOM`, `maxRecordByteLength`, `bufferByteLength`, `signal`. See `AndroidOpenReadDelimitedRecordsStreamOptions` for detailed descriptions of each item.
+	 * 
+	 * @returns A Promise that resolves to a `ReadableStream<AndroidOpenReadDelimitedRecordsStreamItem>` backed by the file opened in read-only mode. This stream has a one-to-one correspondence with the file descriptor.
+	 * @throws The Promise will be rejected with an error, if the specified entry does not exist, if the entry is a directory, if the read permission is missing, or if the delimiter is invalid.
+	 * 
+	 * @see [AndroidFs::open_file_readable](https://docs.rs/tauri-plugin-android-fs/latest/tauri_plugin_android_fs/api/api_async/struct.AndroidFs.html#method.open_file_readable)
+	 * @since 28.2.0
+	 */
+	public static async openReadDelimitedRecordsStream(
+		uri: AndroidFsUri | FsPath,
+		options?: AndroidOpenReadDelimitedRecordsStreamOptions,
+	): Promise<ReadableStream<AndroidOpenReadDelimitedRecordsStreamItem>> {
+
+		throwIfAborted(options?.signal)
+		const csv = options?.csv ?? false
+		const delimiter = mapRecordDelimiterForInput(options?.delimiter ?? (csv ? "," : "\n"))
+		const maxRecordByteLength = mapMaxRecordByteLength(options?.maxRecordByteLength)
+		const bufferByteLength = mapBufferByteLengthForInput(options?.bufferByteLength)
+		const label = mapEncodingLabelForInput(options?.encoding)
+		const fatal = options?.fatal ?? false
+		const ignoreBOM = options?.ignoreBOM ?? false
+		const { open, read, close } = await resolveReadFileStreamEvents(
+			"plugin:android-fs|open_read_delimited_records_stream",
+			mapFsPathForInput(uri),
+		)
+		throwIfAborted(options?.signal)
+
+		try {
+			await open({ label, delimiter, csv, maxRecordByteLength, ignoreBOM })
+			return createDelimitedRecordsReadableStream(
+				{
+					read: () => read(bufferByteLength),
+					release: close
+				},
+				{ label, fatal },
+				options?.signal
+			)
+		}
+		catch (e) {
+			await close().catch(() => { })
+			throw e
+		}
+	}
+
 	/**
 	 * Opens the file with write mode and resolves to a `WritableStream`.  
-	 * Existing content of the file will be truncated.  
+	 * Existing content of the file will be truncated, unless the `append` option is `true`.  
 	 * 
 	 * The returned `WritableStream` must always be released by the caller.
 	 * Failure to do so may cause file resource leaks.
@@ -2404,7 +3651,7 @@ export class AndroidFs {
 	 * - When the provided AbortSignal fires an abort event.
 	 * 
 	 * @param uri - The URI or path of the file to write to.
-	 * @param options - Optional settings: `bufferByteLength`, `signal`, `create`, `notification`. See `AndroidOpenWriteFileStreamOptions` for detailed descriptions of each item.
+	 * @param options - Optional settings: `bufferByteLength`, `signal`, `create`, `notification`, `atomic`, `append`. See `AndroidOpenWriteFileStreamOptions` for detailed descriptions of each item.
 	 * 
 	 * @returns A Promise that resolves to a `WritableStream<Uint8Array<ArrayBufferLike>>` backed by the file opened in write mode. This stream has a one-to-one correspondence with the file descriptor.
 	 *
@@ -2418,12 +3665,14 @@ export class AndroidFs {
 
 		throwIfAborted(options?.signal)
 		const create = options?.create ?? true
+		const atomic = options?.atomic ?? false
+		const append = options?.append ?? false
 		const notification = options?.notification ?? null
 		const bufferByteLength = mapBufferByteLengthForInput(options?.bufferByteLength)
 		const { open, write, close } = await resolveWriteFileStreamEvents(
 			"plugin:android-fs|open_write_file_stream",
 			mapFsPathForInput(uri),
-			{ create, notification }
+			{ create, notification, atomic, append }
 		)
 		throwIfAborted(options?.signal)
 
@@ -2448,6 +3697,175 @@ export class AndroidFs {
 		}
 	}
 
+	/**
+	 * Opens the file with write mode and resolves to a `WritableStream` of text.  
+	 * The text is written as UTF-8, or in the encoding specified by the `encoding` option.  
+	 * Existing content of the file will be truncated, unless the `append` option is `true`.  
+	 * 
+	 * The returned `WritableStream` must always be released by the caller.
+	 * Failure to do so may cause file resource leaks.
+	 * The returned WritableStream is released in the following cases:
+	 * - When the WritableStream or its Writer is closed. 
+	 * - When the WritableStream or its Writer is aborted. 
+	 * - When the WritableStream's Writer's write operation ends with an error. 
+	 * - When the provided AbortSignal fires an abort event.
+	 * 
+	 * @param uri - The URI or path of the file to write to.
+	 * @param options - Optional settings: `bufferLength`, `signal`, `create`, `notification`, `atomic`, `append`, `encoding`, `bom`, `lineEnding`. See `AndroidOpenWriteTextFileStreamOptions` for detailed descriptions of each item.
+	 * 
+	 * @returns A Promise that resolves to a `WritableStream<string>` backed by the file opened in write mode. This stream has a one-to-one correspondence with the file descriptor.
+	 *
+	 * @see [AndroidFs::open_file_writable](https://docs.rs/tauri-plugin-android-fs/latest/tauri_plugin_android_fs/api/api_async/struct.AndroidFs.html#method.open_file_writable)
+	 * @since 28.2.0
+	 */
+	public static async openWriteTextFileStream(
+		uri: AndroidFsUri | FsPath,
+		options?: AndroidOpenWriteTextFileStreamOptions
+	): Promise<WritableStream<string>> {
+
+		throwIfAborted(options?.signal)
+		const create = options?.create ?? true
+		const atomic = options?.atomic ?? false
+		const append = options?.append ?? false
+		const notification = options?.notification ?? null
+		const encoding = options?.encoding ?? null
+		const bom = options?.bom ?? false
+		const lineEnding = options?.lineEnding ?? null
+		const bufferLength = Math.max(1, Math.floor(options?.bufferLength ?? 262144))
+		const { open, write, close } = await resolveWriteFileStreamEvents(
+			"plugin:android-fs|open_write_file_stream",
+			mapFsPathForInput(uri),
+			{ create, notification, atomic, append, encoding, bom, lineEnding }
+		)
+		throwIfAborted(options?.signal)
+
+		try {
+			await open()
+			throwIfAborted(options?.signal)
+		}
+		catch (e) {
+			await close("Err").catch(() => { })
+			throw e
+		}
+
+		let buffer: string[] = []
+		let bufferedLength = 0
+		let released = false
+		let controller: WritableStreamDefaultController | null = null
+
+		const release = async (t: "Ok" | "Err") => {
+			if (released) return
+			released = true
+			options?.signal?.removeEventListener("abort", onAbort)
+			await close(t)
+		}
+		const onAbort = () => {
+			controller?.error(options?.signal?.reason ?? newAbortError())
+			release("Err").catch(() => { })
+		}
+		const flush = async () => {
+			if (buffer.length === 0) return
+			const chunk = buffer.join("")
+			buffer = []
+			bufferedLength = 0
+
+			try {
+				await write(chunk)
+			}
+			catch (e) {
+				await release("Err").catch(() => { })
+				throw e
+			}
+		}
+
+		options?.signal?.addEventListener("abort", onAbort, { once: true })
+
+		return new WritableStream<string>({
+			start: (c) => {
+				controller = c
+			},
+			write: async (chunk) => {
+				if (released) throw newAbortError()
+				buffer.push(chunk)
+				bufferedLength += chunk.length
+				if (bufferLength <= bufferedLength) {
+					await flush()
+				}
+			},
+			close: async () => {
+				if (released) throw newAbortError()
+				await flush()
+				await release("Ok")
+			},
+			abort: async () => {
+				buffer = []
+				await release("Err")
+			},
+		})
+	}
+
+	/**
+	 * Opens the file with write mode and resolves to a handle that can write at any position.  
+	 * Existing content of the file will be truncated.  
+	 * 
+	 * Unlike `AndroidFs.openWriteFileStream`, this can go back and patch the data already written, 
+	 * such as the size fields of a WAV or MP4 header, and can preallocate the file with `setLen`.
+	 * 
+	 * The returned handle must always be released by the caller with `close` or `abort`.
+	 * Failure to do so may cause file resource leaks.
+	 * 
+	 * @param uri - The URI or path of the file to write to.
+	 * @param options - Optional settings: `create`, `notification`, `atomic`. See `AndroidOpenWriteFileHandleOptions` for detailed descriptions of each item.
+	 * 
+	 * @returns A Promise that resolves to an `AndroidWriteFileHandle` backed by the file opened in write mode. This handle has a one-to-one correspondence with the file descriptor.
+	 *
+	 * @see [AndroidFs::open_file_writable](https://docs.rs/tauri-plugin-android-fs/latest/tauri_plugin_android_fs/api/api_async/struct.AndroidFs.html#method.open_file_writable)
+	 * @since 28.2.0
+	 */
+	public static async openWriteFileHandle(
+		uri: AndroidFsUri | FsPath,
+		options?: AndroidOpenWriteFileHandleOptions
+	): Promise<AndroidWriteFileHandle> {
+
+		const create = options?.create ?? true
+		const atomic = options?.atomic ?? false
+		const notification = options?.notification ?? null
+		const { open, write, writeAt, seek, setLen, close } = await resolveWriteFileStreamEvents(
+			"plugin:android-fs|open_write_file_stream",
+			mapFsPathForInput(uri),
+			{ create, notification, atomic, append: false }
+		)
+
+		try {
+			await open()
+
+			// ファイルは切り詰めて開かれるので、最初の位置は 0
+			let position = 0
+
+			return {
+				get position() {
+					return position
+				},
+				write: async (data) => {
+					await write(data)
+					position += data.byteLength
+				},
+				writeAt: (offset, data) => writeAt(offset, data),
+				seek: async (offset, whence) => {
+					position = await seek(offset, whence ?? "start")
+					return position
+				},
+				setLen: (len) => setLen(len),
+				close: () => close("Ok"),
+				abort: () => close("Err"),
+			}
+		}
+		catch (e) {
+			await close("Err").catch(() => { })
+			throw e
+		}
+	}
+
 	/**
 	 * Forcibly disposes of all file streams.
 	 *
@@ -2531,68 +3949,199 @@ export class AndroidFs {
 	 * @returns A Promise that resolves to a string containing the entire file data as a data URL.
 	 * @throws The Promise will be rejected with an error, if the specified entry does not exist, if the entry is a directory, or if the read permission is missing.
 	 *
-	 * @see [AndroidFs::read_file](https://docs.rs/tauri-plugin-android-fs/latest/tauri_plugin_android_fs/api/api_async/struct.AndroidFs.html#method.read_file)
-	 * @since 26.1.0
+	 * @see [AndroidFs::read_file](https://docs.rs/tauri-plugin-android-fs/latest/tauri_plugin_android_fs/api/api_async/struct.AndroidFs.html#method.read_file)
+	 * @since 26.1.0
+	 */
+	public static async readFileAsDataURL(
+		uri: AndroidFsUri | FsPath,
+		options?: AndroidReadFileAsDataUrlOptions
+	): Promise<string> {
+
+		const mimeType = options?.mimeType ?? null
+		const dataUrl = await invoke<ArrayBuffer>('plugin:android-fs|read_file_as_data_url', {
+			uri: mapFsPathForInput(uri),
+			mimeType
+		})
+
+		return decodeUtf8(dataUrl)
+	}
+
+	/**
+	 * Reads the entire contents of the specified file and decodes it as text.
+	 * 
+	 * For large files, consider using `AndroidFs.openReadFileStream` with [`TextDecoderStream`](https://developer.mozilla.org/ja/docs/Web/API/TextDecoderStream) or [`TextDecoder`](https://developer.mozilla.org/en-US/docs/Web/API/TextDecoder/TextDecoder).
+	 *
+	 * @param uri - The URI or path of the target file.
+	 * @param options - Optional settings: `encoding`, `fatal`, `ignoreBOM`.
+	 * @param options.encoding - The text encoding label for decoder, such as `"utf-8"`, `"shift_jis"`, `"iso-8859-2"`. See: [the available encodings](https://developer.mozilla.org/ja/docs/Web/API/Encoding_API/Encodings). Defaults to `"utf-8"`.
+	 * @param options.fatal - Indicates whether an error is thrown when an invalid byte sequence is encountered. If `false`, invalid byte sequences are replaced with U+FFFD (`�`) and decoding continues. Defaults to `false`.
+	 * @param options.ignoreBOM - Indicates whether a leading BOM is preserved and treated as a normal character. Defaults to `false`.
+	 * 
+	 * @returns A Promise that resolves to the decoded text content of the file.
+	 * @throws The Promise will be rejected with an error, if the specified entry does not exist, if the entry is a directory, if the read permission is missing, or if decoding fails.
+	 *
+	 * @see [AndroidFs::open_file_readable](https://docs.rs/tauri-plugin-android-fs/latest/tauri_plugin_android_fs/api/api_async/struct.AndroidFs.html#method.open_file_readable)
+	 * @since 25.1.0
+	 */
+	public static async readTextFile(
+		uri: AndroidFsUri | FsPath,
+		options?: AndroidReadTextFileOptions
+	): Promise<string> {
+
+		const bytes = await invoke<ArrayBuffer>('plugin:android-fs|read_text_file', {
+			uri: mapFsPathForInput(uri),
+		})
+		const decoder = new TextDecoder(
+			options?.encoding ?? "utf-8",
+			{
+				fatal: options?.fatal,
+				ignoreBOM: options?.ignoreBOM
+			}
+		)
+
+		return decoder.decode(bytes)
+	}
+
+	/**
+	 * Reads the last lines of the specified text file, such as the tail of a log file.
+	 * 
+	 * The backend seeks to the end of the file and scans backwards for line breaks, 
+	 * so only the last `n` lines are read and sent to the frontend even if the file is very large.  
+	 * A line break at the end of the file does not start a new empty line.
+	 *
+	 * @param uri - The URI or path of the target file.
+	 * @param n - The maximum number of lines to read.
+	 * @param options - Optional settings: `encoding`, `fatal`, `ignoreBOM`. See `AndroidReadLastLinesOptions` for detailed descriptions of each item.
+	 * 
+	 * @returns A Promise that resolves to at most `n` lines in order from oldest to newest. Fewer lines are returned if the file has fewer lines.
+	 * @throws The Promise will be rejected with an error, if the specified entry does not exist, if the entry is a directory, if the read permission is missing, or if decoding fails.
+	 *
+	 * @see [AndroidFs::read_last_lines](https://docs.rs/tauri-plugin-android-fs/latest/tauri_plugin_android_fs/api/api_async/struct.AndroidFs.html#method.read_last_lines)
+	 * @since 28.2.0
+	 */
+	public static async readLastLines(
+		uri: AndroidFsUri | FsPath,
+		n: number,
+		options?: AndroidReadLastLinesOptions
+	): Promise<AndroidOpenReadTextFileLinesStreamItem[]> {
+
+		if (!Number.isSafeInteger(n) || n < 0) {
+			throw new Error("Invalid n: expected a safe unsigned integer")
+		}
+		const label = mapEncodingLabelForInput(options?.encoding)
+		const fatal = options?.fatal ?? false
+		const ignoreBom = options?.ignoreBOM ?? false
+		let bytes: Uint8Array<ArrayBuffer> | null = new Uint8Array(
+			await invoke<ArrayBuffer>('plugin:android-fs|read_last_lines', {
+				uri: mapFsPathForInput(uri),
+				n,
+				label,
+				ignoreBom,
+			})
+		)
+
+		const stream = createTextLinesReadableStream(
+			{
+				read: async () => {
+					const b = bytes
+					bytes = null
+					return b
+				}
+			},
+			{ label, fatal }
+		)
+
+		const lines: AndroidOpenReadTextFileLinesStreamItem[] = []
+		const reader = stream.getReader()
+		while (true) {
+			const { done, value } = await reader.read()
+			if (done) break
+			lines.push(value)
+		}
+		return lines
+	}
+
+	/**
+	 * Scans the specified text file once and builds an index of the byte offsets of every `interval` lines, 
+	 * such as for a virtualized text view of a huge file.
+	 * 
+	 * The index is stored as a sidecar file in the app's cache directory, 
+	 * replacing the one previously built for the same file. 
+	 * Then `AndroidFs.openReadTextFileLinesStream` with the `startLine` option uses it 
+	 * to jump to the line without reading the file from the beginning.  
+	 * The index is ignored once the file is modified, so build it again in that case.  
+	 * Since it is in the cache directory, the system may delete it when disk space is needed.
+	 * 
+	 * A line break at the end of the file does not start a new empty line.
+	 *
+	 * @param uri - The URI or path of the target file.
+	 * @param options - Optional settings: `interval`, `encoding`. See `AndroidBuildLineIndexOptions` for detailed descriptions of each item.
+	 * 
+	 * @returns A Promise that resolves to the number of lines in the file.
+	 * @throws The Promise will be rejected with an error, if the specified entry does not exist, if the entry is a directory, if the read permission is missing, or if `interval` is `0`.
+	 *
+	 * @see [AndroidFs::build_line_index](https://docs.rs/tauri-plugin-android-fs/latest/tauri_plugin_android_fs/api/api_async/struct.AndroidFs.html#method.build_line_index)
+	 * @since 28.2.0
 	 */
-	public static async readFileAsDataURL(
+	public static async buildLineIndex(
 		uri: AndroidFsUri | FsPath,
-		options?: AndroidReadFileAsDataUrlOptions
-	): Promise<string> {
+		options?: AndroidBuildLineIndexOptions
+	): Promise<number> {
 
-		const mimeType = options?.mimeType ?? null
-		const dataUrl = await invoke<ArrayBuffer>('plugin:android-fs|read_file_as_data_url', {
+		const interval = options?.interval ?? 1000
+		if (!Number.isSafeInteger(interval) || interval < 0) {
+			throw new Error("Invalid interval: expected a safe unsigned integer")
+		}
+		const label = mapEncodingLabelForInput(options?.encoding)
+
+		return await invoke<number>('plugin:android-fs|build_line_index', {
 			uri: mapFsPathForInput(uri),
-			mimeType
+			interval,
+			label,
 		})
-
-		return decodeUtf8(dataUrl)
 	}
 
 	/**
-	 * Reads the entire contents of the specified file and decodes it as text.
+	 * Computes the hash of the contents of the specified file.
+	 * 
+	 * The file is read in chunks on the backend, 
+	 * so the contents are not loaded into memory or sent to the frontend.
 	 * 
-	 * For large files, consider using `AndroidFs.openReadFileStream` with [`TextDecoderStream`](https://developer.mozilla.org/ja/docs/Web/API/TextDecoderStream) or [`TextDecoder`](https://developer.mozilla.org/en-US/docs/Web/API/TextDecoder/TextDecoder).
-	 *
 	 * @param uri - The URI or path of the target file.
-	 * @param options - Optional settings: `encoding`, `fatal`, `ignoreBOM`.
-	 * @param options.encoding - The text encoding label for decoder, such as `"utf-8"`, `"shift_jis"`, `"iso-8859-2"`. See: [the available encodings](https://developer.mozilla.org/ja/docs/Web/API/Encoding_API/Encodings). Defaults to `"utf-8"`.
-	 * @param options.fatal - Indicates whether an error is thrown when an invalid byte sequence is encountered. If `false`, invalid byte sequences are replaced with U+FFFD (`�`) and decoding continues. Defaults to `false`.
-	 * @param options.ignoreBOM - Indicates whether a leading BOM is preserved and treated as a normal character. Defaults to `false`.
+	 * @param algorithm - The hash algorithm: `"SHA-256"`, `"SHA-1"`, `"MD5"`, `"CRC32"` or `"XXH64"`.
+	 * @param options - Optional settings: `notification`. See `AndroidHashFileOptions` for detailed descriptions of each item.
 	 * 
-	 * @returns A Promise that resolves to the decoded text content of the file.
-	 * @throws The Promise will be rejected with an error, if the specified entry does not exist, if the entry is a directory, if the read permission is missing, or if decoding fails.
+	 * @returns A Promise that resolves to the digest as a lowercase hexadecimal string, such as `"cbf43926"`.
+	 * @throws The Promise will be rejected with an error, if the specified entry does not exist, if the entry is a directory, or if the read permission is missing.
 	 *
-	 * @see [AndroidFs::open_file_readable](https://docs.rs/tauri-plugin-android-fs/latest/tauri_plugin_android_fs/api/api_async/struct.AndroidFs.html#method.open_file_readable)
-	 * @since 25.1.0
+	 * @see [AndroidFs::hash](https://docs.rs/tauri-plugin-android-fs/latest/tauri_plugin_android_fs/api/api_async/struct.AndroidFs.html#method.hash)
+	 * @since 28.2.0
 	 */
-	public static async readTextFile(
+	public static async hashFile(
 		uri: AndroidFsUri | FsPath,
-		options?: AndroidReadTextFileOptions
+		algorithm: AndroidHashAlgorithm,
+		options?: AndroidHashFileOptions
 	): Promise<string> {
 
-		const bytes = await invoke<ArrayBuffer>('plugin:android-fs|read_text_file', {
+		const n = options?.notification
+		const notification = n != null ? { ...n, forceIndeterminateProgressBar: true } : null
+
+		return await invoke<string>('plugin:android-fs|hash_file', {
 			uri: mapFsPathForInput(uri),
+			algorithm,
+			notification,
 		})
-		const decoder = new TextDecoder(
-			options?.encoding ?? "utf-8",
-			{
-				fatal: options?.fatal,
-				ignoreBOM: options?.ignoreBOM
-			}
-		)
-
-		return decoder.decode(bytes)
 	}
 
 	/**
 	 * Writes bytes to the file.   
-	 * Existing content of the file will be truncated.  
+	 * Existing content of the file will be truncated, unless the `append` option is `true`.  
 	 * 
 	 * If you want to write `ReadableStream`, use `AndroidFs.openWriteFileStream`.
 	 * 
 	 * @param uri - The URI or path of the file to write to. 
 	 * @param data - The bytes to write.
-	 * @param options - Optional settings: `create`, `notification`. See `AndroidWriteFileOptions` for detailed descriptions of each item.
+	 * @param options - Optional settings: `create`, `notification`, `atomic`, `append`. See `AndroidWriteFileOptions` for detailed descriptions of each item.
 	 * 
 	 * @returns A Promise that resolves when the data has been successfully written.
 	 * 
@@ -2608,10 +4157,12 @@ export class AndroidFs {
 		const n = options?.notification
 		const notification = n != null ? { ...n, forceIndeterminateProgressBar: true } : null
 		const create = options?.create ?? true
+		const atomic = options?.atomic ?? false
+		const append = options?.append ?? false
 		const { open, write, close } = await resolveWriteFileStreamEvents(
 			"plugin:android-fs|write_file",
 			mapFsPathForInput(uri),
-			{ create, notification }
+			{ create, notification, atomic, append }
 		)
 
 		try {
@@ -2626,12 +4177,12 @@ export class AndroidFs {
 	}
 
 	/**
-	 * Writes text data to the file as UTF-8.   
-	 * Existing content of the file will be truncated.   
+	 * Writes text data to the file as UTF-8, or in the encoding specified by the `encoding` option.   
+	 * Existing content of the file will be truncated, unless the `append` option is `true`.   
 	 * 
 	 * @param uri - The URI or path of the file to write to. If the path is specified and the entry does not exist, a new file will be created.
 	 * @param data - The text data to write.
-	 * @param options - Optional settings: `create`, `notification`. See `AndroidWriteTextFileOptions` for detailed descriptions of each item.
+	 * @param options - Optional settings: `create`, `notification`, `atomic`, `append`, `encoding`, `bom`, `lineEnding`. See `AndroidWriteTextFileOptions` for detailed descriptions of each item.
 	 *
 	 * @returns A Promise that resolves when the data has been successfully written.
 	 * 
@@ -2647,10 +4198,15 @@ export class AndroidFs {
 		const n = options?.notification
 		const notification = n != null ? { ...n, forceIndeterminateProgressBar: true } : null
 		const create = options?.create ?? true
+		const atomic = options?.atomic ?? false
+		const append = options?.append ?? false
+		const encoding = options?.encoding ?? null
+		const bom = options?.bom ?? false
+		const lineEnding = options?.lineEnding ?? null
 		const { open, write, close } = await resolveWriteFileStreamEvents(
 			"plugin:android-fs|write_text_file",
 			mapFsPathForInput(uri),
-			{ create, notification }
+			{ create, notification, atomic, append, encoding, bom, lineEnding }
 		)
 
 		try {
@@ -2694,6 +4250,146 @@ export class AndroidFs {
 		})
 	}
 
+	/**
+	 * Creates a zip archive of the specified files and directories, and writes it to the destination file.  
+	 * Existing content of the destination file will be truncated.
+	 * 
+	 * Each source is stored under its own name at the root of the archive, and directories are stored recursively.
+	 * The last modified date of each entry is preserved.
+	 * The archive is written on the backend in chunks, so its contents are not loaded into memory or sent to the frontend.
+	 * 
+	 * @param srcUris - The URIs or paths of the files and directories to archive.
+	 * @param destUri - The URI or path of the destination file, such as a URI from `AndroidFs.showSaveFilePicker`. If this is inside a source directory, it is excluded from the archive.
+	 * @param options - Optional settings: `compression`, `create`, `notification`. See `AndroidCreateZipOptions` for detailed descriptions of each item.
+	 * 
+	 * @returns A Promise that resolves when the archive is written.
+	 * @throws The Promise will be rejected with an error, if a source does not exist, if two entries have the same path in the archive, or if permissions are missing.
+	 * 
+	 * @see [AndroidFs::create_zip](https://docs.rs/tauri-plugin-android-fs/latest/tauri_plugin_android_fs/api/api_async/struct.AndroidFs.html#method.create_zip)
+	 * @since 28.2.0
+	 */
+	public static async createZip(
+		srcUris: (AndroidFsUri | FsPath)[],
+		destUri: AndroidFsUri | FsPath,
+		options?: AndroidCreateZipOptions,
+	): Promise<void> {
+
+		const create = options?.create ?? true
+		const notification = options?.notification ?? null
+
+		return await invoke('plugin:android-fs|create_zip', {
+			srcUris: srcUris.map(uri => mapFsPathForInput(uri)),
+			destUri: mapFsPathForInput(destUri),
+			options: {
+				compression: options?.compression ?? "Deflated",
+				create,
+			},
+			notification,
+		})
+	}
+
+	/**
+	 * Extracts the zip archive into the destination directory.
+	 * 
+	 * Existing entries are never overwritten. If a file with the same name already exists, the file provider may append a sequential number to the new one.
+	 * Entries whose paths point outside the destination directory, such as `"../evil.sh"`, are not extracted and result in `"Failed"`.
+	 * The archive is read on the backend in chunks, so its contents are not loaded into memory or sent to the frontend.
+	 * 
+	 * @param srcUri - The URI or path of the zip archive.
+	 * @param destUri - The URI or path of the destination directory.
+	 * @param options - Optional settings: `include`, `exclude`, `maxTotalLen`, `notification`. See `AndroidExtractZipOptions` for detailed descriptions of each item.
+	 * 
+	 * @returns A Promise that resolves to the result of each entry, in the order recorded in the archive. Failures of individual entries do not stop the extraction.
+	 * @throws The Promise will be rejected with an error, if the archive is not a valid zip, if its total size exceeds `maxTotalLen`, or if permissions are missing.
+	 * 
+	 * @see [AndroidFs::extract_zip](https://docs.rs/tauri-plugin-android-fs/latest/tauri_plugin_android_fs/api/api_async/struct.AndroidFs.html#method.extract_zip)
+	 * @since 28.2.0
+	 */
+	public static async extractZip(
+		srcUri: AndroidFsUri | FsPath,
+		destUri: AndroidFsUri | FsPath,
+		options?: AndroidExtractZipOptions,
+	): Promise<AndroidExtractZipEntry[]> {
+
+		const notification = options?.notification ?? null
+
+		return await invoke<AndroidExtractZipEntry[]>('plugin:android-fs|extract_zip', {
+			srcUri: mapFsPathForInput(srcUri),
+			destUri: mapFsPathForInput(destUri),
+			options: {
+				include: options?.include ?? [],
+				exclude: options?.exclude ?? [],
+				maxTotalLen: options?.maxTotalLen === undefined ? 4 * 1024 * 1024 * 1024 : options.maxTotalLen,
+			},
+			notification,
+		})
+	}
+
+	/**
+	 * Makes the destination directory a mirror of the source directory, in one direction.
+	 * 
+	 * Entries are compared by name, and files are compared by byte length and last modified date, or by contents if `compareContents` is `true`. 
+	 * Files that exist only in the source are copied, and files that differ are overwritten. 
+	 * Entries that exist only in the destination are removed if `deleteExtras` is `true`.
+	 * 
+	 * @param srcUri - The URI or path of the source directory.
+	 * @param destUri - The URI or path of the destination directory. This must not be inside the source directory.
+	 * @param options - Optional settings: `compareContents`, `deleteExtras`, `dryRun`, `onProgress`. See `AndroidSyncDirOptions` for detailed descriptions of each item.
+	 * 
+	 * @returns A Promise that resolves to the performed actions in order, or the planned actions if `dryRun` is `true`.
+	 * @throws The Promise will be rejected with an error, if either directory does not exist, if permissions are missing, or if an action fails. In the last case, the actions before it have already been performed.
+	 * 
+	 * @see [AndroidFs::sync_dir](https://docs.rs/tauri-plugin-android-fs/latest/tauri_plugin_android_fs/api/api_async/struct.AndroidFs.html#method.sync_dir)
+	 * @since 28.2.0
+	 */
+	public static async syncDir(
+		srcUri: AndroidFsUri | FsPath,
+		destUri: AndroidFsUri | FsPath,
+		options?: AndroidSyncDirOptions,
+	): Promise<AndroidSyncDirAction[]> {
+
+		const dryRun = options?.dryRun ?? false
+		const onProgress = options?.onProgress
+
+		let received = 0
+		let total: number | null = null
+		let resolveAllReceived: () => void = () => { }
+		const allReceived = new Promise<void>(resolve => resolveAllReceived = resolve)
+
+		const channel = new Channel<AndroidSyncDirProgress>()
+		channel.onmessage = progress => {
+			received += 1
+			try {
+				onProgress?.(progress)
+			}
+			finally {
+				if (total !== null && total <= received) {
+					resolveAllReceived()
+				}
+			}
+		}
+
+		const actions = await invoke<AndroidSyncDirAction[]>('plugin:android-fs|sync_dir', {
+			srcUri: mapFsPathForInput(srcUri),
+			destUri: mapFsPathForInput(destUri),
+			options: {
+				compareContents: options?.compareContents ?? false,
+				deleteExtras: options?.deleteExtras ?? false,
+				dryRun,
+			},
+			onProgress: channel,
+		})
+
+		// チャンネルのメッセージはコマンドの結果より後に届くことがある
+		total = dryRun ? 0 : actions.length
+		if (total <= received) {
+			resolveAllReceived()
+		}
+		await allReceived
+
+		return actions
+	}
+
 	/**
 	 * Deletes the existing content and sets the file size to zero.
 	 * 
@@ -2736,112 +4432,475 @@ export class AndroidFs {
 	}
 
 	/**
-	 * Renames the specified directory and returns its new URI.
+	 * Renames the specified directory and returns its new URI.
+	 * 
+	 * **NOTE**:  
+	 * For URIs from the directory picker, all permissions are lost after this operation, including for the new URI.
+	 * 
+	 * @param uri - The URI of the directory to rename.
+	 * @param name - New name. If an entry with the same name already exists, a sequential number is appended to ensure uniqueness.
+	 * 
+	 * @returns A Promise that resolves to the new URI of the target directory.
+	 * @throws The Promise will be rejected with an error, if the entry does not exist, if the entry is not a directory, if write permission is missing, or if the file provider does not support rename.
+	 * 
+	 * @see [AndroidFs::rename](https://docs.rs/tauri-plugin-android-fs/latest/tauri_plugin_android_fs/api/api_async/struct.AndroidFs.html#method.rename)
+	 * @since 24.1.0
+	 */
+	public static async renameDir(
+		uri: AndroidFsUri,
+		name: string
+	): Promise<AndroidFsUri> {
+
+		return await invoke('plugin:android-fs|rename_dir', {
+			uri,
+			name
+		})
+	}
+
+	/**
+	 * Removes the specified file.
+	 * 
+	 * @param uri - The URI of the file to remove.
+	 * 
+	 * @returns A Promise that resolves when the removing is complete.
+	 * @throws The Promise will be rejected with an error, if the entry does not exist, if the entry is not a file, if write permission is missing, or if the file provider does not support removing.
+	 * 
+	 * @see [AndroidFs::remove_file](https://docs.rs/tauri-plugin-android-fs/latest/tauri_plugin_android_fs/api/api_async/struct.AndroidFs.html#method.remove_file)
+	 * @since 22.0.0
+	 */
+	public static async removeFile(uri: AndroidFsUri): Promise<void> {
+		return await invoke('plugin:android-fs|remove_file', { uri })
+	}
+
+	/**
+	 * Removes the specified directory and all of its contents recursively.
+	 * 
+	 * @param uri - The URI of the directory to remove.
+	 * 
+	 * @returns A Promise that resolves when the removing is complete.
+	 * @throws The Promise will be rejected with an error, if the entry does not exist, if the entry is not a directory, if write permission is missing, or if the file provider does not support removing.
+	 * 
+	 * @see [AndroidFs::remove_dir_all](https://docs.rs/tauri-plugin-android-fs/latest/tauri_plugin_android_fs/api/api_async/struct.AndroidFs.html#method.remove_dir_all)
+	 * @since 22.0.0
+	 */
+	public static async removeDirAll(uri: AndroidFsUri): Promise<void> {
+		return await invoke('plugin:android-fs|remove_dir_all', { uri })
+	}
+
+	/**
+	 * Removes the specified directory if empty.
+	 * 
+	 * @param uri - The URI of the directory to remove.
+	 * 
+	 * @returns A Promise that resolves when the removing is complete.
+	 * @throws The Promise will be rejected with an error, if the entry does not exist, if the entry is not an empty directory, if write permission is missing, or if the file provider does not support removing.
+	 * 
+	 * @see [AndroidFs::remove_dir](https://docs.rs/tauri-plugin-android-fs/latest/tauri_plugin_android_fs/api/api_async/struct.AndroidFs.html#method.remove_dir)
+	 * @since 22.0.0
+	 */
+	public static async removeEmptyDir(uri: AndroidFsUri): Promise<void> {
+		return await invoke('plugin:android-fs|remove_empty_dir', { uri })
+	}
+
+	/**
+	 * Retrieves metadata and URIs for the child files and subdirectories of the specified directory.
+	 * 
+	 * Sorting and filtering are done on the native side, 
+	 * so a sorted listing can be paged through with `offset` and `limit`.  
+	 * However, each page re-reads and sorts the whole directory, 
+	 * so this should not be used to page through large directories.
+	 * 
+	 * @param uri - The URI of the directory to read.
+	 * @param options - Optional settings: `offset`, `limit`, `sortBy`, `type`, `mimeTypes`, `nameContains`, `namePattern`. See `AndroidReadDirOptions` for detailed descriptions of each item.
+	 * @param options.offset - Number of entries to skip from the beginning. Defaults to `0`.
+	 * @param options.limit - Maximum number of entries to get. If omitted, all available entries starting from `offset` are returned.
+	 * 
+	 * @returns A Promise that resolves to an array of entries, each containing metadata and the URI of a file or directory. The order of the entries depends on the file provider, unless `sortBy` is specified.  
+	 * @throws The Promise will be rejected with an error, if the entry does not exist, if the entry is not a directory, if read permission is missing, or if `namePattern` is invalid.
+	 * 
+	 * @see [AndroidFs::read_dir_with_options_and_range](https://docs.rs/tauri-plugin-android-fs/latest/tauri_plugin_android_fs/api/api_async/struct.AndroidFs.html#method.read_dir_with_options_and_range)
+	 * @since 22.0.0
+	 */
+	public static async readDir(
+		uri: AndroidFsUri,
+		options?: AndroidReadDirOptions
+	): Promise<AndroidEntryMetadataWithUri[]> {
+
+		const offset = options?.offset ?? null
+		const limit = options?.limit ?? null
+		const entries = await invoke<AndroidEntryMetadataWithUriInner[]>('plugin:android-fs|read_dir', {
+			uri,
+			offset,
+			limit,
+			options: {
+				sortBy: options?.sortBy?.map(({ key, descending }) => ({ key, descending: descending ?? false })) ?? [],
+				entryType: options?.type ?? null,
+				mimeTypes: options?.mimeTypes ?? [],
+				nameContains: options?.nameContains ?? null,
+				namePattern: options?.namePattern ?? null,
+			},
+		})
+
+		const buffer: AndroidEntryMetadataWithUri[] = new Array(entries.length)
+
+		for (let i = 0; i < entries.length; i++) {
+			const e = entries[i];
+			const lastModified = new Date(e.lastModified);
+
+			buffer[i] = e.type === "Dir"
+				? { type: "Dir", name: e.name, uri: e.uri, lastModified }
+				: { type: "File", name: e.name, uri: e.uri, lastModified, byteLength: e.byteLength, mimeType: e.mimeType };
+		}
+
+		return buffer
+	}
+
+	/**
+	 * Opens the directory and resolves to a `ReadableStream` of its child files and subdirectories.  
+	 * 
+	 * Unlike `AndroidFs.readDir`, entries are read page by page when the stream is pulled,
+	 * so this can be used for a directory with a large number of entries.
+	 * 
+	 * The returned `ReadableStream` must always be released by the caller.
+	 * Failure to do so may cause resource leaks.
+	 * The returned ReadableStream is released in the following cases:
+	 * - When the ReadableStream or its Reader is canceled. 
+	 * - When the ReadableStream's Reader has been fully read. 
+	 * - When the ReadableStream's Reader's read operation ends with an error. 
+	 * - When the provided AbortSignal fires an abort event.
+	 * 
+	 * @param uri - The URI or path of the directory to read.
+	 * @param options - Optional settings: `pageSize`, `signal`. See `AndroidOpenReadDirStreamOptions` for detailed descriptions of each item.
+	 * 
+	 * @returns A Promise that resolves to a `ReadableStream<AndroidEntryMetadataWithUri>`. The order of the entries depends on the file provider.
+	 * @throws The Promise will be rejected with an error, if `pageSize` is invalid. Errors such as a missing directory or read permission are reported by the stream.
+	 * 
+	 * @see [AndroidFs::read_dir_paged](https://docs.rs/tauri-plugin-android-fs/latest/tauri_plugin_android_fs/api/api_async/struct.AndroidFs.html#method.read_dir_paged)
+	 * @since 28.2.0
+	 */
+	public static async openReadDirStream(
+		uri: AndroidFsUri | FsPath,
+		options?: AndroidOpenReadDirStreamOptions
+	): Promise<ReadableStream<AndroidEntryMetadataWithUri>> {
+
+		throwIfAborted(options?.signal)
+		const pageSize = options?.pageSize ?? 256
+		if (!isNonzeroSafeInt(pageSize)) {
+			throw new Error(`Invalid pageSize: ${pageSize}`)
+		}
+
+		type CmdEvents = {
+			Open: { in: { uri: string | AndroidFsUri, pageSize: number }, out: number },
+			Read: { in: { id: number }, out: AndroidEntryMetadataWithUriInner[] },
+			Close: { in: { id: number }, out: null },
+		}
+		type CmdType = keyof CmdEvents
+		function dispatch<T extends CmdType>(type: T, input: CmdEvents[T]["in"]): Promise<CmdEvents[T]["out"]> {
+			return invoke('plugin:android-fs|open_read_dir_stream', { event: { type, ...input } })
+		}
+
+		const id = await dispatch("Open", { uri: mapFsPathForInput(uri), pageSize })
+		const signal = options?.signal
+		let abortListener: (() => void) | null = null
+		let cleanupPromise: Promise<void> | null = null
+		function cleanup(): Promise<void> {
+			if (cleanupPromise === null) {
+				if (signal != null && abortListener != null) {
+					signal.removeEventListener("abort", abortListener)
+					abortListener = null
+				}
+				cleanupPromise = dispatch("Close", { id }).then(() => { })
+			}
+			return cleanupPromise
+		}
+
+		try {
+			throwIfAborted(signal)
+		}
+		catch (e) {
+			await cleanup().catch(() => { })
+			throw e
+		}
+
+		return new ReadableStream<AndroidEntryMetadataWithUri>({
+			start(controller) {
+				if (signal) {
+					abortListener = () => {
+						cleanup().catch(() => { })
+						controller.error(signal.reason ?? newAbortError())
+					}
+					signal.addEventListener("abort", abortListener)
+				}
+			},
+
+			async pull(controller) {
+				try {
+					const entries = await dispatch("Read", { id })
+					throwIfAborted(signal)
+
+					if (entries.length === 0) {
+						await cleanup()
+						controller.close()
+						return
+					}
+
+					for (const e of entries) {
+						const lastModified = new Date(e.lastModified)
+
+						controller.enqueue(e.type === "Dir"
+							? { type: "Dir", name: e.name, uri: e.uri, lastModified }
+							: { type: "File", name: e.name, uri: e.uri, lastModified, byteLength: e.byteLength, mimeType: e.mimeType }
+						)
+					}
+				}
+				catch (e) {
+					await cleanup().catch(() => { })
+					throw e
+				}
+			},
+
+			async cancel() {
+				await cleanup()
+			}
+		}, { highWaterMark: 0 })
+	}
+
+	/**
+	 * Recursively walks the files and subdirectories under the specified directory.  
+	 * The root directory itself is not passed.
 	 * 
-	 * **NOTE**:  
-	 * For URIs from the directory picker, all permissions are lost after this operation, including for the new URI.
+	 * Entries are read page by page and passed to `onEntries` in batches, 
+	 * so this can be used for a large directory tree.
+	 * The URIs of the descendants inherit the permissions of the root directory.
 	 * 
-	 * @param uri - The URI of the directory to rename.
-	 * @param name - New name. If an entry with the same name already exists, a sequential number is appended to ensure uniqueness.
+	 * @param uri - The URI or path of the directory to walk.
+	 * @param onEntries - Callback called with each batch of entries. The order of the entries in the same directory depends on the file provider.
+	 * @param options - Optional settings: `maxDepth`, `order`, `type`, `mimeTypes`, `namePattern`, `batchSize`. See `AndroidWalkDirOptions` for detailed descriptions of each item.
 	 * 
-	 * @returns A Promise that resolves to the new URI of the target directory.
-	 * @throws The Promise will be rejected with an error, if the entry does not exist, if the entry is not a directory, if write permission is missing, or if the file provider does not support rename.
+	 * @returns A Promise that resolves after all entries have been passed to `onEntries`.
+	 * @throws The Promise will be rejected with an error, if the entry does not exist, if the entry is not a directory, if read permission is missing, or if `namePattern` is invalid.
 	 * 
-	 * @see [AndroidFs::rename](https://docs.rs/tauri-plugin-android-fs/latest/tauri_plugin_android_fs/api/api_async/struct.AndroidFs.html#method.rename)
-	 * @since 24.1.0
+	 * @see [AndroidFs::walk_dir](https://docs.rs/tauri-plugin-android-fs/latest/tauri_plugin_android_fs/api/api_async/struct.AndroidFs.html#method.walk_dir)
+	 * @since 28.2.0
 	 */
-	public static async renameDir(
-		uri: AndroidFsUri,
-		name: string
-	): Promise<AndroidFsUri> {
+	public static async walkDir(
+		uri: AndroidFsUri | FsPath,
+		onEntries: (entries: AndroidWalkEntry[]) => void,
+		options?: AndroidWalkDirOptions
+	): Promise<void> {
 
-		return await invoke('plugin:android-fs|rename_dir', {
-			uri,
-			name
+		let received = 0
+		let total: number | null = null
+		let resolveAllReceived: () => void = () => { }
+		const allReceived = new Promise<void>(resolve => resolveAllReceived = resolve)
+
+		const channel = new Channel<AndroidWalkEntryInner[]>()
+		channel.onmessage = entries => {
+			const buffer: AndroidWalkEntry[] = new Array(entries.length)
+
+			for (let i = 0; i < entries.length; i++) {
+				const e = entries[i];
+				const lastModified = new Date(e.lastModified);
+				const { depth, relativePath } = e;
+
+				buffer[i] = e.type === "Dir"
+					? { type: "Dir", name: e.name, uri: e.uri, lastModified, depth, relativePath }
+					: { type: "File", name: e.name, uri: e.uri, lastModified, byteLength: e.byteLength, mimeType: e.mimeType, depth, relativePath };
+			}
+
+			received += entries.length
+			try {
+				onEntries(buffer)
+			}
+			finally {
+				if (total !== null && total <= received) {
+					resolveAllReceived()
+				}
+			}
+		}
+
+		total = await invoke<number>('plugin:android-fs|walk_dir', {
+			uri: mapFsPathForInput(uri),
+			options: {
+				maxDepth: options?.maxDepth ?? null,
+				order: options?.order ?? "PreOrder",
+				entryType: options?.type ?? null,
+				mimeTypes: options?.mimeTypes ?? [],
+				namePattern: options?.namePattern ?? null,
+				pageSize: options?.batchSize ?? 256,
+			},
+			onEntries: channel,
 		})
-	}
 
-	/**
-	 * Removes the specified file.
-	 * 
-	 * @param uri - The URI of the file to remove.
-	 * 
-	 * @returns A Promise that resolves when the removing is complete.
-	 * @throws The Promise will be rejected with an error, if the entry does not exist, if the entry is not a file, if write permission is missing, or if the file provider does not support removing.
-	 * 
-	 * @see [AndroidFs::remove_file](https://docs.rs/tauri-plugin-android-fs/latest/tauri_plugin_android_fs/api/api_async/struct.AndroidFs.html#method.remove_file)
-	 * @since 22.0.0
-	 */
-	public static async removeFile(uri: AndroidFsUri): Promise<void> {
-		return await invoke('plugin:android-fs|remove_file', { uri })
+		// チャンネルのメッセージはコマンドの結果より後に届くことがある
+		if (total <= received) {
+			resolveAllReceived()
+		}
+		await allReceived
 	}
 
 	/**
-	 * Removes the specified directory and all of its contents recursively.
+	 * Watches the direct children of the specified directory for changes.
 	 * 
-	 * @param uri - The URI of the directory to remove.
+	 * Since the file provider has no way to notify changes, 
+	 * the directory is read at the interval and compared with the previous result.
+	 * So changes between two reads are merged, 
+	 * and a change that is reverted before the next read is not notified.
 	 * 
-	 * @returns A Promise that resolves when the removing is complete.
-	 * @throws The Promise will be rejected with an error, if the entry does not exist, if the entry is not a directory, if write permission is missing, or if the file provider does not support removing.
+	 * @param uri - The URI or path of the directory to watch.
+	 * @param onEvent - Callback called with each event. 
+	 * @param options - Optional settings: `intervalMs`. See `AndroidWatchDirOptions` for detailed descriptions of each item.
 	 * 
-	 * @see [AndroidFs::remove_dir_all](https://docs.rs/tauri-plugin-android-fs/latest/tauri_plugin_android_fs/api/api_async/struct.AndroidFs.html#method.remove_dir_all)
-	 * @since 22.0.0
+	 * @returns A Promise that resolves to a function that stops the watching. The watching also stops when the webview is closed.
+	 * @throws The Promise will be rejected with an error, if the entry does not exist, if the entry is not a directory, if read permission is missing, or if `intervalMs` is `0`.
+	 * 
+	 * @see [AndroidFs::watch_dir](https://docs.rs/tauri-plugin-android-fs/latest/tauri_plugin_android_fs/api/api_async/struct.AndroidFs.html#method.watch_dir)
+	 * @since 28.2.0
 	 */
-	public static async removeDirAll(uri: AndroidFsUri): Promise<void> {
-		return await invoke('plugin:android-fs|remove_dir_all', { uri })
+	public static async watchDir(
+		uri: AndroidFsUri | FsPath,
+		onEvent: (event: AndroidDirWatchEvent) => void,
+		options?: AndroidWatchDirOptions
+	): Promise<() => Promise<void>> {
+
+		const mapEntry = (e: AndroidEntryMetadataWithUriInner): AndroidEntryMetadataWithUri => {
+			const lastModified = new Date(e.lastModified)
+			return e.type === "Dir"
+				? { type: "Dir", name: e.name, uri: e.uri, lastModified }
+				: { type: "File", name: e.name, uri: e.uri, lastModified, byteLength: e.byteLength, mimeType: e.mimeType }
+		}
+
+		const channel = new Channel<AndroidDirWatchEventInner>()
+		channel.onmessage = event => {
+			switch (event.type) {
+				case "Created":
+				case "Removed":
+					onEvent({ type: event.type, entry: mapEntry(event.entry) })
+					break
+				case "Modified":
+				case "Renamed":
+					onEvent({ type: event.type, old: mapEntry(event.old), new: mapEntry(event.new) })
+					break
+				case "Error":
+					onEvent(event)
+					break
+			}
+		}
+
+		const id = await invoke<number>('plugin:android-fs|watch_dir', {
+			uri: mapFsPathForInput(uri),
+			intervalMs: options?.intervalMs ?? 1000,
+			onEvent: channel,
+		})
+
+		let isUnwatched = false
+		return async () => {
+			if (isUnwatched) {
+				return
+			}
+			isUnwatched = true
+			await invoke('plugin:android-fs|unwatch_dir', { id })
+		}
 	}
 
 	/**
-	 * Removes the specified directory if empty.
+	 * Finds files with the same contents under the specified directories.
 	 * 
-	 * @param uri - The URI of the directory to remove.
+	 * Files are first grouped by byte length, then by the SHA-256 hash of their start, 
+	 * and finally by the SHA-256 hash of their entire contents, 
+	 * so only files that may be duplicates are read to the end. 
+	 * The same file reached from multiple directories is counted only once.
 	 * 
-	 * @returns A Promise that resolves when the removing is complete.
-	 * @throws The Promise will be rejected with an error, if the entry does not exist, if the entry is not an empty directory, if write permission is missing, or if the file provider does not support removing.
+	 * @param uris - The URIs or paths of the directories to search.
+	 * @param options - Optional settings: `minByteLength`, `partialHashByteLength`, `onProgress`. See `AndroidFindDuplicatesOptions` for detailed descriptions of each item.
 	 * 
-	 * @see [AndroidFs::remove_dir](https://docs.rs/tauri-plugin-android-fs/latest/tauri_plugin_android_fs/api/api_async/struct.AndroidFs.html#method.remove_dir)
-	 * @since 22.0.0
+	 * @returns A Promise that resolves to the groups of duplicate files in descending order of the byte length.
+	 * @throws The Promise will be rejected with an error, if any directory does not exist, if read permission is missing, or if a file cannot be read.
+	 * 
+	 * @see [AndroidFs::find_duplicates](https://docs.rs/tauri-plugin-android-fs/latest/tauri_plugin_android_fs/api/api_async/struct.AndroidFs.html#method.find_duplicates)
+	 * @since 28.2.0
 	 */
-	public static async removeEmptyDir(uri: AndroidFsUri): Promise<void> {
-		return await invoke('plugin:android-fs|remove_empty_dir', { uri })
+	public static async findDuplicates(
+		uris: (AndroidFsUri | FsPath)[],
+		options?: AndroidFindDuplicatesOptions
+	): Promise<AndroidDuplicateGroup[]> {
+
+		const onProgress = options?.onProgress
+
+		// 結果を返した後に届いた進捗は呼び出し元に渡さない
+		let isFinished = false
+		const channel = new Channel<AndroidFindDuplicatesProgress>()
+		channel.onmessage = progress => {
+			if (!isFinished) {
+				onProgress?.(progress)
+			}
+		}
+
+		try {
+			const groups = await invoke<AndroidDuplicateGroupInner[]>('plugin:android-fs|find_duplicates', {
+				uris: uris.map(mapFsPathForInput),
+				options: {
+					minLen: options?.minByteLength ?? 1,
+					partialHashLen: options?.partialHashByteLength ?? 4096,
+				},
+				onProgress: channel,
+			})
+
+			return groups.map(group => ({
+				byteLength: group.byteLength,
+				entries: group.entries.map(e => ({
+					type: "File",
+					name: e.name,
+					uri: e.uri,
+					lastModified: new Date(e.lastModified),
+					byteLength: e.byteLength,
+					mimeType: e.mimeType,
+				})),
+			}))
+		}
+		finally {
+			isFinished = true
+		}
 	}
 
 	/**
-	 * Retrieves metadata and URIs for the child files and subdirectories of the specified directory.
+	 * Searches the files and subdirectories under the specified directory 
+	 * whose paths relative to it match the glob pattern.
 	 * 
-	 * @param uri - The URI of the directory to read.
-	 * @param options - Optional settings: `offset`, `limit`.
-	 * @param options.offset - Number of entries to skip from the beginning. Defaults to `0`.
-	 * @param options.limit - Maximum number of entries to get. If omitted, all available entries starting from `offset` are returned.
+	 * Since the search is done on the native side, 
+	 * only the matching entries are passed over IPC. 
+	 * Directories that cannot contain a match are not read.
+	 * The URIs of the entries inherit the permissions of the directory.
 	 * 
-	 * @returns A Promise that resolves to an array of entries, each containing metadata and the URI of a file or directory. The order of the entries depends on the file provider.  
-	 * @throws The Promise will be rejected with an error, if the entry does not exist, if the entry is not a directory, if read permission is missing.
+	 * @param uri - The URI or path of the directory to search.
+	 * @param pattern - Glob pattern of relative paths, such as `"*.txt"` and `"photos/**\/*.jpg"`. `*` does not match `/`, and `**` matches zero or more directories.
 	 * 
-	 * @see [AndroidFs::read_dir](https://docs.rs/tauri-plugin-android-fs/latest/tauri_plugin_android_fs/api/api_async/struct.AndroidFs.html#method.read_dir)
-	 * @since 22.0.0
+	 * @returns A Promise that resolves to an array of the matching entries with their relative paths. The order of the entries depends on the file provider.
+	 * @throws The Promise will be rejected with an error, if the entry does not exist, if the entry is not a directory, if read permission is missing, or if `pattern` is invalid.
+	 * 
+	 * @see [AndroidFs::find](https://docs.rs/tauri-plugin-android-fs/latest/tauri_plugin_android_fs/api/api_async/struct.AndroidFs.html#method.find)
+	 * @since 28.2.0
 	 */
-	public static async readDir(
-		uri: AndroidFsUri,
-		options?: AndroidReadDirOptions
-	): Promise<AndroidEntryMetadataWithUri[]> {
+	public static async find(
+		uri: AndroidFsUri | FsPath,
+		pattern: string
+	): Promise<AndroidFoundEntry[]> {
 
-		const offset = options?.offset ?? null
-		const limit = options?.limit ?? null
-		const entries = await invoke<AndroidEntryMetadataWithUriInner[]>('plugin:android-fs|read_dir', {
-			uri,
-			offset,
-			limit,
+		const entries = await invoke<AndroidFoundEntryInner[]>('plugin:android-fs|find', {
+			uri: mapFsPathForInput(uri),
+			pattern,
 		})
 
-		const buffer: AndroidEntryMetadataWithUri[] = new Array(entries.length)
+		const buffer: AndroidFoundEntry[] = new Array(entries.length)
 
 		for (let i = 0; i < entries.length; i++) {
 			const e = entries[i];
 			const lastModified = new Date(e.lastModified);
+			const { relativePath } = e;
 
 			buffer[i] = e.type === "Dir"
-				? { type: "Dir", name: e.name, uri: e.uri, lastModified }
-				: { type: "File", name: e.name, uri: e.uri, lastModified, byteLength: e.byteLength, mimeType: e.mimeType };
+				? { type: "Dir", name: e.name, uri: e.uri, lastModified, relativePath }
+				: { type: "File", name: e.name, uri: e.uri, lastModified, byteLength: e.byteLength, mimeType: e.mimeType, relativePath };
 		}
 
 		return buffer
@@ -3122,6 +5181,29 @@ function mapMaxLineByteLength(s?: number): number {
 	return s
 }
 
+function mapMaxRecordByteLength(s?: number): number {
+	if (s == null) return 0
+
+	if (!Number.isSafeInteger(s) || s < 0) {
+		throw new Error("Invalid maxRecordByteLength: expected a safe unsigned integer");
+	}
+
+	return s
+}
+
+function mapRecordDelimiterForInput(
+	delimiter: string | Uint8Array | number[]
+): { type: "Text", text: string } | { type: "Bytes", bytes: number[] } {
+
+	if (delimiter.length === 0) {
+		throw new Error("Invalid delimiter: expected a non-empty value")
+	}
+	if (typeof delimiter === "string") {
+		return { type: "Text", text: delimiter }
+	}
+	return { type: "Bytes", bytes: Array.from(delimiter) }
+}
+
 const UTF8_DECODER = new TextDecoder()
 const UTF8_ENCODER = new TextEncoder()
 
@@ -3133,8 +5215,10 @@ function encodeUtf8(text: string): Uint8Array<ArrayBuffer> {
 }
 
 type ReadFileStreamEvents = {
-	open: (options?: Record<any, any>) => Promise<void>
+	open: (options?: Record<any, any>) => Promise<Uint8Array<ArrayBuffer>>
 	read: (len: number, options?: Record<any, any>) => Promise<Uint8Array<ArrayBuffer> | null>,
+	readAt: (offset: number, len: number) => Promise<Uint8Array<ArrayBuffer>>,
+	seek: (offset: number, whence: AndroidSeekWhence) => Promise<number>,
 	close: (options?: Record<any, any>) => Promise<void>,
 }
 async function resolveReadFileStreamEvents(
@@ -3145,6 +5229,8 @@ async function resolveReadFileStreamEvents(
 	type CmdEvents = {
 		Open: { uri: string | AndroidFsUri },
 		Read: { id: number, len: number },
+		ReadAt: { id: number, offset: number, len: number },
+		Seek: { id: number, offset: number, whence: AndroidSeekWhence },
 		Close: { id: number },
 	}
 	type CmdType = keyof CmdEvents
@@ -3159,8 +5245,11 @@ async function resolveReadFileStreamEvents(
 	return {
 		open: async (ops) => {
 			if (id !== null) throw new Error("File already opened")
-			id = dispatch("Open", { ...ops, uri }).then(ridFromBytes)
+			const res = dispatch("Open", { ...ops, uri })
+			// 先頭の 4 バイトが ID で、残りはコマンドごとの情報
+			id = res.then(data => ridFromBytes(new Uint8Array(data, 0, 4)))
 			await id
+			return new Uint8Array(await res, 4)
 		},
 
 		read: async (len, ops) => {
@@ -3169,6 +5258,18 @@ async function resolveReadFileStreamEvents(
 			return data.byteLength === 0 ? null : new Uint8Array(data)
 		},
 
+		readAt: async (offset, len) => {
+			if (id === null) throw new Error("File not opened")
+			const data = await dispatch("ReadAt", { id: await id, offset, len })
+			return new Uint8Array(data)
+		},
+
+		seek: async (offset, whence) => {
+			if (id === null) throw new Error("File not opened")
+			const data = await dispatch("Seek", { id: await id, offset, whence })
+			return trySafeU64FromBytes(data, "bigEndian")
+		},
+
 		close: async (ops) => {
 			if (id === null) return
 			await dispatch("Close", { ...ops, id: await id })
@@ -3179,6 +5280,9 @@ async function resolveReadFileStreamEvents(
 type WriteFileStreamEvents = {
 	open: () => Promise<void>,
 	write: (data: Uint8Array<ArrayBufferLike> | string) => Promise<void>,
+	writeAt: (offset: number, data: Uint8Array<ArrayBufferLike>) => Promise<void>,
+	seek: (offset: number, whence: AndroidSeekWhence) => Promise<number>,
+	setLen: (len: number) => Promise<void>,
 	close: (type: "Err" | "Ok") => Promise<void>,
 }
 async function resolveWriteFileStreamEvents(
@@ -3186,13 +5290,21 @@ async function resolveWriteFileStreamEvents(
 	uri: string | AndroidFsUri,
 	options: {
 		create: boolean,
-		notification: AndroidProgressNotificationTemplate | null
+		notification: AndroidProgressNotificationTemplate | null,
+		atomic: boolean,
+		append: boolean,
+		encoding?: string | null,
+		bom?: boolean,
+		lineEnding?: "lf" | "crlf" | null,
 	}
 ): Promise<WriteFileStreamEvents> {
 
 	type CmdEvents = {
 		Open: { body: Uint8Array, headers: { uri: string, options: string }, out: { id: number, supportsRawIpcRequestBody: boolean } },
-		Write: { body: Uint8Array | { data: string, format: "dataUrlToDecodedData" | "textToUtf8" }, headers: { id: string }, out: void },
+		Write: { body: Uint8Array | { data: string, format: "dataUrlToDecodedData" | "textToUtf8" }, headers: { id: string, text?: string }, out: void },
+		WriteAt: { body: Uint8Array | { data: string, format: "dataUrlToDecodedData" }, headers: { id: string, offset: string }, out: void },
+		Seek: { body: {}, headers: { id: string, offset: string, whence: AndroidSeekWhence }, out: number },
+		SetLen: { body: {}, headers: { id: string, len: string }, out: void },
 		Close: { body: {}, headers: { id: string, error: string }, out: void },
 	}
 	type CmdType = keyof CmdEvents
@@ -3232,11 +5344,13 @@ async function resolveWriteFileStreamEvents(
 			const { id, supportsRawIpcRequestBody } = await state
 
 			if (supportsRawIpcRequestBody) {
-				const data = typeof chunk === "string"
-					? encodeUtf8(chunk)
-					: chunk
-
-				await dispatch("Write", data, { id })
+				// テキストは UTF-8 で送り、Open 時に指定されたエンコーディングへの変換はバックエンドで行う
+				if (typeof chunk === "string") {
+					await dispatch("Write", encodeUtf8(chunk), { id, text: "true" })
+				}
+				else {
+					await dispatch("Write", chunk, { id })
+				}
 			}
 			else {
 				if (typeof chunk === "string") {
@@ -3253,6 +5367,29 @@ async function resolveWriteFileStreamEvents(
 			}
 		},
 
+		writeAt: async (offset, chunk) => {
+			if (state === null) throw new Error("File not opened")
+			const { id, supportsRawIpcRequestBody } = await state
+			const headers = { id, offset: offset.toString() }
+
+			if (supportsRawIpcRequestBody) {
+				await dispatch("WriteAt", chunk, headers)
+			}
+			else {
+				await dispatch("WriteAt", { data: await bytesToDataUrl(chunk), format: "dataUrlToDecodedData" }, headers)
+			}
+		},
+
+		seek: async (offset, whence) => {
+			if (state === null) throw new Error("File not opened")
+			return await dispatch("Seek", {}, { id: (await state).id, offset: offset.toString(), whence })
+		},
+
+		setLen: async (len) => {
+			if (state === null) throw new Error("File not opened")
+			await dispatch("SetLen", {}, { id: (await state).id, len: len.toString() })
+		},
+
 		close: async (t) => {
 			if (state === null) return
 			await dispatch("Close", {}, { id: (await state).id, error: (t === "Err").toString() })
@@ -3402,6 +5539,157 @@ function createTextLinesReadableStream(
 	})
 }
 
+function createDelimitedRecordsReadableStream(
+	handler: {
+		/** null か空で EOF */
+		read: () => Promise<Uint8Array<ArrayBuffer> | null>,
+		release?: () => Promise<void>
+	},
+	options?: {
+		fatal?: boolean,
+		label?: string,
+	},
+	signal?: AbortSignal
+): ReadableStream<{ fields: string[], terminated: boolean }> {
+
+	/*
+	 * bytes は以下の形式のレコードが連続したものであり、
+	 * 各レコードが分断されることはない。
+	 * 
+	 * - err flag (u8, 0 = ok, 1 = err)
+	 * - terminator type (u8, 0 = null, 1 = delimiter or "\n", 2 = "\r\n")
+	 * - field count (u64, big endian)
+	 * - 以下をフィールドの数だけ繰り返す
+	 *     - field bytes len (u64, big endian)
+	 *     - field bytes (variable bytes)
+	 * 
+	 * err flag が 1 の場合、そのレコードでエラーが発生したことを示す。
+	 * この場合、フィールドは utf-8 形式のエラーメッセージの 1 つのみであり、
+	 * この呼び出しでの最後のレコードとなる。
+	 * 
+	 * エラー発生後の呼び出しの挙動は未定義。
+	 */
+	const ERR_FLAG_LEN = 1;
+	const TERMINATOR_TYPE_LEN = 1;
+	const FIELD_COUNT_LEN = 8;
+	const FIELD_LEN_LEN = 8;
+
+	const ERR_FLAG_OFFSET = 0;
+	const TERMINATOR_TYPE_OFFSET = ERR_FLAG_OFFSET + ERR_FLAG_LEN;
+	const FIELD_COUNT_OFFSET = TERMINATOR_TYPE_OFFSET + TERMINATOR_TYPE_LEN;
+	const FIELDS_OFFSET = FIELD_COUNT_OFFSET + FIELD_COUNT_LEN;
+
+	const TERMINATOR_NULL = 0
+
+	let abortListener: (() => void) | null = null
+	let decoder: TextDecoder | null = null
+	let buffer: Uint8Array<ArrayBuffer> | null = null
+
+	let cleanupPromise: Promise<void> | null = null
+	function cleanup(): Promise<void> {
+		if (cleanupPromise === null) {
+			cleanupPromise = (async () => {
+				buffer = null
+				decoder = null
+				if (signal != null && abortListener != null) {
+					signal.removeEventListener("abort", abortListener)
+					abortListener = null
+				}
+				if (handler.release) {
+					await handler.release()
+				}
+			})()
+		}
+		return cleanupPromise
+	}
+
+	// エラーはその原因となったレコードを読み込んだ際に発生させたいため、
+	// 1回の pull では1回だけ enqueue　を行う。
+	return new ReadableStream({
+		start(controller) {
+			if (signal) {
+				abortListener = () => {
+					cleanup().catch(() => { })
+					controller.error(signal.reason ?? newAbortError())
+				}
+				signal.addEventListener("abort", abortListener);
+			}
+		},
+
+		async pull(controller) {
+			try {
+				throwIfAborted(signal)
+				if (buffer == null || buffer.byteLength === 0) {
+					buffer = await handler.read()
+					throwIfAborted(signal)
+				}
+				if (buffer == null || buffer.byteLength === 0) {
+					await cleanup()
+					controller.close()
+					return
+				}
+
+				if (buffer.byteLength < FIELDS_OFFSET) {
+					throw new Error("Invalid data: Chunk ended with partial header.")
+				}
+				const fieldCount = trySafeU64FromBytes(
+					buffer.subarray(FIELD_COUNT_OFFSET, FIELD_COUNT_OFFSET + FIELD_COUNT_LEN),
+					"bigEndian"
+				)
+
+				const fieldBytesList: Uint8Array<ArrayBuffer>[] = []
+				let offset = FIELDS_OFFSET
+				for (let i = 0; i < fieldCount; i++) {
+					if (buffer.byteLength < offset + FIELD_LEN_LEN) {
+						throw new Error("Invalid data: Record split detected.")
+					}
+					const fieldLen = trySafeU64FromBytes(
+						buffer.subarray(offset, offset + FIELD_LEN_LEN),
+						"bigEndian"
+					)
+					offset += FIELD_LEN_LEN
+
+					if (buffer.byteLength < offset + fieldLen) {
+						throw new Error("Invalid data: Record split detected.")
+					}
+					fieldBytesList.push(buffer.subarray(offset, offset + fieldLen))
+					offset += fieldLen
+				}
+
+				const errFlag = buffer[ERR_FLAG_OFFSET]
+				if (numToFlag(errFlag)) {
+					throw new Error(fieldBytesList.map(b => decodeUtf8(b)).join(""))
+				}
+
+				const terminated = buffer[TERMINATOR_TYPE_OFFSET] !== TERMINATOR_NULL
+
+				if (decoder == null) {
+					decoder = new TextDecoder(options?.label, {
+						fatal: options?.fatal,
+						ignoreBOM: true
+					})
+				}
+				const fields: string[] = []
+				for (const fieldBytes of fieldBytesList) {
+					fields.push(decoder.decode(fieldBytes))
+				}
+
+				throwIfAborted(signal)
+				controller.enqueue({ fields, terminated })
+				buffer = buffer.subarray(offset)
+			}
+			catch (e) {
+				await cleanup().catch(() => { })
+				throw e
+			}
+		},
+
+		async cancel() {
+			await cleanup()
+		}
+	})
+}
+
 function throwIfAborted(signal: AbortSignal | undefined | null) {
 	if (signal?.aborted === true) {
 		throw (signal?.reason ?? newAbortError())
@@ -38,6 +38,20 @@ const COMMANDS: &'static [&'static str] = &[
     "write_file",
     "write_text_file",
     "read_dir",
+    "open_read_dir_stream",
+    "walk_dir",
+    "find",
+    "get_dir_stats",
+    "sync_dir",
+    "watch_dir",
+    "unwatch_dir",
+    "find_duplicates",
+    "hash_file",
+    "create_zip",
+    "extract_zip",
+    "open_read_delimited_records_stream",
+    "read_last_lines",
+    "build_line_index",
     "rename_file",
     "rename_dir",
     "check_picker_uri_permission",
@@ -62,6 +76,17 @@ fn main() {
         .global_scope_schema(schemars::schema_for!(scope::Scope))
         .build();
 
+    // API の実装 (impls) が利用可能な環境を表す cfg。
+    // Android では Kotlin 側のプラグインが、
+    // それ以外では custom-backend feature で設定されたバックエンドが使われる。
+    println!("cargo:rustc-check-cfg=cfg(afs_impls)");
+    let is_android = std::env::var("CARGO_CFG_TARGET_OS").as_deref() == Ok("android");
+    let is_unix = std::env::var("CARGO_CFG_UNIX").is_ok();
+    let custom_backend = std::env::var("CARGO_FEATURE_CUSTOM_BACKEND").is_ok();
+    if is_android || (is_unix && custom_backend) {
+        println!("cargo:rustc-cfg=afs_impls");
+    }
+
     let mut permissions = Vec::new();
 
     if std::env::var("CARGO_FEATURE_NOTIFICATION_PERMISSION").is_ok() {
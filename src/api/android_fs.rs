@@ -17,15 +17,11 @@ use super::*;
 
 #[sync_async]
 pub struct AndroidFs<R: tauri::Runtime> {
-    #[cfg(target_os = "android")]
-    pub(crate) handle: tauri::plugin::PluginHandle<R>,
-
-    #[cfg(not(target_os = "android"))]
     #[allow(unused)]
-    pub(crate) handle: std::marker::PhantomData<fn() -> R>
+    pub(crate) handle: Handle<R>,
 }
 
-#[cfg(target_os = "android")]
+#[cfg(afs_impls)]
 #[sync_async(
     use(if_sync) impls::SyncImpls as Impls;
     use(if_async) impls::AsyncImpls as Impls;
@@ -39,8 +35,8 @@ impl<R: tauri::Runtime> AndroidFs<R> {
 }
 
 #[sync_async(
-    use(if_async) api_async::{FileOpener, FilePicker, AppStorage, PrivateStorage, PublicStorage, Utils, ProgressNotificationGuard};
-    use(if_sync) api_sync::{FileOpener, FilePicker, AppStorage, PrivateStorage, PublicStorage, Utils, ProgressNotificationGuard};
+    use(if_async) api_async::{FileOpener, FilePicker, AppStorage, PrivateStorage, PublicStorage, Utils, ProgressNotificationGuard, WalkDir, ReadDir};
+    use(if_sync) api_sync::{FileOpener, FilePicker, AppStorage, PrivateStorage, PublicStorage, Utils, ProgressNotificationGuard, WalkDir, ReadDir};
 )]
 impl<R: tauri::Runtime> AndroidFs<R> {
 
@@ -91,10 +87,10 @@ impl<R: tauri::Runtime> AndroidFs<R> {
     /// All Android version.
     #[maybe_async]
     pub fn get_name(&self, uri: &FileUri) -> Result<String> {
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().get_entry_name(uri).await
         }
     }
@@ -103,7 +99,7 @@ impl<R: tauri::Runtime> AndroidFs<R> {
     /// or falls back to the URI's last path segment (percent-decoded). 
     #[maybe_async]
     pub fn get_name_or_last_path_segment(&self, uri: &FileUri) -> String {
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             if let Ok(name) = self.impls().get_entry_name(uri).await {
                 return name
             }
@@ -136,10 +132,10 @@ impl<R: tauri::Runtime> AndroidFs<R> {
     /// All Android version.
     #[maybe_async]
     pub fn get_mime_type(&self, uri: &FileUri) -> Result<String> {
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().get_file_mime_type(uri).await
         }
     }
@@ -162,10 +158,10 @@ impl<R: tauri::Runtime> AndroidFs<R> {
     /// All Android version.
     #[maybe_async]
     pub fn get_type(&self, uri: &FileUri) -> Result<EntryType> {
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().get_entry_type(uri).await
         }
     }
@@ -181,10 +177,10 @@ impl<R: tauri::Runtime> AndroidFs<R> {
     /// All Android version.
     #[maybe_async]
     pub fn get_info(&self, uri: &FileUri) -> Result<Entry> {
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().get_entry_info(uri).await
         }
     }
@@ -200,10 +196,10 @@ impl<R: tauri::Runtime> AndroidFs<R> {
     /// All Android version.
     #[maybe_async]
     pub fn get_len(&self, uri: &FileUri) -> Result<u64> {
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().get_file_len(uri).await
         }
     }
@@ -222,10 +218,10 @@ impl<R: tauri::Runtime> AndroidFs<R> {
     /// All Android version.
     #[maybe_async]
     pub fn get_metadata(&self, uri: &FileUri) -> Result<std::fs::Metadata> {
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().get_entry_metadata(uri).await
         }
     }
@@ -247,10 +243,10 @@ impl<R: tauri::Runtime> AndroidFs<R> {
     /// All Android version.
     #[maybe_async]
     pub fn open_file_readable(&self, uri: &FileUri) -> Result<std::fs::File> {
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().open_file_readable(uri).await
         }
     }
@@ -271,10 +267,10 @@ impl<R: tauri::Runtime> AndroidFs<R> {
         uri: &FileUri, 
     ) -> Result<std::fs::File> {
 
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().open_file_writable(uri).await
         }
     }
@@ -312,10 +308,10 @@ impl<R: tauri::Runtime> AndroidFs<R> {
     /// All Android version.
     #[maybe_async]
     pub fn open_file(&self, uri: &FileUri, mode: FileAccessMode) -> Result<std::fs::File> {
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().open_file(uri, mode).await
         }
     }
@@ -332,10 +328,10 @@ impl<R: tauri::Runtime> AndroidFs<R> {
         candidate_modes: impl IntoIterator<Item = FileAccessMode>
     ) -> Result<(std::fs::File, FileAccessMode)> {
 
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().open_file_with_fallback(uri, candidate_modes).await
         }
     }
@@ -351,10 +347,10 @@ impl<R: tauri::Runtime> AndroidFs<R> {
     /// All Android version.
     #[maybe_async]
     pub fn read(&self, uri: &FileUri) -> Result<Vec<u8>> {
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().read_file(uri).await
         }
     }
@@ -370,14 +366,170 @@ impl<R: tauri::Runtime> AndroidFs<R> {
     /// All Android version.
     #[maybe_async]
     pub fn read_to_string(&self, uri: &FileUri) -> Result<String> {
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().read_file_to_string(uri).await
         }
     }
 
+    /// Computes the hash of the contents of a file.  
+    /// 
+    /// The file is read in chunks on a blocking thread, 
+    /// so the entire contents are never loaded into memory.  
+    /// 
+    /// This returns the digest as bytes.
+    /// For [`HashAlgorithm::Crc32`] and [`HashAlgorithm::Xxh64`], the value is in big endian,
+    /// so its hexadecimal representation is the same as the common one.  
+    /// 
+    /// # Args
+    /// - ***uri*** :  
+    /// Target file URI.  
+    /// Must be **readable**.
+    /// 
+    /// - ***algorithm*** :  
+    /// Hash algorithm.
+    /// 
+    /// # Support
+    /// All Android version.
+    #[maybe_async]
+    pub fn hash(&self, uri: &FileUri, algorithm: HashAlgorithm) -> Result<Vec<u8>> {
+        #[cfg(not(afs_impls))] {
+            Err(Error::NOT_ANDROID)
+        }
+        #[cfg(afs_impls)] {
+            self.impls().hash_file(uri, algorithm).await
+        }
+    }
+
+    /// Reads the last `n` lines of a text file.  
+    /// 
+    /// This seeks to the end of the file and scans backwards for line breaks, 
+    /// so only the last `n` lines are read even if the file is very large, such as a log file.  
+    /// 
+    /// The lines are returned in order from oldest to newest, without line breaks (`"\n"` or `"\r\n"`).  
+    /// A line break at the end of the file does not start a new empty line, 
+    /// and a leading BOM is removed.  
+    /// Invalid byte sequences are replaced with U+FFFD.  
+    /// 
+    /// # Args
+    /// - ***uri*** :  
+    /// Target file URI.  
+    /// Must be **readable**.
+    /// 
+    /// - ***n*** :  
+    /// Maximum number of lines to read.
+    /// 
+    /// - ***encoding*** :  
+    /// [WHATWG encoding label](https://encoding.spec.whatwg.org/#names-and-labels) of the file, such as `"utf-8"`, `"shift_jis"` or `"utf-16le"`.  
+    /// If `None`, UTF-8 is used.
+    /// 
+    /// # Support
+    /// All Android version.
+    #[maybe_async]
+    pub fn read_last_lines(
+        &self,
+        uri: &FileUri,
+        n: usize,
+        encoding: Option<&str>,
+    ) -> Result<Vec<String>> {
+
+        #[cfg(not(afs_impls))] {
+            Err(Error::NOT_ANDROID)
+        }
+        #[cfg(afs_impls)] {
+            self.impls().read_last_lines(uri, n, encoding).await
+        }
+    }
+
+    /// Scans a text file once and builds an index of the byte offsets of every `interval` lines.  
+    /// 
+    /// This allows jumping to a line of a huge text file, such as for a virtualized text view, 
+    /// without reading it from the beginning.  
+    /// Use [`LineIndex::position`] to get where to seek.  
+    /// The lines are counted in the same way as [`AndroidFs::read_last_lines`].  
+    /// 
+    /// To reuse it later, save it with [`AndroidFs::save_line_index`].
+    /// 
+    /// # Args
+    /// - ***uri*** :  
+    /// Target file URI.  
+    /// Must be **readable**.
+    /// 
+    /// - ***interval*** :  
+    /// Number of lines between the recorded offsets.  
+    /// Smaller values make seeking faster but the index larger.  
+    /// This must be greater than `0`.
+    /// 
+    /// - ***encoding*** :  
+    /// [WHATWG encoding label](https://encoding.spec.whatwg.org/#names-and-labels) of the file, such as `"utf-8"`, `"shift_jis"` or `"utf-16le"`.  
+    /// If `None`, UTF-8 is used.
+    /// 
+    /// # Support
+    /// All Android version.
+    #[maybe_async]
+    pub fn build_line_index(
+        &self,
+        uri: &FileUri,
+        interval: u64,
+        encoding: Option<&str>,
+    ) -> Result<LineIndex> {
+
+        #[cfg(not(afs_impls))] {
+            Err(Error::NOT_ANDROID)
+        }
+        #[cfg(afs_impls)] {
+            self.impls().build_line_index(uri, interval, encoding).await
+        }
+    }
+
+    /// Saves the index built by [`AndroidFs::build_line_index`] as a sidecar file in [`PrivateDir::Cache`].  
+    /// An index previously saved for the same file is replaced.  
+    /// 
+    /// Since it is in the cache directory, the system may delete it when disk space is needed.
+    /// 
+    /// # Args
+    /// - ***uri*** :  
+    /// URI of the file the index was built from.
+    /// 
+    /// - ***index*** :  
+    /// Index to save.
+    /// 
+    /// # Support
+    /// All Android version.
+    #[maybe_async]
+    pub fn save_line_index(&self, uri: &FileUri, index: &LineIndex) -> Result<()> {
+        #[cfg(not(afs_impls))] {
+            Err(Error::NOT_ANDROID)
+        }
+        #[cfg(afs_impls)] {
+            self.impls().save_line_index(uri, index).await
+        }
+    }
+
+    /// Loads the index saved by [`AndroidFs::save_line_index`].  
+    /// 
+    /// This returns `None` if no index is saved for the file, 
+    /// or if the file's length or last modified time has changed since the index was built.
+    /// 
+    /// # Args
+    /// - ***uri*** :  
+    /// Target file URI.  
+    /// Must be **readable**.
+    /// 
+    /// # Support
+    /// All Android version.
+    #[maybe_async]
+    pub fn load_line_index(&self, uri: &FileUri) -> Result<Option<LineIndex>> {
+        #[cfg(not(afs_impls))] {
+            Err(Error::NOT_ANDROID)
+        }
+        #[cfg(afs_impls)] {
+            self.impls().load_line_index(uri).await
+        }
+    }
+
     /// Writes a slice as the entire contents of a file.  
     /// This function will entirely replace its contents if it does exist.    
     /// 
@@ -390,14 +542,77 @@ impl<R: tauri::Runtime> AndroidFs<R> {
     /// All Android version.
     #[maybe_async]
     pub fn write(&self, uri: &FileUri, contents: impl AsRef<[u8]>) -> Result<()> {
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().write_file(uri, contents).await
         }
     }
 
+    /// Writes a slice as the entire contents of a file,
+    /// so that the file has either the old or the new contents even if the app dies while writing.
+    ///
+    /// The contents are written to a temporary file and flushed to the storage,
+    /// and then the temporary file replaces the target file as follows:
+    /// - For a file URI via [`FileUri::from_path`],
+    /// the temporary file is created in the same directory and replaces the target by [`std::fs::rename`].
+    /// - For a document in a directory picked by [`FilePicker::pick_dir`] on the external storage,
+    /// the temporary file is created in the same directory,
+    /// and the target is renamed to a backup name, and then the temporary file is renamed to the target name.
+    /// The URI of the target does not change.
+    ///
+    /// If the temporary file cannot be created in the same directory or cannot be renamed,
+    /// such as for a file picked by [`FilePicker::pick_files`] or a file on cloud storage,
+    /// this falls back to copying the temporary file into the target.
+    /// In this case the replacement is not atomic,
+    /// but the target is truncated only while copying, not while the caller is writing.
+    ///
+    /// The temporary file is removed whether this succeeds or fails.
+    ///
+    /// # Args
+    /// - ***uri*** :
+    /// Target file URI.
+    /// Must be **read-write**.
+    /// If this is in a directory, the directory must also be **read-write** to create the temporary file.
+    ///
+    /// # Support
+    /// All Android version.
+    #[maybe_async]
+    pub fn write_atomic(&self, uri: &FileUri, contents: impl AsRef<[u8]>) -> Result<()> {
+        #[cfg(not(afs_impls))] {
+            Err(Error::NOT_ANDROID)
+        }
+        #[cfg(afs_impls)] {
+            self.impls().write_file_atomically(uri, contents).await
+        }
+    }
+
+    /// Appends a slice to the end of a file.  
+    /// The existing contents are preserved.  
+    /// 
+    /// This opens the file with [`FileAccessMode::WriteAppend`].
+    /// If the file provider does not support it, 
+    /// this opens the file with [`FileAccessMode::ReadWrite`] and seeks to the end before writing.  
+    /// 
+    /// # Args
+    /// - ***uri*** :  
+    /// Target file URI.  
+    /// Must be **writable**. 
+    /// If the file provider does not support [`FileAccessMode::WriteAppend`], this must also be **readable**.
+    /// 
+    /// # Support
+    /// All Android version.
+    #[maybe_async]
+    pub fn append(&self, uri: &FileUri, contents: impl AsRef<[u8]>) -> Result<()> {
+        #[cfg(not(afs_impls))] {
+            Err(Error::NOT_ANDROID)
+        }
+        #[cfg(afs_impls)] {
+            self.impls().append_file(uri, contents).await
+        }
+    }
+
     /// Copies the contents of the source file to the destination.  
     /// If the destination already has contents, they are truncated before writing the source contents.  
     /// 
@@ -414,14 +629,252 @@ impl<R: tauri::Runtime> AndroidFs<R> {
     /// All Android version.
     #[maybe_async]
     pub fn copy(&self, src: &FileUri, dest: &FileUri) -> Result<()> {
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().copy_file(src, dest).await
         }
     }
 
+    /// Recursively copies the files and directories under the source directory into the destination directory.  
+    /// The source directory itself is not copied, only its contents.  
+    /// 
+    /// The directory structure is recreated by [`AndroidFs::create_dir_all`] and [`AndroidFs::create_new_file`],
+    /// and the contents of files are copied by [`AndroidFs::copy`], 
+    /// so this works across file providers, 
+    /// e.g. from a directory picked by [`FilePicker::pick_dir`] to [`PrivateDir::Data`].  
+    /// 
+    /// This returns the result of each source entry.
+    /// Failures of individual entries, such as a file that cannot be read, do not stop the copy 
+    /// and are reported in [`CopyDirEntry::result`].  
+    /// If a directory fails, its children are not copied.  
+    /// 
+    /// # Args
+    /// - ***src_dir*** :  
+    /// The URI of source directory.  
+    /// Must be **readable**.
+    /// 
+    /// - ***dest_dir*** :  
+    /// The URI of destination directory.  
+    /// Must be **read-write**.  
+    /// This must not be ***src_dir*** itself or inside it.
+    /// 
+    /// - ***options*** :  
+    /// Policy for entries that already exist in the destination. See [`CopyDirOptions`].  
+    /// If [`CopyConflictPolicy::Fail`], this returns an error at the first conflict
+    /// and the entries copied so far are left as they are.  
+    /// 
+    /// # Support
+    /// All Android version.
+    #[maybe_async]
+    pub fn copy_dir(
+        &self,
+        src_dir: &FileUri,
+        dest_dir: &FileUri,
+        options: CopyDirOptions,
+    ) -> Result<Vec<CopyDirEntry>> {
+
+        #[cfg(not(afs_impls))] {
+            Err(Error::NOT_ANDROID)
+        }
+        #[cfg(afs_impls)] {
+            self.impls().copy_dir(src_dir, dest_dir, options).await
+        }
+    }
+
+    /// Makes the destination directory a mirror of the source directory, in one direction.  
+    /// 
+    /// Entries are compared by name, and files are compared by length and last modified time,
+    /// or by contents if [`SyncOptions::compare_contents`] is true.  
+    /// Files that exist only in the source are copied, and files that differ are overwritten.  
+    /// Entries that exist only in the destination are removed if [`SyncOptions::delete_extras`] is true.  
+    /// 
+    /// This returns the performed actions in order.
+    /// If [`SyncOptions::dry_run`] is true, nothing is changed and this returns the planned actions.  
+    /// If an action fails, this stops and returns the error.  
+    /// 
+    /// This is the same as [`AndroidFs::sync_dir_with_progress`] without the progress callback.
+    /// 
+    /// # Args
+    /// - ***src_dir*** :  
+    /// The URI of source directory.  
+    /// Must be **readable**.
+    /// 
+    /// - ***dest_dir*** :  
+    /// The URI of destination directory.  
+    /// Must be **read-write**.  
+    /// This must not be ***src_dir*** itself or inside it.
+    /// 
+    /// - ***options*** :  
+    /// See [`SyncOptions`].  
+    /// 
+    /// # Support
+    /// All Android version.
+    #[maybe_async]
+    pub fn sync_dir(
+        &self,
+        src_dir: &FileUri,
+        dest_dir: &FileUri,
+        options: SyncOptions,
+    ) -> Result<Vec<SyncAction>> {
+
+        self.sync_dir_with_progress(src_dir, dest_dir, options, |_, _, _| {}).await
+    }
+
+    /// See [`AndroidFs::sync_dir`] for descriptions.  
+    /// 
+    /// ***on_progress*** is called after each action is performed,
+    /// with the action, the number of performed actions, and the total number of actions.  
+    /// This is not called if [`SyncOptions::dry_run`] is true.
+    #[maybe_async]
+    pub fn sync_dir_with_progress(
+        &self,
+        src_dir: &FileUri,
+        dest_dir: &FileUri,
+        options: SyncOptions,
+        on_progress: impl FnMut(&SyncAction, u64, u64) + Send,
+    ) -> Result<Vec<SyncAction>> {
+
+        #[cfg(not(afs_impls))] {
+            Err(Error::NOT_ANDROID)
+        }
+        #[cfg(afs_impls)] {
+            self.impls().sync_dir(src_dir, dest_dir, options, on_progress).await
+        }
+    }
+
+    /// Creates a zip archive of files and directory trees, and writes it to the destination file.
+    /// This function will entirely replace its contents if it does exist.
+    ///
+    /// Each source is stored under its own name at the root of the archive.
+    /// Directories are stored recursively with their relative paths, e.g. `dir/sub/file.txt`.
+    /// The last modified time of each entry is preserved.
+    ///
+    /// The contents of files are read and compressed in chunks,
+    /// so the entire archive is never loaded into memory.
+    /// Files of 4 GiB or more and archives with many entries are written in Zip64 format.
+    /// The last modified time is recorded in UTC.
+    ///
+    /// This is the same as [`AndroidFs::create_zip_with_progress`] without the progress callback.
+    ///
+    /// # Args
+    /// - ***sources*** :
+    /// URIs of files and directories to archive.
+    /// Must be **readable**.
+    /// If two entries have the same path in the archive, this returns an error before writing anything.
+    ///
+    /// - ***dest*** :
+    /// Destination file URI, e.g. a URI from [`FilePicker::save_file`].
+    /// Must be **writable** and seekable, which most file providers support.
+    /// If this is inside a source directory, it is excluded from the archive.
+    ///
+    /// - ***options*** :
+    /// See [`CreateZipOptions`].
+    ///
+    /// # Support
+    /// All Android version.
+    #[maybe_async]
+    pub fn create_zip(
+        &self,
+        sources: &[FileUri],
+        dest: &FileUri,
+        options: CreateZipOptions,
+    ) -> Result<()> {
+
+        self.create_zip_with_progress(sources, dest, options, |_, _| {}).await
+    }
+
+    /// See [`AndroidFs::create_zip`] for descriptions.
+    ///
+    /// ***on_progress*** is called after each chunk of file contents is archived,
+    /// with the number of archived bytes and the total bytes of the source files.
+    /// These can be passed to [`ProgressNotificationGuard::update_progress`] and [`ProgressNotificationGuard::update_progress_max`].
+    #[maybe_async]
+    pub fn create_zip_with_progress(
+        &self,
+        sources: &[FileUri],
+        dest: &FileUri,
+        options: CreateZipOptions,
+        on_progress: impl FnMut(u64, u64) + Send,
+    ) -> Result<()> {
+
+        #[cfg(not(afs_impls))] {
+            Err(Error::NOT_ANDROID)
+        }
+        #[cfg(afs_impls)] {
+            self.impls().create_zip(sources, dest, options, on_progress).await
+        }
+    }
+
+    /// Extracts a zip archive into the destination directory.
+    ///
+    /// The archive is read through the seekable file descriptor of [`AndroidFs::open_file_readable`],
+    /// and the entries are created by [`AndroidFs::create_dir_all`] and [`AndroidFs::create_new_file`],
+    /// so this works with directories of any file provider, e.g. one picked by [`FilePicker::pick_dir`].
+    /// Existing entries are never overwritten.
+    /// If a file with the same name already exists, the file provider may append a sequential number to the new one.
+    /// The last modified time of each file is restored if the file provider allows it.
+    ///
+    /// Entries whose paths point outside the destination directory,
+    /// such as `../evil.sh` and `/etc/passwd` (so-called zip slip), are not extracted and result in an error.
+    /// Symbolic links and encrypted entries are not supported either.
+    ///
+    /// This returns the result of each entry in the archive, in the order recorded in it.
+    /// Failures of individual entries, such as a CRC mismatch, do not stop the extraction
+    /// and are reported in [`ExtractZipEntry::result`].
+    /// A partially written file is removed.
+    ///
+    /// This is the same as [`AndroidFs::extract_zip_with_progress`] without the progress callback.
+    ///
+    /// # Args
+    /// - ***src*** :
+    /// URI of the zip archive.
+    /// Must be **readable**.
+    ///
+    /// - ***dest_dir*** :
+    /// URI of the destination directory.
+    /// Must be **read-write**.
+    ///
+    /// - ***options*** :
+    /// Filters of entries and the size limit. See [`ExtractZipOptions`].
+    /// If the total size exceeds [`ExtractZipOptions::max_total_len`], this returns an error without extracting anything.
+    ///
+    /// # Support
+    /// All Android version.
+    #[maybe_async]
+    pub fn extract_zip(
+        &self,
+        src: &FileUri,
+        dest_dir: &FileUri,
+        options: ExtractZipOptions,
+    ) -> Result<Vec<ExtractZipEntry>> {
+
+        self.extract_zip_with_progress(src, dest_dir, options, |_, _| {}).await
+    }
+
+    /// See [`AndroidFs::extract_zip`] for descriptions.
+    ///
+    /// ***on_progress*** is called after each file is extracted,
+    /// with the number of extracted bytes and the total uncompressed bytes of the entries to extract.
+    /// These can be passed to [`ProgressNotificationGuard::update_progress`] and [`ProgressNotificationGuard::update_progress_max`].
+    #[maybe_async]
+    pub fn extract_zip_with_progress(
+        &self,
+        src: &FileUri,
+        dest_dir: &FileUri,
+        options: ExtractZipOptions,
+        on_progress: impl FnMut(u64, u64) + Send,
+    ) -> Result<Vec<ExtractZipEntry>> {
+
+        #[cfg(not(afs_impls))] {
+            Err(Error::NOT_ANDROID)
+        }
+        #[cfg(afs_impls)] {
+            self.impls().extract_zip(src, dest_dir, options, on_progress).await
+        }
+    }
+
     /// Renames a file or directory to a new name, and return new URI.  
     /// Even if the names conflict, the existing file will not be overwritten.  
     /// 
@@ -447,14 +900,51 @@ impl<R: tauri::Runtime> AndroidFs<R> {
     /// All Android version.
     #[maybe_async]
     pub fn rename(&self, uri: &FileUri, new_name: impl AsRef<str>) -> Result<FileUri> {
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().rename_entry(uri, new_name).await
         }
     }
 
+    /// Moves a file or directory into the specified directory, and return new URI.  
+    /// The name of the entry is kept. If an entry with the same name already exists in the destination, an error occurs.  
+    /// 
+    /// This first tries to move the entry natively, which is possible e.g. within the same file provider.  
+    /// Otherwise, this falls back to copying the entry, verifying the copy, and removing the source.  
+    /// If the fallback fails before the source is removed, anything written to the destination is removed,
+    /// so the entry remains only in the source.  
+    /// 
+    /// As with [`AndroidFs::rename`], the returned URI may not have the permissions that the source URI had,
+    /// except that the URIs in a directory picked by [`FilePicker::pick_dir`] have the permissions of it.
+    /// 
+    /// # Args
+    /// - ***src*** :  
+    /// URI of the file or directory to be moved.  
+    /// Must be **read-write**.
+    /// 
+    /// - ***dest_parent*** :  
+    /// URI of the destination directory.  
+    /// Must be **read-write**.  
+    /// If ***src*** is a directory, this must not be inside it.
+    /// 
+    /// # Note
+    /// If the fallback is used for a directory and removing the source fails partway,
+    /// the complete copy is left in the destination and an error is returned.  
+    /// 
+    /// # Support
+    /// All Android version.
+    #[maybe_async]
+    pub fn move_entry(&self, src: &FileUri, dest_parent: &FileUri) -> Result<FileUri> {
+        #[cfg(not(afs_impls))] {
+            Err(Error::NOT_ANDROID)
+        }
+        #[cfg(afs_impls)] {
+            self.impls().move_entry(src, dest_parent).await
+        }
+    }
+
     /// Remove the file.
     /// 
     /// # Args
@@ -467,10 +957,10 @@ impl<R: tauri::Runtime> AndroidFs<R> {
     /// All Android version.
     #[maybe_async]
     pub fn remove_file(&self, uri: &FileUri) -> Result<()> {
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().remove_file(uri).await
         }
     }
@@ -487,10 +977,10 @@ impl<R: tauri::Runtime> AndroidFs<R> {
     /// All Android version.
     #[maybe_async]
     pub fn remove_dir(&self, uri: &FileUri) -> Result<()> {
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().remove_dir_if_empty(uri).await
         }
     }
@@ -507,10 +997,10 @@ impl<R: tauri::Runtime> AndroidFs<R> {
     /// All Android version.
     #[maybe_async]
     pub fn remove_dir_all(&self, uri: &FileUri) -> Result<()> {
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().remove_dir_all(uri).await
         }
     }
@@ -543,10 +1033,10 @@ impl<R: tauri::Runtime> AndroidFs<R> {
         relative_path: impl AsRef<std::path::Path>
     ) -> Result<FileUri> {
 
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().resolve_file_uri(dir, relative_path, false).await
         }
     }
@@ -579,10 +1069,10 @@ impl<R: tauri::Runtime> AndroidFs<R> {
         relative_path: impl AsRef<std::path::Path>
     ) -> Result<FileUri> {
 
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().resolve_dir_uri(dir, relative_path, false).await
         }
     }
@@ -599,10 +1089,10 @@ impl<R: tauri::Runtime> AndroidFs<R> {
         format: ImageFormat,
     ) -> Result<bool> {
 
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().get_file_thumbnail_to_file(src, dest, preferred_size, format).await
         }
     }
@@ -643,10 +1133,10 @@ impl<R: tauri::Runtime> AndroidFs<R> {
         format: ImageFormat,
     ) -> Result<Option<Vec<u8>>> {
 
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().get_file_thumbnail(uri, preferred_size, format).await
         }
     }
@@ -691,10 +1181,10 @@ impl<R: tauri::Runtime> AndroidFs<R> {
         format: ImageFormat,
     ) -> Result<Option<String>> {
 
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().get_file_thumbnail_base64(uri, preferred_size, format).await
         }
     }
@@ -732,10 +1222,10 @@ impl<R: tauri::Runtime> AndroidFs<R> {
         mime_type: Option<&str>
     ) -> Result<FileUri> {
 
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().create_new_file(dir, relative_path, mime_type).await
         }
     }
@@ -778,10 +1268,10 @@ impl<R: tauri::Runtime> AndroidFs<R> {
         mime_type: Option<&str>
     ) -> Result<(FileUri, std::path::PathBuf)> {
 
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().create_new_file_and_retrun_relative_path(dir, relative_path, mime_type).await
         }
     }
@@ -812,10 +1302,10 @@ impl<R: tauri::Runtime> AndroidFs<R> {
         relative_path: impl AsRef<std::path::Path>, 
     ) -> Result<FileUri> {
 
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().create_dir_all(dir, relative_path).await
         }
     }
@@ -850,10 +1340,10 @@ impl<R: tauri::Runtime> AndroidFs<R> {
         relative_path: impl AsRef<std::path::Path>, 
     ) -> Result<(FileUri, std::path::PathBuf)> {
 
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().create_dir_all_and_return_relative_path(dir, relative_path).await
         }
     }
@@ -873,10 +1363,10 @@ impl<R: tauri::Runtime> AndroidFs<R> {
     /// All Android version.
     #[maybe_async]
     pub fn read_dir(&self, uri: &FileUri) -> Result<Vec<Entry>> {
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls()
                 .read_dir(uri, EntryOptions::ALL, ..).await?
                 .map(Entry::try_from)
@@ -904,10 +1394,10 @@ impl<R: tauri::Runtime> AndroidFs<R> {
         range: impl std::ops::RangeBounds<u64>
     ) -> Result<Vec<Entry>> {
         
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls()
                 .read_dir(uri, EntryOptions::ALL, range).await?
                 .map(Entry::try_from)
@@ -916,7 +1406,7 @@ impl<R: tauri::Runtime> AndroidFs<R> {
     }
 
     /// Returns the child files and directories of the specified directory.  
-    /// The order of the entries depends on the file provider.  
+    /// The order of the entries depends on the file provider, unless sort keys are specified.  
     /// 
     /// The permissions and validity period of the returned URIs depend on the origin directory 
     /// (e.g., the top directory selected by [`FilePicker::pick_dir`])  
@@ -926,27 +1416,30 @@ impl<R: tauri::Runtime> AndroidFs<R> {
     /// Target directory URI.  
     /// Must be **readable**.
     /// 
+    /// - ***options*** :  
+    /// Fields, sort keys and filters of the entries. See [`ReadDirOptions`].  
+    /// [`EntryOptions`] can also be passed.  
+    /// 
     /// # Support
     /// All Android version.
     #[maybe_async]
     pub fn read_dir_with_options(
         &self, 
         uri: &FileUri, 
-        options: EntryOptions
+        options: impl Into<ReadDirOptions>
     ) -> Result<Vec<OptionalEntry>> {
         
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls()
-                .read_dir(uri, options, ..).await
-                .map(|i| i.collect())
+                .read_dir_with_sort_and_filter(uri, &options.into(), ..).await
         }
     }
 
     /// Returns the child files and directories of the specified directory.  
-    /// The order of the entries depends on the file provider.  
+    /// The order of the entries depends on the file provider, unless sort keys are specified.  
     /// 
     /// The permissions and validity period of the returned URIs depend on the origin directory 
     /// (e.g., the top directory selected by [`FilePicker::pick_dir`])  
@@ -956,36 +1449,393 @@ impl<R: tauri::Runtime> AndroidFs<R> {
     /// Target directory URI.  
     /// Must be **readable**.
     /// 
+    /// - ***options*** :  
+    /// Fields, sort keys and filters of the entries. See [`ReadDirOptions`].  
+    /// [`EntryOptions`] can also be passed.  
+    /// 
+    /// - ***range*** :  
+    /// Range of the entries to be returned.  
+    /// If sort keys or filters are specified, this is applied after sorting and filtering.  
+    /// In that case, the whole directory is read and sorted on every call, even for a small range, 
+    /// so paging through a large directory this way is slow.  
+    /// 
     /// # Support
     /// All Android version.
     #[maybe_async]
     pub fn read_dir_with_options_and_range(
         &self, 
         uri: &FileUri, 
-        options: EntryOptions,
+        options: impl Into<ReadDirOptions>,
         range: impl std::ops::RangeBounds<u64>
     ) -> Result<Vec<OptionalEntry>> {
         
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls()
-                .read_dir(uri, options, range).await
-                .map(|i| i.collect())
+                .read_dir_with_sort_and_filter(uri, &options.into(), range).await
+        }
+    }
+
+    /// Returns an iterator over the child files and directories of the specified directory.
+    /// The order of the entries depends on the file provider.
+    ///
+    /// Unlike [`AndroidFs::read_dir`], entries are read by [`AndroidFs::read_dir_with_options_and_range`]
+    /// page by page when they are needed, so this does not load the entire directory into memory.
+    /// The async version of the returned value also implements [`futures_core::Stream`].
+    ///
+    /// The permissions and validity period of the returned URIs depend on the origin directory
+    /// (e.g., the top directory selected by [`FilePicker::pick_dir`])
+    ///
+    /// # Args
+    /// - ***uri*** :
+    /// Target directory URI.
+    /// Must be **readable**.
+    ///
+    /// - ***options*** :
+    /// Fields of the entries.
+    ///
+    /// - ***page_size*** :
+    /// Number of entries to be read at once.
+    /// Must not be zero.
+    ///
+    /// # Support
+    /// All Android version.
+    #[always_sync]
+    pub fn read_dir_paged(
+        &self,
+        uri: &FileUri,
+        options: EntryOptions,
+        page_size: u64
+    ) -> Result<ReadDir<R>> {
+
+        #[cfg(not(afs_impls))] {
+            Err(Error::NOT_ANDROID)
+        }
+        #[cfg(afs_impls)] {
+            ReadDir::new(uri, options, page_size, self.handle.clone())
+        }
+    }
+
+    /// Returns an iterator that recursively walks the files and directories under the specified directory.  
+    /// The root directory itself is not yielded.  
+    /// The order of the entries in the same directory depends on the file provider.  
+    /// 
+    /// Entries are read by [`AndroidFs::read_dir_with_options_and_range`] page by page,
+    /// so this does not load the entire tree into memory.  
+    /// The async version of the returned value also implements [`futures_core::Stream`].  
+    /// The URIs of the descendants inherit `document_top_tree_uri` of ***uri***.  
+    /// 
+    /// # Args
+    /// - ***uri*** :  
+    /// Target directory URI.  
+    /// Must be **readable**.
+    /// 
+    /// - ***options*** :  
+    /// Max depth, order, filters and fields of the entries. See [`WalkOptions`].  
+    /// 
+    /// # Support
+    /// All Android version.
+    #[always_sync]
+    pub fn walk_dir(&self, uri: &FileUri, options: WalkOptions) -> Result<WalkDir<R>> {
+        #[cfg(not(afs_impls))] {
+            Err(Error::NOT_ANDROID)
+        }
+        #[cfg(afs_impls)] {
+            WalkDir::new(uri, options, self.handle.clone())
+        }
+    }
+
+    /// Searches the files and directories under the specified directory 
+    /// whose paths relative to it match the glob pattern,
+    /// and returns them with the relative paths.  
+    /// The order of the entries depends on the file provider.  
+    /// 
+    /// Directories that cannot contain a match, such as `videos` for `photos/**/*.jpg`, are not read.  
+    /// The URIs of the entries inherit `document_top_tree_uri` of ***uri***.  
+    /// 
+    /// # Args
+    /// - ***uri*** :  
+    /// Target directory URI.  
+    /// Must be **readable**.
+    /// 
+    /// - ***pattern*** :  
+    /// Glob pattern of relative paths, such as `*.txt` and `photos/**/*.jpg`.  
+    /// `*` does not match `/`, and `**` matches zero or more directories.  
+    /// 
+    /// # Support
+    /// All Android version.
+    #[maybe_async]
+    pub fn find(
+        &self,
+        uri: &FileUri,
+        pattern: &str,
+    ) -> Result<Vec<(Entry, std::path::PathBuf)>> {
+
+        #[cfg(not(afs_impls))] {
+            Err(Error::NOT_ANDROID)
+        }
+        #[cfg(afs_impls)] {
+            let pattern = std::sync::Arc::new(RelativePathPattern::new(pattern)?);
+            let options = WalkOptions {
+                max_depth: pattern.max_depth(),
+                ..Default::default()
+            };
+            let mut walk_dir = self.walk_dir(uri, options)?.with_descend_filter({
+                let pattern = std::sync::Arc::clone(&pattern);
+                move |dir| pattern.may_match_descendant_of(dir)
+            });
+
+            let mut entries = Vec::new();
+            while let Some(entry) = walk_dir.next_entry().await {
+                let entry = entry?;
+                if pattern.matches(&entry.relative_path) {
+                    entries.push((Entry::try_from(entry.entry)?, entry.relative_path));
+                }
+            }
+            Ok(entries)
+        }
+    }
+
+    /// Returns the total length, the number of files and directories, 
+    /// and the breakdown by MIME type of the files under the specified directory.  
+    /// The root directory itself is not counted.  
+    /// 
+    /// This is the same as [`AndroidFs::dir_stats_with_options`] with the default options and without cancellation.
+    /// 
+    /// # Args
+    /// - ***uri*** :  
+    /// Target directory URI.  
+    /// Must be **readable**.
+    /// 
+    /// # Support
+    /// All Android version.
+    #[maybe_async]
+    pub fn dir_stats(&self, uri: &FileUri) -> Result<DirStats> {
+        self.dir_stats_with_options(uri, DirStatsOptions::default(), || false).await
+    }
+
+    /// Returns the total length, the number of files and directories, 
+    /// and the breakdown by MIME type of the files under the specified directory.  
+    /// The root directory itself is not counted.  
+    /// 
+    /// Entries are read by [`AndroidFs::walk_dir`] page by page,
+    /// so this does not load the entire tree into memory.  
+    /// 
+    /// # Args
+    /// - ***uri*** :  
+    /// Target directory URI.  
+    /// Must be **readable**.
+    /// 
+    /// - ***options*** :  
+    /// Max depth and page size. See [`DirStatsOptions`].  
+    /// 
+    /// - ***is_canceled*** :  
+    /// Called each time an entry is read.  
+    /// If this returns true, the traversal stops and an error is returned.  
+    /// 
+    /// # Support
+    /// All Android version.
+    #[maybe_async]
+    pub fn dir_stats_with_options(
+        &self,
+        uri: &FileUri,
+        options: DirStatsOptions,
+        is_canceled: impl FnMut() -> bool + Send,
+    ) -> Result<DirStats> {
+
+        #[cfg(not(afs_impls))] {
+            Err(Error::NOT_ANDROID)
+        }
+        #[cfg(afs_impls)] {
+            let mut is_canceled = is_canceled;
+            let options = WalkOptions {
+                max_depth: options.max_depth,
+                page_size: options.page_size,
+                entry_options: EntryOptions {
+                    len: true,
+                    mime_type: true,
+                    ..EntryOptions::NONE
+                },
+                ..Default::default()
+            };
+
+            let mut walk_dir = self.walk_dir(uri, options)?;
+            let mut stats = DirStats::default();
+
+            while let Some(entry) = walk_dir.next_entry().await {
+                if is_canceled() {
+                    return Err(Error::with("dir_stats was canceled"))
+                }
+
+                match entry?.entry {
+                    OptionalEntry::File { len, mime_type, .. } => stats.add_file(
+                        len.ok_or_else(|| Error::missing_value("len"))?,
+                        mime_type.as_deref().ok_or_else(|| Error::missing_value("mime_type"))?,
+                    ),
+                    OptionalEntry::Dir { .. } => stats.add_dir(),
+                }
+            }
+
+            Ok(stats)
+        }
+    }
+
+    /// Watches the child files and directories of the specified directory for changes,
+    /// and returns a guard that stops the watching when dropped.
+    ///
+    /// Since the file providers do not notify changes,
+    /// this reads the directory at every ***interval*** and compares it with the previous one.
+    /// Entries are keyed on the URI, and changes of the name, length and last modified time are detected.
+    /// The URIs of the entries inherit `document_top_tree_uri` of ***uri***.
+    ///
+    /// # Args
+    /// - ***uri*** :
+    /// Target directory URI.
+    /// Must be **readable**.
+    ///
+    /// - ***interval*** :
+    /// Interval between reads.
+    /// Must not be zero.
+    ///
+    /// - ***on_event*** :
+    /// Callback called with each event on a background thread.
+    /// If the directory cannot be read, this is called with the error, and the watching continues.
+    ///
+    /// # Support
+    /// All Android version.
+    #[maybe_async]
+    pub fn watch_dir(
+        &self,
+        uri: &FileUri,
+        interval: std::time::Duration,
+        on_event: impl FnMut(Result<DirWatchEvent>) + Send + 'static,
+    ) -> Result<DirWatchGuard> {
+
+        #[cfg(not(afs_impls))] {
+            Err(Error::NOT_ANDROID)
+        }
+        #[cfg(afs_impls)] {
+            self.impls().watch_dir(uri, interval, on_event).await
         }
     }
 
+    /// Finds files with the same contents under the specified directories.
+    ///
+    /// Files are first grouped by length, then by the SHA-256 hash of their start,
+    /// and finally by the SHA-256 hash of their entire contents,
+    /// so only files that may be duplicates are read to the end.
+    /// The same file reached from multiple directories is counted only once,
+    /// even if it is reached through different tree URIs.
+    /// The URIs of the entries inherit `document_top_tree_uri` of the directory in which they are found.
+    ///
+    /// This returns the groups in descending order of the file length.
+    /// Empty files are ignored.
+    ///
+    /// This is the same as [`AndroidFs::find_duplicates_with_options`] with the default options and without the progress callback.
+    ///
+    /// # Args
+    /// - ***roots*** :
+    /// Target directory URIs.
+    /// Must be **readable**.
+    ///
+    /// # Support
+    /// All Android version.
+    #[maybe_async]
+    pub fn find_duplicates(&self, roots: &[FileUri]) -> Result<Vec<DuplicateGroup>> {
+        self.find_duplicates_with_options(roots, FindDuplicatesOptions::default(), |_, _, _| {}).await
+    }
+
+    /// See [`AndroidFs::find_duplicates`] for descriptions.
+    ///
+    /// ***options*** sets the minimum file length and the length of the partial hash. See [`FindDuplicatesOptions`].
+    ///
+    /// ***on_progress*** is called after each file is hashed,
+    /// with the phase, the number of hashed files, and the total number of files to be hashed in the phase.
+    #[maybe_async]
+    pub fn find_duplicates_with_options(
+        &self,
+        roots: &[FileUri],
+        options: FindDuplicatesOptions,
+        on_progress: impl FnMut(FindDuplicatesPhase, u64, u64) + Send,
+    ) -> Result<Vec<DuplicateGroup>> {
+
+        #[cfg(not(afs_impls))] {
+            Err(Error::NOT_ANDROID)
+        }
+        #[cfg(afs_impls)] {
+            self.impls().find_duplicates(roots, options, on_progress).await
+        }
+    }
+
+    /// Takes a manifest of the files and subdirectories under the specified directory.
+    /// The root directory itself is not contained.
+    ///
+    /// The manifest holds the relative paths and metadata of the entries, but not their URIs,
+    /// so it can be serialized, saved, and compared later by [`AndroidFs::diff_manifests`].
+    ///
+    /// This is the same as [`AndroidFs::snapshot_tree_with_options`] with the default options,
+    /// so the file contents are not hashed.
+    ///
+    /// # Args
+    /// - ***uri*** :
+    /// Target directory URI.
+    /// Must be **readable**.
+    ///
+    /// # Support
+    /// All Android version.
+    #[maybe_async]
+    pub fn snapshot_tree(&self, uri: &FileUri) -> Result<TreeManifest> {
+        self.snapshot_tree_with_options(uri, SnapshotTreeOptions::default()).await
+    }
+
+    /// See [`AndroidFs::snapshot_tree`] for descriptions.
+    ///
+    /// ***options*** sets whether the file contents are hashed. See [`SnapshotTreeOptions`].
+    #[maybe_async]
+    pub fn snapshot_tree_with_options(
+        &self,
+        uri: &FileUri,
+        options: SnapshotTreeOptions,
+    ) -> Result<TreeManifest> {
+
+        #[cfg(not(afs_impls))] {
+            Err(Error::NOT_ANDROID)
+        }
+        #[cfg(afs_impls)] {
+            self.impls().snapshot_tree(uri, options).await
+        }
+    }
+
+    /// Compares two manifests taken by [`AndroidFs::snapshot_tree`],
+    /// and returns the changes from ***old*** to ***new*** in the order of the relative path.
+    ///
+    /// Entries are matched by the relative path.
+    /// Files are treated as modified if their length differs,
+    /// or if their hashes differ when both manifests have them,
+    /// or otherwise if their last modified time differs.
+    /// Directories are treated as modified only if they became files, and vice versa.
+    ///
+    /// This does not access the file system.
+    ///
+    /// # Support
+    /// All platforms.
+    #[always_sync]
+    pub fn diff_manifests(&self, old: &TreeManifest, new: &TreeManifest) -> Vec<ManifestChange> {
+        crate::api::models::diff_manifests(old, new)
+    }
+
     /// See [`AppStorage::get_volumes`] or [`PublicStorage::get_volumes`] for details.
     /// 
     /// The difference is that this does not perform any filtering.
     /// You can it by [`StorageVolume { is_available_for_app_storage, is_available_for_public_storage, .. } `](StorageVolume).
     #[maybe_async]
     pub fn get_volumes(&self) -> Result<Vec<StorageVolume>> {
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().get_available_storage_volumes().await
         }
     }
@@ -996,10 +1846,10 @@ impl<R: tauri::Runtime> AndroidFs<R> {
     /// You can it by [`StorageVolume { is_available_for_app_storage, is_available_for_public_storage, .. } `](StorageVolume).
     #[maybe_async]
     pub fn get_primary_volume(&self) -> Result<Option<StorageVolume>> {
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().get_primary_storage_volume_if_available().await
         }
     }
@@ -1021,10 +1871,10 @@ impl<R: tauri::Runtime> AndroidFs<R> {
     /// All Android version.
     #[maybe_async]
     pub fn resolve_root_initial_location(&self, volume_id: Option<&StorageVolumeId>) -> Result<FileUri> {
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().resolve_root_initial_location(volume_id).await
         }
     }
@@ -1035,10 +1885,10 @@ impl<R: tauri::Runtime> AndroidFs<R> {
     /// All Android version.
     #[maybe_async]
     pub fn get_mime_type_from_extension(&self, ext: impl AsRef<str>) -> Result<Option<String>> {
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().get_mime_type_from_extension(ext).await
         }
     }
@@ -1046,10 +1896,11 @@ impl<R: tauri::Runtime> AndroidFs<R> {
     /// Verify whether this plugin is available.  
     /// 
     /// On Android, this returns true.  
-    /// On other platforms, this returns false.  
+    /// On other platforms, this returns false, 
+    /// unless the plugin is initialized with a backend via `init_with_backend`.  
     #[always_sync]
     pub fn is_available(&self) -> bool {
-        cfg!(target_os = "android")
+        self.handle.is_available()
     }
 
     /// Get the api level of this Android device.
@@ -1079,10 +1930,10 @@ impl<R: tauri::Runtime> AndroidFs<R> {
     /// Tauri does not support Android versions below 7.
     #[always_sync]
     pub fn api_level(&self) -> Result<i32> {
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().api_level()
         }
     }
@@ -1102,10 +1953,10 @@ impl<R: tauri::Runtime> AndroidFs<R> {
         relative_path: impl AsRef<std::path::Path>
     ) -> Result<FileUri> {
 
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().resolve_file_uri(dir, relative_path, true).await
         }
     }
@@ -1125,10 +1976,10 @@ impl<R: tauri::Runtime> AndroidFs<R> {
         relative_path: impl AsRef<std::path::Path>
     ) -> Result<FileUri> {
 
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().resolve_dir_uri(dir, relative_path, true).await
         }
     }
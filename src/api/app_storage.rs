@@ -16,15 +16,11 @@ use super::*;
 /// ```
 #[sync_async]
 pub struct AppStorage<'a, R: tauri::Runtime> {
-    #[cfg(target_os = "android")]
-    pub(crate) handle: &'a tauri::plugin::PluginHandle<R>,
-
-    #[cfg(not(target_os = "android"))]
     #[allow(unused)]
-    pub(crate) handle: &'a std::marker::PhantomData<fn() -> R>,
+    pub(crate) handle: &'a Handle<R>,
 }
 
-#[cfg(target_os = "android")]
+#[cfg(afs_impls)]
 #[sync_async(
     use(if_sync) impls::SyncImpls as Impls;
     use(if_async) impls::AsyncImpls as Impls;
@@ -70,10 +66,10 @@ impl<'a, R: tauri::Runtime> AppStorage<'a, R> {
     /// All Android version.
     #[maybe_async]
     pub fn get_volumes(&self) -> Result<Vec<StorageVolume>> {
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().get_available_storage_volumes_for_app_storage().await
         }
     }
@@ -97,10 +93,10 @@ impl<'a, R: tauri::Runtime> AppStorage<'a, R> {
     /// All Android version.
     #[maybe_async]
     pub fn get_primary_volume(&self) -> Result<Option<StorageVolume>> {
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().get_primary_storage_volume_if_available_for_app_storage().await
         }
     }
@@ -166,10 +162,10 @@ impl<'a, R: tauri::Runtime> AppStorage<'a, R> {
         dir: AppDir
     ) -> Result<std::path::PathBuf> {
 
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().resolve_dir_path_in_app_storage(volume_id, dir).await
         }
     }
@@ -183,10 +179,10 @@ impl<'a, R: tauri::Runtime> AppStorage<'a, R> {
         relative_path: impl AsRef<std::path::Path>
     ) -> Result<FileUri> {
 
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             let mut path = self.resolve_path(volume_id, dir).await?;
             path.push(relative_path.as_ref());
             Ok(path.into())
@@ -216,10 +212,10 @@ impl<'a, R: tauri::Runtime> AppStorage<'a, R> {
         mime_type: Option<&str>,
     ) -> Result<FileUri> {
 
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().scan_file_to_media_store_by_path(path, mime_type).await
         }
     }
@@ -239,10 +235,10 @@ impl<'a, R: tauri::Runtime> AppStorage<'a, R> {
         uri: &FileUri,
     ) -> Result<std::path::PathBuf> {
 
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().get_public_media_file_path_in_app_storage(uri).await
         }
     }
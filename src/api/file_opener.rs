@@ -16,15 +16,11 @@ use super::*;
 /// ```
 #[sync_async]
 pub struct FileOpener<'a, R: tauri::Runtime> {
-    #[cfg(target_os = "android")]
-    pub(crate) handle: &'a tauri::plugin::PluginHandle<R>,
-
-    #[cfg(not(target_os = "android"))]
     #[allow(unused)]
-    pub(crate) handle: &'a std::marker::PhantomData<fn() -> R>,
+    pub(crate) handle: &'a Handle<R>,
 }
 
-#[cfg(target_os = "android")]
+#[cfg(afs_impls)]
 #[sync_async(
     use(if_sync) impls::SyncImpls as Impls;
     use(if_async) impls::AsyncImpls as Impls;
@@ -70,10 +66,10 @@ impl<'a, R: tauri::Runtime> FileOpener<'a, R> {
         uris: impl IntoIterator<Item = &'b FileUri>, 
     ) -> Result<()> {
 
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().show_share_file_app_chooser(uris).await
         }
     }
@@ -103,10 +99,10 @@ impl<'a, R: tauri::Runtime> FileOpener<'a, R> {
         uri: &FileUri,
     ) -> Result<()> {
         
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().show_share_file_app_chooser([uri]).await
         }
     }
@@ -136,10 +132,10 @@ impl<'a, R: tauri::Runtime> FileOpener<'a, R> {
         uri: &FileUri,
     ) -> Result<()> {
 
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().show_open_file_app_chooser(uri).await
         }
     }
@@ -168,10 +164,10 @@ impl<'a, R: tauri::Runtime> FileOpener<'a, R> {
         uri: &FileUri,
     ) -> Result<()> {
 
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().show_open_dir_app_chooser(uri).await
         }
     }
@@ -206,10 +202,10 @@ impl<'a, R: tauri::Runtime> FileOpener<'a, R> {
         uri: &FileUri,
     ) -> Result<()> {
 
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
            self.impls().show_edit_file_app_chooser(uri).await
         }
     }
@@ -16,15 +16,11 @@ use super::*;
 /// ```
 #[sync_async]
 pub struct FilePicker<'a, R: tauri::Runtime> {
-    #[cfg(target_os = "android")]
-    pub(crate) handle: &'a tauri::plugin::PluginHandle<R>,
-
-    #[cfg(not(target_os = "android"))]
     #[allow(unused)]
-    pub(crate) handle: &'a std::marker::PhantomData<fn() -> R>,
+    pub(crate) handle: &'a Handle<R>,
 }
 
-#[cfg(target_os = "android")]
+#[cfg(afs_impls)]
 #[sync_async(
     use(if_sync) impls::SyncImpls as Impls;
     use(if_async) impls::AsyncImpls as Impls;
@@ -92,10 +88,10 @@ impl<'a, R: tauri::Runtime> FilePicker<'a, R> {
         local_only: bool,
     ) -> Result<Vec<FileUri>> {
 
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().show_pick_file_dialog(initial_location, mime_types, true, local_only).await
         }
     }
@@ -149,10 +145,10 @@ impl<'a, R: tauri::Runtime> FilePicker<'a, R> {
         local_only: bool
     ) -> Result<Option<FileUri>> {
 
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().show_pick_file_dialog(initial_location, mime_types, false, local_only)
                 .await
                 .map(|mut i| i.pop())
@@ -199,10 +195,10 @@ impl<'a, R: tauri::Runtime> FilePicker<'a, R> {
         local_only: bool
     ) -> Result<Vec<FileUri>> {
 
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().show_pick_visual_media_dialog(target, true, local_only).await
         }
     }
@@ -247,10 +243,10 @@ impl<'a, R: tauri::Runtime> FilePicker<'a, R> {
         local_only: bool
     ) -> Result<Option<FileUri>> {
 
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().show_pick_visual_media_dialog(target, false, local_only)
                 .await
                 .map(|mut i| i.pop())
@@ -284,10 +280,10 @@ impl<'a, R: tauri::Runtime> FilePicker<'a, R> {
         mime_types: &[&str],
     ) -> Result<Vec<FileUri>> {
 
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().show_pick_content_dialog(mime_types, true).await
         }
     }
@@ -319,10 +315,10 @@ impl<'a, R: tauri::Runtime> FilePicker<'a, R> {
         mime_types: &[&str],
     ) -> Result<Option<FileUri>> {
 
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().show_pick_content_dialog(mime_types, false)
                 .await
                 .map(|mut i| i.pop())
@@ -371,10 +367,10 @@ impl<'a, R: tauri::Runtime> FilePicker<'a, R> {
         local_only: bool
     ) -> Result<Option<FileUri>> {
 
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().show_pick_dir_dialog(initial_location, local_only).await
         }
     }
@@ -438,10 +434,10 @@ impl<'a, R: tauri::Runtime> FilePicker<'a, R> {
         local_only: bool
     ) -> Result<Option<FileUri>> {
         
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
            self.impls().show_save_file_dialog(initial_location, initial_file_name, mime_type, local_only).await 
         }
     }
@@ -452,10 +448,10 @@ impl<'a, R: tauri::Runtime> FilePicker<'a, R> {
     /// All Android version.
     #[maybe_async]
     pub fn is_visual_media_picker_available(&self) -> Result<bool> {
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().is_visual_media_picker_available().await
         }
     }
@@ -480,10 +476,10 @@ impl<'a, R: tauri::Runtime> FilePicker<'a, R> {
         permission: UriPermission
     ) -> Result<bool> {
         
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().check_picker_uri_permission(uri, permission).await
         }
     }
@@ -517,10 +513,10 @@ impl<'a, R: tauri::Runtime> FilePicker<'a, R> {
     /// All Android version. 
     #[maybe_async]
     pub fn persist_uri_permission(&self, uri: &FileUri) -> Result<()> {
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().persist_picker_uri_permission(uri).await
         }
     }
@@ -554,10 +550,10 @@ impl<'a, R: tauri::Runtime> FilePicker<'a, R> {
         permission: UriPermission
     ) -> Result<bool> {
 
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().check_persisted_picker_uri_permission(uri, permission).await
         }
     }
@@ -568,10 +564,10 @@ impl<'a, R: tauri::Runtime> FilePicker<'a, R> {
     /// All Android version.
     #[maybe_async]
     pub fn get_all_persisted_uri_permissions(&self) -> Result<Vec<PersistedUriPermissionState>> {
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls()
                 .get_all_persisted_picker_uri_permissions().await
                 .map(|v| v.collect())
@@ -592,10 +588,10 @@ impl<'a, R: tauri::Runtime> FilePicker<'a, R> {
     /// All Android version.
     #[maybe_async]
     pub fn release_persisted_uri_permission(&self, uri: &FileUri) -> Result<bool> {
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().release_persisted_picker_uri_permission(uri).await
         }
     }
@@ -607,10 +603,10 @@ impl<'a, R: tauri::Runtime> FilePicker<'a, R> {
     /// All Android version.
     #[maybe_async]
     pub fn release_all_persisted_uri_permissions(&self) -> Result<()> {
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().release_all_persisted_picker_uri_permissions().await
         }
     }
@@ -0,0 +1,88 @@
+#[allow(unused_imports)]
+use crate::*;
+
+
+/// API の呼び出し先。
+///
+/// Android では Kotlin 側のプラグイン、
+/// custom-backend feature が有効な場合は設定されたバックエンドが使われる。
+/// どちらも存在しない場合は全ての API が [`Error::NOT_ANDROID`] を返す。
+pub(crate) struct Handle<R: tauri::Runtime> {
+    #[cfg(target_os = "android")]
+    pub(crate) plugin: Option<tauri::plugin::PluginHandle<R>>,
+
+    #[cfg(all(afs_impls, feature = "custom-backend"))]
+    pub(crate) backend: Option<std::sync::Arc<dyn crate::backend::AndroidFsBackend>>,
+
+    #[cfg(afs_impls)]
+    pub(crate) cache: std::sync::Arc<super::impls::Cache>,
+
+    marker: std::marker::PhantomData<fn() -> R>,
+}
+
+#[allow(unused)]
+impl<R: tauri::Runtime> Handle<R> {
+
+    pub(crate) fn unavailable() -> Self {
+        Self {
+            #[cfg(target_os = "android")]
+            plugin: None,
+
+            #[cfg(all(afs_impls, feature = "custom-backend"))]
+            backend: None,
+
+            #[cfg(afs_impls)]
+            cache: Default::default(),
+
+            marker: std::marker::PhantomData,
+        }
+    }
+
+    #[cfg(target_os = "android")]
+    pub(crate) fn with_plugin(plugin: tauri::plugin::PluginHandle<R>) -> Self {
+        Self { plugin: Some(plugin), ..Self::unavailable() }
+    }
+
+    #[cfg(feature = "custom-backend")]
+    pub(crate) fn with_backend(backend: std::sync::Arc<dyn crate::backend::AndroidFsBackend>) -> Self {
+        #[cfg(afs_impls)] {
+            Self { backend: Some(backend), ..Self::unavailable() }
+        }
+        #[cfg(not(afs_impls))] {
+            Self::unavailable()
+        }
+    }
+
+    pub(crate) fn is_available(&self) -> bool {
+        #[cfg(target_os = "android")] {
+            if self.plugin.is_some() {
+                return true
+            }
+        }
+        #[cfg(all(afs_impls, feature = "custom-backend"))] {
+            if self.backend.is_some() {
+                return true
+            }
+        }
+
+        false
+    }
+}
+
+impl<R: tauri::Runtime> Clone for Handle<R> {
+
+    fn clone(&self) -> Self {
+        Self {
+            #[cfg(target_os = "android")]
+            plugin: self.plugin.clone(),
+
+            #[cfg(all(afs_impls, feature = "custom-backend"))]
+            backend: self.backend.clone(),
+
+            #[cfg(afs_impls)]
+            cache: std::sync::Arc::clone(&self.cache),
+
+            marker: std::marker::PhantomData,
+        }
+    }
+}
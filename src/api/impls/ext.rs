@@ -1,4 +1,4 @@
-use std::io::{Read, Write};
+use std::io::{BufRead, Read, Seek, Write};
 use sync_async::sync_async;
 use crate::*;
 use super::*;
@@ -16,8 +16,8 @@ impl<'a, R: tauri::Runtime> Impls<'a, R> {
     }
 
     #[always_sync]
-    pub fn public_dir_name(&self, dir: impl Into<PublicDir>) -> Result<&'static str> {
-        Ok(self.consts()?.public_dir_name(dir)?)
+    pub fn public_dir_name(&self, dir: impl Into<PublicDir>) -> Result<&'a str> {
+        self.consts()?.public_dir_name(dir)
     }
 
     #[maybe_async]
@@ -79,6 +79,24 @@ impl<'a, R: tauri::Runtime> Impls<'a, R> {
         }
     }
 
+    #[maybe_async]
+    pub fn open_file_appendable(&self, uri: &FileUri) -> Result<std::fs::File> {
+        // wa は全ての file provider が対応しているとは限らない。
+        // 対応していない場合は rw で開いて末尾にシークしてから書き込む。
+        let (mut file, mode) = self.open_file_with_fallback(uri, [
+            FileAccessMode::WriteAppend,
+            FileAccessMode::ReadWrite,
+        ]).await?;
+
+        if mode == FileAccessMode::WriteAppend {
+            return Ok(file)
+        }
+        run_blocking(move || {
+            file.seek(std::io::SeekFrom::End(0))?;
+            Ok(file)
+        }).await
+    }
+
     #[maybe_async]
     pub fn read_file(&self, uri: &FileUri) -> Result<Vec<u8>> {
         let mut file = self.open_file_readable(uri).await?;
@@ -107,6 +125,135 @@ impl<'a, R: tauri::Runtime> Impls<'a, R> {
         }).await
     }
 
+    #[maybe_async]
+    pub fn hash_file(&self, uri: &FileUri, algorithm: HashAlgorithm) -> Result<Vec<u8>> {
+        self.hash_file_prefix(uri, algorithm, None).await
+    }
+
+    /// ファイルの先頭から最大 `limit` バイトの内容のハッシュを返す。
+    #[maybe_async]
+    fn hash_file_prefix(&self, uri: &FileUri, algorithm: HashAlgorithm, limit: Option<u64>) -> Result<Vec<u8>> {
+        let file = self.open_file_readable(uri).await?;
+        run_blocking(move || {
+            let mut hasher = Hasher::new(algorithm);
+            std::io::copy(&mut file.take(limit.unwrap_or(u64::MAX)), &mut hasher)?;
+            Ok(hasher.finalize())
+        }).await
+    }
+
+    #[maybe_async]
+    pub fn read_last_lines_raw(
+        &self,
+        uri: &FileUri,
+        n: usize,
+        markers: TextMarkers,
+    ) -> Result<Vec<RawLine>> {
+
+        let mut file = self.open_file_readable(uri).await?;
+        run_blocking(move || read_last_lines(&mut file, n, &markers)).await
+    }
+
+    #[maybe_async]
+    pub fn read_last_lines(
+        &self,
+        uri: &FileUri,
+        n: usize,
+        encoding: Option<&str>,
+    ) -> Result<Vec<String>> {
+
+        let encoding = match encoding {
+            Some(label) => encoding_for_label(label)?,
+            None => encoding_rs::UTF_8,
+        };
+
+        let lines = self.read_last_lines_raw(uri, n, TextMarkers::for_encoding(encoding)).await?;
+        Ok(lines.into_iter()
+            .map(|line| encoding.decode_without_bom_handling(&line.bytes).0.into_owned())
+            .collect())
+    }
+
+    #[maybe_async]
+    pub fn build_line_index(
+        &self,
+        uri: &FileUri,
+        interval: u64,
+        encoding: Option<&str>,
+    ) -> Result<LineIndex> {
+
+        let encoding = match encoding {
+            Some(label) => encoding_for_label(label)?,
+            None => encoding_rs::UTF_8,
+        };
+
+        let mut file = self.open_file_readable(uri).await?;
+        run_blocking(move || {
+            let file_modified_ms = file_modified_ms(&file.metadata()?);
+            let markers = TextMarkers::for_encoding(encoding);
+            let (offsets, line_count, file_len) = scan_line_index(&mut file, interval, &markers)?;
+
+            Ok(LineIndex {
+                interval,
+                encoding: encoding.name().to_string(),
+                file_len,
+                file_modified_ms,
+                line_count,
+                offsets,
+            })
+        }).await
+    }
+
+    #[maybe_async]
+    pub fn save_line_index(&self, uri: &FileUri, index: &LineIndex) -> Result<()> {
+        let path = self.line_index_sidecar_path(uri)?;
+        let bytes = encode_line_index(uri, index);
+
+        run_blocking(move || {
+            let dir = path.parent().ok_or_else(|| Error::with("invalid sidecar path"))?;
+            std::fs::create_dir_all(dir)?;
+
+            // 読み込み中に書き込み途中の索引が見えないよう、一時ファイルに書き込んでから置き換える
+            let temp = dir.join(atomic_write_temp_name("line-index", "tmp"));
+            let result = std::fs::write(&temp, bytes).and_then(|_| std::fs::rename(&temp, &path));
+            if result.is_err() {
+                let _ = std::fs::remove_file(&temp);
+            }
+            result.map_err(Into::into)
+        }).await
+    }
+
+    #[maybe_async]
+    pub fn load_line_index(&self, uri: &FileUri) -> Result<Option<LineIndex>> {
+        let path = self.line_index_sidecar_path(uri)?;
+        let file = self.open_file_readable(uri).await?;
+        let uri = uri.clone();
+
+        run_blocking(move || {
+            let bytes = match std::fs::read(&path) {
+                Ok(bytes) => bytes,
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+                Err(err) => return Err(err.into()),
+            };
+
+            // 索引を作成した後にファイルが変更された場合は使わない
+            let metadata = file.metadata()?;
+            Ok(decode_line_index(&uri, &bytes).filter(|index| {
+                index.file_len == metadata.len() 
+                    && index.file_modified_ms == file_modified_ms(&metadata)
+            }))
+        }).await
+    }
+
+    /// 行の索引を保存するキャッシュディレクトリ内のパス。  
+    /// ファイル名は URI のハッシュであり、URI 自体はファイルの内容に含めて照合する。
+    #[always_sync]
+    fn line_index_sidecar_path(&self, uri: &FileUri) -> Result<std::path::PathBuf> {
+        let mut hasher = Hasher::new(HashAlgorithm::Xxh64);
+        hasher.update(uri.uri.as_bytes());
+        let name = hasher.finalize().iter().map(|b| format!("{b:02x}")).collect::<String>();
+
+        Ok(self.private_dir_path(PrivateDir::Cache)?.join("line-index").join(format!("{name}.idx")))
+    }
+
     #[maybe_async]
     pub fn write_file(
         &self,
@@ -126,6 +273,184 @@ impl<'a, R: tauri::Runtime> Impls<'a, R> {
         Ok(())
     }
 
+    #[maybe_async]
+    pub fn append_file(
+        &self,
+        uri: &FileUri, 
+        contents: impl AsRef<[u8]>,
+    ) -> Result<()> {
+
+        let mut file = self.open_file_appendable(uri).await?;
+
+        #[if_sync] {
+            file.write_all(contents.as_ref())?;
+        }
+        #[if_async] {
+            let contents = upgrade_bytes_ref(contents);
+            run_blocking(move || file.write_all(&contents).map_err(Into::into)).await?;
+        }
+        Ok(())
+    }
+
+    #[maybe_async]
+    pub fn write_file_atomically(
+        &self,
+        uri: &FileUri,
+        contents: impl AsRef<[u8]>,
+    ) -> Result<()> {
+
+        let (mut file, atomic) = self.open_file_writable_atomically(uri).await?;
+        let contents = upgrade_bytes_ref(contents);
+
+        match run_blocking(move || file.write_all(&contents).map(|_| file).map_err(Into::into)).await {
+            Ok(file) => self.commit_atomic_write(file, atomic).await,
+            Err(err) => {
+                self.abort_atomic_write(atomic).await;
+                Err(err)
+            },
+        }
+    }
+
+    /// 対象のファイルの代わりに書き込む一時ファイルを作成して開く。
+    /// 書き込み後に [`Self::commit_atomic_write`] か [`Self::abort_atomic_write`] を呼ぶ必要がある。
+    #[maybe_async]
+    pub fn open_file_writable_atomically(&self, uri: &FileUri) -> Result<(std::fs::File, AtomicWrite)> {
+        if let Some(path) = uri.to_path() {
+            let (Some(parent), Some(name)) = (path.parent(), path.file_name()) else {
+                return Err(Error::invalid_value(format!("uri: {}", uri.uri)))
+            };
+            let temp = parent.join(atomic_write_temp_name(&name.to_string_lossy(), "tmp"));
+            let file = {
+                let temp = temp.clone();
+                run_blocking(move || std::fs::File::create_new(temp).map_err(Into::into)).await
+            };
+            // 同じディレクトリに作成する権限がない場合は、キャッシュディレクトリを用いる
+            if let Ok(file) = file {
+                return Ok((file, AtomicWrite { target: uri.clone(), temp: AtomicWriteTemp::Path(temp) }))
+            }
+        }
+
+        let name = self.get_entry_name(uri).await?;
+
+        // 親ディレクトリが分かる場合は、同じディレクトリに一時ファイルを作成する
+        if let Some(parent) = external_storage_provider_parent_uri(uri) {
+            let mime_type = self.get_file_mime_type(uri).await?;
+            let temp_name = atomic_write_temp_name(&name, "tmp");
+            if let Ok(temp) = self.create_new_file(&parent, temp_name, Some(&mime_type)).await {
+                let temp = inherit_top_tree_uri(temp, &parent);
+                match self.open_file_writable(&temp).await {
+                    Ok(file) => return Ok((file, AtomicWrite { target: uri.clone(), temp: AtomicWriteTemp::Sibling(temp) })),
+                    Err(_) => { let _ = self.remove_file(&temp).await; },
+                }
+            }
+        }
+
+        // それ以外はキャッシュディレクトリに書き込み、後で内容をコピーする
+        let temp = self.private_dir_path(PrivateDir::Cache)?.join(atomic_write_temp_name(&name, "tmp"));
+        let file = {
+            let temp = temp.clone();
+            run_blocking(move || {
+                if let Some(dir) = temp.parent() {
+                    std::fs::create_dir_all(dir)?;
+                }
+                std::fs::File::create_new(temp).map_err(Into::into)
+            }).await?
+        };
+        Ok((file, AtomicWrite { target: uri.clone(), temp: AtomicWriteTemp::Staging(temp) }))
+    }
+
+    /// 一時ファイルの内容を保存してから、対象のファイルと置き換える。
+    #[maybe_async]
+    pub fn commit_atomic_write(&self, file: std::fs::File, atomic: AtomicWrite) -> Result<()> {
+        if let Err(err) = run_blocking(move || file.sync_all().map_err(Into::into)).await {
+            self.abort_atomic_write(atomic).await;
+            return Err(err)
+        }
+
+        let result = match &atomic.temp {
+            AtomicWriteTemp::Path(temp) => {
+                let temp = temp.clone();
+                let target = atomic.target.to_path().ok_or_else(|| Error::invalid_value(format!("uri: {}", atomic.target.uri)))?;
+                run_blocking(move || std::fs::rename(temp, target).map_err(Into::into)).await
+            },
+            AtomicWriteTemp::Sibling(temp) => {
+                match self.replace_by_rename(&atomic.target, temp).await {
+                    Ok(true) => return Ok(()),
+                    // 名前を変更できない provider では、内容をコピーする
+                    Ok(false) => self.copy_atomic_write_temp(&atomic).await,
+                    Err(err) => Err(err),
+                }
+            },
+            AtomicWriteTemp::Staging(_) => self.copy_atomic_write_temp(&atomic).await,
+        };
+
+        if result.is_err() {
+            self.abort_atomic_write(atomic).await;
+            return result
+        }
+        if !matches!(atomic.temp, AtomicWriteTemp::Path(_)) {
+            self.abort_atomic_write(atomic).await;
+        }
+        Ok(())
+    }
+
+    /// 一時ファイルを削除する。
+    #[maybe_async]
+    pub fn abort_atomic_write(&self, atomic: AtomicWrite) {
+        let _ = match atomic.temp {
+            AtomicWriteTemp::Path(temp) | AtomicWriteTemp::Staging(temp) => {
+                run_blocking(move || std::fs::remove_file(temp).map_err(Into::into)).await
+            },
+            AtomicWriteTemp::Sibling(temp) => self.remove_file(&temp).await,
+        };
+    }
+
+    /// 対象のファイルを退避してから一時ファイルの名前を対象のファイルの名前に変更する。
+    /// ExternalStorageProvider ではドキュメント ID がパスに基づくので、対象のファイルの URI は変わらない。
+    ///
+    /// 名前を変更できず、対象のファイルが元のまま残っている場合は false を返す。
+    /// 対象のファイルを削除してから名前を変更することはしないので、
+    /// 途中で失敗しても対象のファイルか退避したファイルのどちらかは必ず残る。
+    #[maybe_async]
+    fn replace_by_rename(&self, target: &FileUri, temp: &FileUri) -> Result<bool> {
+        let name = self.get_entry_name(target).await?;
+        let Ok(backup) = self.rename_entry(target, atomic_write_temp_name(&name, "bak")).await else {
+            return Ok(false)
+        };
+        let backup = inherit_top_tree_uri(backup, target);
+
+        if let Err(err) = self.rename_entry(temp, &name).await {
+            // 退避したファイルを戻せない場合は、内容をコピーする対象が無いのでエラーとする
+            return match self.rename_entry(&backup, &name).await {
+                Ok(_) => Ok(false),
+                Err(_) => Err(Error::with(format!(
+                    "failed to replace {}: {err}. The original file remains as {}", 
+                    target.uri, 
+                    backup.uri
+                ))),
+            }
+        }
+
+        // 置き換えは完了しているので、退避したファイルを削除できなくても成功とする
+        let _ = self.remove_file(&backup).await;
+        Ok(true)
+    }
+
+    /// 一時ファイルの内容を対象のファイルにコピーする。
+    /// これはアトミックではないが、一時ファイルは完全に書き込まれているので、
+    /// 書き込みの途中で中断されて対象のファイルが壊れる時間は短くなる。
+    #[maybe_async]
+    fn copy_atomic_write_temp(&self, atomic: &AtomicWrite) -> Result<()> {
+        let mut src = self.open_file_readable(&atomic.temp.uri()).await?;
+        let mut dest = self.open_file_writable(&atomic.target).await?;
+        run_blocking(move || {
+            std::io::copy(&mut src, &mut dest)?;
+            // provider によってはパイプなどで同期できないため、失敗しても無視する
+            let _ = dest.sync_all();
+            Ok(())
+        }).await
+    }
+
     #[maybe_async]
     pub fn copy_file(&self, src: &FileUri, dest: &FileUri) -> Result<()> {
         let mut src = self.open_file_readable(src).await?;
@@ -134,6 +459,956 @@ impl<'a, R: tauri::Runtime> Impls<'a, R> {
         Ok(())
     }
 
+    #[maybe_async]
+    pub fn read_dir_with_sort_and_filter(
+        &self,
+        uri: &FileUri,
+        options: &ReadDirOptions,
+        range: impl std::ops::RangeBounds<u64>
+    ) -> Result<Vec<OptionalEntry>> {
+
+        if !options.has_sort_or_filter() {
+            return self.read_dir(uri, options.entry_options, range).await.map(|i| i.collect())
+        }
+
+        let name_pattern = options.name_pattern
+            .as_deref()
+            .map(glob::Pattern::new)
+            .transpose()
+            .map_err(|e| Error::with(format!("invalid name_pattern: {e}")))?;
+
+        // ソートとフィルタは全てのエントリに対して行い、その後に範囲を適用する。
+        // 結果はキャッシュしないので、ページごとにディレクトリ全体を読み直してソートすることになる
+        let mut entries = self.read_dir(uri, options.read_entry_options(), ..).await?
+            .filter(|entry| options.matches(entry, name_pattern.as_ref()))
+            .collect::<Vec<_>>();
+
+        entries.sort_by(|a, b| options.compare(a, b));
+
+        let (offset, len) = range_to_offset_and_len(range);
+        let offset = usize::try_from(offset).unwrap_or(usize::MAX);
+        let len = len.map(|len| usize::try_from(len).unwrap_or(usize::MAX)).unwrap_or(usize::MAX);
+
+        Ok(entries.into_iter()
+            .skip(offset)
+            .take(len)
+            .map(|entry| entry.retain_fields(options.entry_options))
+            .collect())
+    }
+
+    #[maybe_async]
+    pub fn watch_dir(
+        &self,
+        uri: &FileUri,
+        interval: std::time::Duration,
+        mut on_event: impl FnMut(Result<DirWatchEvent>) + Send + 'static,
+    ) -> Result<DirWatchGuard> {
+
+        if interval.is_zero() {
+            return Err(Error::invalid_value("interval: must not be zero"))
+        }
+
+        let mut snapshot = self.read_dir_snapshot(uri).await?;
+        let (stop, stopped) = std::sync::mpsc::channel::<()>();
+        let handle = self.handle.clone();
+        let uri = uri.clone();
+
+        std::thread::spawn(move || {
+            let impls = SyncImpls { handle: &handle };
+
+            // ガードが破棄されると送信側が切断され、待機が終了する
+            while let Err(std::sync::mpsc::RecvTimeoutError::Timeout) = stopped.recv_timeout(interval) {
+                match impls.read_dir_snapshot(&uri) {
+                    Ok(new) => {
+                        for event in diff_dir_snapshots(&snapshot, &new) {
+                            on_event(Ok(event));
+                        }
+                        snapshot = new;
+                    },
+                    Err(err) => on_event(Err(err)),
+                }
+            }
+        });
+
+        Ok(DirWatchGuard::new(stop))
+    }
+
+    #[maybe_async]
+    fn read_dir_snapshot(&self, dir: &FileUri) -> Result<Vec<Entry>> {
+        self.read_dir(dir, EntryOptions::ALL, ..).await?
+            .map(|entry| {
+                let mut entry = Entry::try_from(entry)?;
+                let (Entry::File { uri, .. } | Entry::Dir { uri, .. }) = &mut entry;
+                *uri = inherit_top_tree_uri(uri.clone(), dir);
+                Ok(entry)
+            })
+            .collect()
+    }
+
+    #[maybe_async]
+    pub fn create_zip(
+        &self,
+        sources: &[FileUri],
+        dest: &FileUri,
+        options: CreateZipOptions,
+        mut on_progress: impl FnMut(u64, u64) + Send,
+    ) -> Result<()> {
+
+        const CHUNK_LEN: u64 = 1024 * 1024;
+
+        let dest_key = EntryKey::new(dest);
+
+        // ディレクトリがその中のエントリより先になるように並べる
+        let mut entries = Vec::new();
+        let mut paths = std::collections::HashSet::new();
+        for source in sources {
+            let entry = self.get_entry_info(source).await?;
+            let mut stack = vec![(source.clone(), entry.name().to_string(), entry)];
+
+            while let Some((uri, path, entry)) = stack.pop() {
+                if !paths.insert(path.clone()) {
+                    return Err(Error::with(format!("duplicate path in archive: {path}")))
+                }
+                if entry.is_dir() {
+                    let mut children = self.read_dir_snapshot(&uri).await?;
+                    children.sort_by(|a, b| b.name().cmp(a.name()));
+
+                    for child in children {
+                        // 出力先がソースのディレクトリ内にある場合、アーカイブ自身は含めない
+                        if EntryKey::new(child.uri()) == dest_key {
+                            continue
+                        }
+                        let child_path = format!("{path}/{}", child.name());
+                        stack.push((child.uri().clone(), child_path, child));
+                    }
+                }
+                entries.push((uri, path, entry));
+            }
+        }
+
+        let total = entries.iter().filter_map(|(_, _, e)| e.file_len()).sum::<u64>();
+        let mut done = 0;
+
+        let file = self.open_file_writable(dest).await?;
+        let mut zip = zip::ZipWriter::new(std::io::BufWriter::new(file));
+        let compression = match options.compression {
+            ZipCompression::Stored => zip::CompressionMethod::Stored,
+            ZipCompression::Deflated => zip::CompressionMethod::Deflated,
+        };
+
+        for (uri, path, entry) in entries {
+            let file_options = zip::write::SimpleFileOptions::default()
+                .last_modified_time(zip_date_time(entry.last_modified()));
+
+            let len = match entry {
+                Entry::File { len, .. } => len,
+                Entry::Dir { .. } => {
+                    zip = run_blocking(move || {
+                        zip.add_directory(path, file_options)?;
+                        Ok(zip)
+                    }).await?;
+                    continue
+                },
+            };
+
+            let file = self.open_file_readable(&uri).await?;
+            let file_options = file_options
+                .compression_method(compression)
+                .large_file(u32::MAX as u64 <= len);
+            let mut state = run_blocking(move || {
+                zip.start_file(path, file_options)?;
+                Ok((zip, file, Vec::with_capacity(CHUNK_LEN as usize)))
+            }).await?;
+
+            // 進捗を報告できるように、チャンクごとにブロッキングスレッドに渡す
+            loop {
+                let (mut z, mut file, mut buf) = state;
+                let (z, file, buf, read) = run_blocking(move || {
+                    buf.clear();
+                    let read = (&mut file).take(CHUNK_LEN).read_to_end(&mut buf)?;
+                    z.write_all(&buf)?;
+                    Ok((z, file, buf, read as u64))
+                }).await?;
+
+                if read == 0 {
+                    zip = z;
+                    break
+                }
+                done += read;
+                on_progress(done, u64::max(total, done));
+                state = (z, file, buf);
+            }
+        }
+
+        run_blocking(move || {
+            zip.finish()?.flush()?;
+            Ok(())
+        }).await
+    }
+
+    #[maybe_async]
+    pub fn extract_zip(
+        &self,
+        src: &FileUri,
+        dest_dir: &FileUri,
+        options: ExtractZipOptions,
+        mut on_progress: impl FnMut(u64, u64) + Send,
+    ) -> Result<Vec<ExtractZipEntry>> {
+
+        let patterns = |patterns: &[String]| patterns.iter()
+            .map(|p| RelativePathPattern::new(p))
+            .collect::<Result<Vec<_>>>();
+        let include = patterns(&options.include)?;
+        let exclude = patterns(&options.exclude)?;
+
+        let file = self.open_file_readable(src).await?;
+        let (entries, archive) = run_blocking(move || {
+            let mut archive = zip::ZipArchive::new(file)?;
+            let entries = (0..archive.len())
+                .map(|i| Ok(ZipArchiveEntry::new(i, &archive.by_index_raw(i)?)))
+                .collect::<Result<Vec<_>>>()?;
+            Ok((entries, archive))
+        }).await?;
+        let archive = std::sync::Arc::new(std::sync::Mutex::new(archive));
+
+        // 展開するエントリと展開先の相対パス。展開しないエントリは None。
+        let mut planned = Vec::new();
+        let mut total = 0;
+        for entry in entries {
+            let path = match zip_entry_relative_path(&entry.name) {
+                Ok(_) if entry.is_symlink => Err(Error::with(format!("symbolic link is not supported: {}", entry.name))),
+                path => path,
+            };
+            let selected = match &path {
+                Ok(path) => {
+                    (include.is_empty() || include.iter().any(|p| p.matches(path))) &&
+                    !exclude.iter().any(|p| p.matches(path))
+                },
+                Err(_) => true,
+            };
+
+            if selected && path.is_ok() && !entry.is_dir {
+                total += entry.len;
+            }
+            planned.push((entry, selected.then_some(path)));
+        }
+
+        // 記録されたサイズは展開中に検証されるので、ここで合計を制限すれば展開されるサイズも制限される
+        if let Some(max_total_len) = options.max_total_len {
+            if max_total_len < total {
+                return Err(Error::with(format!("zip archive is too large to extract: {total} bytes exceeds the limit of {max_total_len} bytes")))
+            }
+        }
+
+        let mut done = 0;
+        let mut on_read = |read: u64| {
+            done += read;
+            on_progress(done, u64::max(total, done));
+        };
+
+        let mut results = Vec::new();
+        for (entry, path) in planned {
+            let (name, is_dir, len) = (entry.name.clone(), entry.is_dir, entry.len);
+
+            let result = match path {
+                None => Ok(ExtractZipOutcome::Skipped),
+                Some(Err(err)) => Err(err),
+                Some(Ok(path)) if is_dir => self
+                    .create_dir_all_and_return_relative_path(dest_dir, &path).await
+                    .map(|(uri, relative_path)| ExtractZipOutcome::Extracted {
+                        dest: inherit_top_tree_uri(uri, dest_dir),
+                        relative_path,
+                    }),
+                Some(Ok(path)) => self.extract_zip_file(&archive, entry, dest_dir, &path, &mut on_read).await,
+            };
+            results.push(ExtractZipEntry { name, is_dir, len, result });
+        }
+
+        Ok(results)
+    }
+
+    #[maybe_async]
+    fn extract_zip_file(
+        &self,
+        archive: &std::sync::Arc<std::sync::Mutex<zip::ZipArchive<std::fs::File>>>,
+        entry: ZipArchiveEntry,
+        dest_dir: &FileUri,
+        path: &std::path::Path,
+        on_read: &mut (impl FnMut(u64) + Send),
+    ) -> Result<ExtractZipOutcome> {
+
+        if entry.is_encrypted {
+            return Err(Error::with(format!("encrypted entry is not supported: {}", entry.name)))
+        }
+
+        let (uri, relative_path) = self.create_new_file_and_retrun_relative_path(dest_dir, path, None).await?;
+        let uri = inherit_top_tree_uri(uri, dest_dir);
+
+        match self.write_zip_entry(archive, entry, &uri).await {
+            Ok(written) => on_read(written),
+            Err(err) => {
+                // 中途半端なファイルを残さないようにする
+                let _ = self.remove_file(&uri).await;
+                return Err(err)
+            },
+        }
+
+        Ok(ExtractZipOutcome::Extracted { dest: uri, relative_path })
+    }
+
+    /// エントリーを展開して書き込み、書き込んだバイト数を返す。
+    /// 内容が記録されたサイズと異なる場合や、CRC が一致しない場合はエラーになる。
+    #[maybe_async]
+    fn write_zip_entry(
+        &self,
+        archive: &std::sync::Arc<std::sync::Mutex<zip::ZipArchive<std::fs::File>>>,
+        entry: ZipArchiveEntry,
+        dest: &FileUri,
+    ) -> Result<u64> {
+
+        let mut out = self.open_file_writable(dest).await?;
+        let archive = std::sync::Arc::clone(archive);
+
+        run_blocking(move || {
+            let mut archive = archive.lock()?;
+            let file = archive.by_index(entry.index)?;
+
+            // 記録されたサイズより 1 バイトだけ多く読めるようにして、超過を検出する
+            let written = std::io::copy(&mut file.take(entry.len.saturating_add(1)), &mut out)?;
+            if written != entry.len {
+                let size = if entry.len < written { "larger" } else { "smaller" };
+                return Err(Error::with(format!("zip entry is {size} than its recorded size: {}", entry.name)))
+            }
+
+            // file provider によっては最終更新日時を変更できないので、失敗しても無視する
+            let _ = out.set_modified(entry.last_modified);
+            Ok(written)
+        }).await
+    }
+
+    #[maybe_async]
+    pub fn copy_dir(
+        &self,
+        src_dir: &FileUri,
+        dest_dir: &FileUri,
+        options: CopyDirOptions,
+    ) -> Result<Vec<CopyDirEntry>> {
+
+        let dest_key = EntryKey::new(dest_dir);
+        if dest_key.is_same_or_inside(&EntryKey::new(src_dir)) {
+            return Err(Error::with(format!("cannot copy a directory into itself: {}", dest_dir.uri)))
+        }
+
+        struct Frame {
+            src: FileUri,
+            dest: FileUri,
+            src_relative_path: std::path::PathBuf,
+            dest_relative_path: std::path::PathBuf,
+
+            /// results 内のこのディレクトリ自身の位置。ルートの場合は None。
+            index: Option<usize>,
+        }
+
+        const OPTIONS: EntryOptions = EntryOptions {
+            uri: true,
+            name: true,
+            mime_type: true,
+            ..EntryOptions::NONE
+        };
+
+        let mut results: Vec<CopyDirEntry> = Vec::new();
+        let mut stack = vec![Frame {
+            src: src_dir.clone(),
+            dest: dest_dir.clone(),
+            src_relative_path: std::path::PathBuf::new(),
+            dest_relative_path: std::path::PathBuf::new(),
+            index: None,
+        }];
+
+        while let Some(frame) = stack.pop() {
+            let listed = match self.read_dir(&frame.src, OPTIONS, ..).await {
+                Ok(children) => match self.read_dir(&frame.dest, OPTIONS, ..).await {
+                    Ok(existing) => Ok((children.collect::<Vec<_>>(), existing.collect::<Vec<_>>())),
+                    Err(err) => Err(err),
+                },
+                Err(err) => Err(err),
+            };
+            let (children, existing) = match listed {
+                Ok(listed) => listed,
+                Err(err) => match frame.index {
+                    Some(i) => {
+                        // 読めなかったディレクトリの子孫は飛ばし、ディレクトリ自身の結果をエラーにする
+                        results[i].result = Err(err);
+                        continue
+                    },
+                    None => return Err(err),
+                },
+            };
+
+            let existing = existing.into_iter()
+                .filter_map(|e| Some((e.name()?.to_string(), e)))
+                .collect::<HashMap<_, _>>();
+
+            // Rename で付ける名前は、既存のエントリだけでなく後続のエントリとも衝突しないようにする
+            let mut taken = existing.keys()
+                .cloned()
+                .chain(children.iter().filter_map(|e| e.name().map(ToString::to_string)))
+                .collect::<std::collections::HashSet<_>>();
+
+            for child in children {
+                let name = child.name_or_err()?.to_string();
+                let src = inherit_top_tree_uri(child.uri_or_err()?.clone(), &frame.src);
+                let src_relative_path = frame.src_relative_path.join(&name);
+                let dest_relative_path = |path: &std::path::Path| frame.dest_relative_path.join(path);
+                let is_dir = child.is_dir();
+
+                // document ID がパスに基づかない provider では事前に判定できないため、
+                // コピー先のディレクトリに辿り着いた時点で、その中に入らないようにする
+                if is_dir && EntryKey::new(&src) == dest_key {
+                    let err = Error::with(format!("cannot copy a directory into itself: {}", dest_dir.uri));
+                    results.push(CopyDirEntry { src, relative_path: src_relative_path, is_dir, result: Err(err) });
+                    continue
+                }
+
+                let conflict_err = |existing_is_dir: bool| {
+                    let path = dest_relative_path(name.as_ref());
+                    match existing_is_dir {
+                        true => Error::with(format!("directory already exists: {path:?}")),
+                        false => Error::with(format!("file already exists: {path:?}")),
+                    }
+                };
+
+                let result = match existing.get(&name).map(|e| (e.is_dir(), e.uri())) {
+                    None => self
+                        .copy_dir_entry(&src, &frame.dest, &name, child.file_mime_type()).await
+                        .map(|(dest, path)| CopyDirOutcome::Copied { dest, relative_path: dest_relative_path(&path) }),
+
+                    Some((true, Some(dest))) if is_dir => Ok(CopyDirOutcome::Merged {
+                        dest: inherit_top_tree_uri(dest.clone(), &frame.dest),
+                        relative_path: dest_relative_path(name.as_ref()),
+                    }),
+
+                    Some((existing_is_dir, _)) if options.on_conflict == CopyConflictPolicy::Fail => {
+                        return Err(conflict_err(existing_is_dir))
+                    },
+
+                    Some(_) if options.on_conflict == CopyConflictPolicy::Skip => {
+                        Ok(CopyDirOutcome::Skipped)
+                    },
+
+                    Some(_) if options.on_conflict == CopyConflictPolicy::Rename => {
+                        let new_name = unique_name(&name, |n| taken.contains(n));
+                        taken.insert(new_name.clone());
+
+                        self.copy_dir_entry(&src, &frame.dest, &new_name, child.file_mime_type()).await
+                            .map(|(dest, path)| CopyDirOutcome::Renamed { dest, relative_path: dest_relative_path(&path) })
+                    },
+
+                    Some((false, Some(dest))) if !is_dir && options.on_conflict == CopyConflictPolicy::Overwrite => {
+                        let dest = inherit_top_tree_uri(dest.clone(), &frame.dest);
+                        self.copy_file(&src, &dest).await
+                            .map(|_| CopyDirOutcome::Overwritten { dest, relative_path: dest_relative_path(name.as_ref()) })
+                    },
+
+                    // 上書きでは、ファイルとディレクトリを置き換えることはできない
+                    Some((existing_is_dir, _)) => Err(conflict_err(existing_is_dir)),
+                };
+
+                if let Ok(CopyDirOutcome::Copied { dest, relative_path }
+                    | CopyDirOutcome::Merged { dest, relative_path }
+                    | CopyDirOutcome::Renamed { dest, relative_path }) = &result
+                {
+                    if is_dir {
+                        stack.push(Frame {
+                            src: src.clone(),
+                            dest: dest.clone(),
+                            src_relative_path: src_relative_path.clone(),
+                            dest_relative_path: relative_path.clone(),
+                            index: Some(results.len()),
+                        });
+                    }
+                }
+
+                results.push(CopyDirEntry { src, relative_path: src_relative_path, is_dir, result });
+            }
+        }
+
+        Ok(results)
+    }
+
+    /// `dest_dir` 直下に `name` でエントリを作成し、ファイルの場合は内容をコピーする。
+    /// 作成されたエントリの URI と `dest_dir` からの相対パスを返す。
+    #[maybe_async]
+    fn copy_dir_entry(
+        &self,
+        src: &FileUri,
+        dest_dir: &FileUri,
+        name: &str,
+        mime_type: Option<&str>,
+    ) -> Result<(FileUri, std::path::PathBuf)> {
+
+        if mime_type.is_none() {
+            let (uri, relative_path) = self.create_dir_all_and_return_relative_path(dest_dir, name).await?;
+            return Ok((inherit_top_tree_uri(uri, dest_dir), relative_path))
+        }
+
+        let (uri, relative_path) = self.create_new_file_and_retrun_relative_path(dest_dir, name, mime_type).await?;
+        let uri = inherit_top_tree_uri(uri, dest_dir);
+        if let Err(err) = self.copy_file(src, &uri).await {
+            // 中途半端なファイルを残さないようにする
+            let _ = self.remove_file(&uri).await;
+            return Err(err)
+        }
+        Ok((uri, relative_path))
+    }
+
+    #[maybe_async]
+    pub fn move_entry(&self, src: &FileUri, dest_dir: &FileUri) -> Result<FileUri> {
+        // 同じ provider 内であれば、まずネイティブな移動を試みる。
+        // ネイティブに移動できない場合のみ None が返るので、コピーにフォールバックする。
+        // 同名のエントリがあるなどのエラーはそのまま返す。
+        if let Some(uri) = self.move_entry_natively(src, dest_dir).await? {
+            return Ok(inherit_top_tree_uri(uri, dest_dir))
+        }
+
+        let name = self.get_entry_name(src).await?;
+        let entry_type = self.get_entry_type(src).await?;
+
+        let conflicts = self.read_dir(dest_dir, EntryOptions::URI_AND_NAME, ..).await?
+            .any(|e| e.name() == Some(name.as_str()));
+        if conflicts {
+            return Err(Error::with(format!("entry already exists: {name}")))
+        }
+
+        match entry_type {
+            EntryType::File { mime_type } => self.move_file_by_copy(src, dest_dir, &name, &mime_type).await,
+            EntryType::Dir => self.move_dir_by_copy(src, dest_dir, &name).await,
+        }
+    }
+
+    #[maybe_async]
+    fn move_file_by_copy(
+        &self,
+        src: &FileUri,
+        dest_dir: &FileUri,
+        name: &str,
+        mime_type: &str,
+    ) -> Result<FileUri> {
+
+        let (dest, _) = self.copy_dir_entry(src, dest_dir, name, Some(mime_type)).await?;
+
+        let verified = match (self.get_file_len(src).await, self.get_file_len(&dest).await) {
+            (Ok(src_len), Ok(dest_len)) if src_len == dest_len => Ok(()),
+            (Ok(_), Ok(_)) => Err(Error::with(format!("copied file does not match the source: {name}"))),
+            (Err(err), _) | (_, Err(err)) => Err(err),
+        };
+
+        // 元のファイルを削除できなかった場合も、コピーを取り除いて元の状態に戻す
+        let result = match verified {
+            Ok(_) => self.remove_file(src).await,
+            Err(err) => Err(err),
+        };
+        if let Err(err) = result {
+            let _ = self.remove_file(&dest).await;
+            return Err(err)
+        }
+
+        Ok(dest)
+    }
+
+    #[maybe_async]
+    fn move_dir_by_copy(&self, src: &FileUri, dest_dir: &FileUri, name: &str) -> Result<FileUri> {
+        let (dest, _) = self.copy_dir_entry(src, dest_dir, name, None).await?;
+
+        let copied = self.copy_dir(src, &dest, CopyDirOptions { on_conflict: CopyConflictPolicy::Fail }).await;
+        let verified = match copied {
+            Ok(entries) => self.verify_copied_dir(src, &dest, entries).await,
+            Err(err) => Err(err),
+        };
+        if let Err(err) = verified {
+            let _ = self.remove_dir_all(&dest).await;
+            return Err(err)
+        }
+
+        // 元のディレクトリの削除が途中で失敗した場合、
+        // 完全なコピーは出力先にしか無いので、それは残したままエラーを返す。
+        if let Err(err) = self.remove_dir_all(src).await {
+            return Err(Error::with(format!(
+                "copied to {}, but failed to remove the source: {err}",
+                dest.uri
+            )))
+        }
+
+        Ok(dest)
+    }
+
+    /// 全てのエントリがコピーされ、ファイルの長さが一致しているかを確認する。
+    #[maybe_async]
+    fn verify_copied_dir(&self, src: &FileUri, dest: &FileUri, entries: Vec<CopyDirEntry>) -> Result<()> {
+        let src_lens = self.read_file_lens_recursively(src).await?;
+        let dest_lens = self.read_file_lens_recursively(dest).await?;
+
+        if src_lens.len() != entries.len() || dest_lens.len() != entries.len() {
+            return Err(Error::with(format!("copied directory does not match the source: {}", dest.uri)))
+        }
+
+        for entry in entries {
+            let outcome = entry.result?;
+            let dest_len = outcome.relative_path().and_then(|p| dest_lens.get(p));
+            let src_len = src_lens.get(&entry.relative_path);
+
+            if dest_len.is_none() || dest_len != src_len {
+                return Err(Error::with(format!(
+                    "copied entry does not match the source: {}",
+                    entry.relative_path.display()
+                )))
+            }
+        }
+
+        Ok(())
+    }
+
+    #[maybe_async]
+    pub fn sync_dir(
+        &self,
+        src_dir: &FileUri,
+        dest_dir: &FileUri,
+        options: SyncOptions,
+        mut on_progress: impl FnMut(&SyncAction, u64, u64) + Send,
+    ) -> Result<Vec<SyncAction>> {
+
+        if EntryKey::new(dest_dir).is_same_or_inside(&EntryKey::new(src_dir)) {
+            return Err(Error::with(format!("cannot sync a directory into itself: {}", dest_dir.uri)))
+        }
+
+        let (actions, mut dest_dirs) = self.plan_sync_dir(src_dir, dest_dir, options).await?;
+        if options.dry_run {
+            return Ok(actions)
+        }
+
+        let total = actions.len() as u64;
+        for (i, action) in actions.iter().enumerate() {
+            self.run_sync_action(action, &mut dest_dirs).await?;
+            on_progress(action, i as u64 + 1, total);
+        }
+
+        Ok(actions)
+    }
+
+    /// 同期に必要な操作と、出力先に既に存在するディレクトリの URI を返す。
+    /// 操作は、ディレクトリの作成がその中のエントリの操作より先になるように並ぶ。
+    #[maybe_async]
+    fn plan_sync_dir(
+        &self,
+        src_dir: &FileUri,
+        dest_dir: &FileUri,
+        options: SyncOptions,
+    ) -> Result<(Vec<SyncAction>, HashMap<std::path::PathBuf, FileUri>)> {
+
+        let mut actions = Vec::new();
+        let mut dest_dirs = HashMap::new();
+        dest_dirs.insert(std::path::PathBuf::new(), dest_dir.clone());
+        let dest_key = EntryKey::new(dest_dir);
+
+        // 出力先にまだ存在しないディレクトリの場合、dest は None
+        let mut stack = vec![(src_dir.clone(), Some(dest_dir.clone()), std::path::PathBuf::new())];
+
+        while let Some((src, dest, relative_path)) = stack.pop() {
+            let src_entries = self.read_dir(&src, EntryOptions::ALL, ..).await?.collect::<Vec<_>>();
+            let mut dest_entries = HashMap::new();
+            if let Some(dest) = &dest {
+                for entry in self.read_dir(dest, EntryOptions::ALL, ..).await? {
+                    let mut entry = Entry::try_from(entry)?;
+                    let (Entry::File { uri, .. } | Entry::Dir { uri, .. }) = &mut entry;
+                    *uri = inherit_top_tree_uri(uri.clone(), dest);
+                    dest_entries.insert(entry.name().to_string(), entry);
+                }
+            }
+
+            for src_entry in src_entries {
+                let src_entry = Entry::try_from(src_entry)?;
+                let src_uri = inherit_top_tree_uri(src_entry.uri().clone(), &src);
+                let path = relative_path.join(src_entry.name());
+
+                let dest_entry = match dest_entries.remove(src_entry.name()) {
+                    // 種類が異なる場合は、削除してから作り直す
+                    Some(dest_entry) if dest_entry.is_dir() != src_entry.is_dir() => {
+                        if !options.delete_extras {
+                            return Err(Error::with(format!("entry of a different type already exists: {path:?}")))
+                        }
+                        actions.push(remove_action(dest_entry, path.clone()));
+                        None
+                    },
+                    dest_entry => dest_entry,
+                };
+
+                // document ID がパスに基づかない provider では事前に判定できないため、
+                // 何も変更しない計画の段階でコピー先のディレクトリに辿り着いた場合にエラーにする
+                if src_entry.is_dir() && EntryKey::new(&src_uri) == dest_key {
+                    return Err(Error::with(format!("cannot sync a directory into itself: {}", dest_dir.uri)))
+                }
+
+                match (src_entry.is_dir(), dest_entry) {
+                    (true, Some(dest_entry)) => {
+                        let dest_uri = dest_entry.uri().clone();
+                        dest_dirs.insert(path.clone(), dest_uri.clone());
+                        stack.push((src_uri, Some(dest_uri), path));
+                    },
+                    (true, None) => {
+                        actions.push(SyncAction::CreateDir { src: src_uri.clone(), relative_path: path.clone() });
+                        stack.push((src_uri, None, path));
+                    },
+                    (false, Some(dest_entry)) => {
+                        if self.is_file_changed(&src_entry, &dest_entry, options.compare_contents).await? {
+                            actions.push(SyncAction::UpdateFile {
+                                src: src_uri,
+                                dest: dest_entry.uri().clone(),
+                                relative_path: path,
+                            });
+                        }
+                    },
+                    (false, None) => {
+                        actions.push(SyncAction::CreateFile { src: src_uri, relative_path: path });
+                    },
+                }
+            }
+
+            if options.delete_extras {
+                for (name, dest_entry) in dest_entries {
+                    actions.push(remove_action(dest_entry, relative_path.join(name)));
+                }
+            }
+        }
+
+        Ok((actions, dest_dirs))
+    }
+
+    #[maybe_async]
+    fn is_file_changed(&self, src: &Entry, dest: &Entry, compare_contents: bool) -> Result<bool> {
+        if src.file_len() != dest.file_len() {
+            return Ok(true)
+        }
+        if compare_contents {
+            return Ok(!self.has_same_contents(src.uri(), dest.uri()).await?)
+        }
+
+        // コピー先の最終更新日時はコピーした時刻になるので、それより新しい場合のみ変更とみなす
+        Ok(dest.last_modified() < src.last_modified())
+    }
+
+    #[maybe_async]
+    fn has_same_contents(&self, a: &FileUri, b: &FileUri) -> Result<bool> {
+        let a = self.open_file_readable(a).await?;
+        let b = self.open_file_readable(b).await?;
+
+        run_blocking(move || {
+            let mut a = std::io::BufReader::new(a);
+            let mut b = std::io::BufReader::new(b);
+
+            loop {
+                let buf_a = a.fill_buf()?;
+                let buf_b = b.fill_buf()?;
+                let len = usize::min(buf_a.len(), buf_b.len());
+
+                if len == 0 {
+                    return Ok(buf_a.is_empty() && buf_b.is_empty())
+                }
+                if buf_a[..len] != buf_b[..len] {
+                    return Ok(false)
+                }
+
+                a.consume(len);
+                b.consume(len);
+            }
+        }).await
+    }
+
+    #[maybe_async]
+    fn run_sync_action(
+        &self,
+        action: &SyncAction,
+        dest_dirs: &mut HashMap<std::path::PathBuf, FileUri>,
+    ) -> Result<()> {
+
+        let path = action.relative_path();
+        let (parent, name) = match (path.parent(), path.file_name()) {
+            (Some(parent), Some(name)) => (parent, name.to_string_lossy()),
+            _ => return Err(Error::invalid_value(format!("relative_path: {path:?}"))),
+        };
+
+        match action {
+            SyncAction::CreateDir { .. } => {
+                let parent = dest_dirs.get(parent)
+                    .cloned()
+                    .ok_or_else(|| Error::with(format!("destination directory not found: {parent:?}")))?;
+                let (uri, _) = self.create_dir_all_and_return_relative_path(&parent, name.as_ref()).await?;
+                dest_dirs.insert(path.to_path_buf(), inherit_top_tree_uri(uri, &parent));
+            },
+            SyncAction::CreateFile { src, .. } => {
+                let parent = dest_dirs.get(parent)
+                    .ok_or_else(|| Error::with(format!("destination directory not found: {parent:?}")))?;
+                let mime_type = self.get_file_mime_type(src).await?;
+                self.copy_dir_entry(src, parent, &name, Some(&mime_type)).await?;
+            },
+            SyncAction::UpdateFile { src, dest, .. } => self.copy_file(src, dest).await?,
+            SyncAction::RemoveFile { dest, .. } => self.remove_file(dest).await?,
+            SyncAction::RemoveDir { dest, .. } => self.remove_dir_all(dest).await?,
+        }
+
+        Ok(())
+    }
+
+    /// ディレクトリ以下の全てのエントリの相対パスと、ファイルの場合はその長さを返す。
+    #[maybe_async]
+    fn read_file_lens_recursively(
+        &self,
+        dir: &FileUri,
+    ) -> Result<HashMap<std::path::PathBuf, Option<u64>>> {
+
+        const OPTIONS: EntryOptions = EntryOptions {
+            uri: true,
+            name: true,
+            len: true,
+            ..EntryOptions::NONE
+        };
+
+        let mut lens = HashMap::new();
+        let mut stack = vec![(dir.clone(), std::path::PathBuf::new())];
+
+        while let Some((uri, relative_path)) = stack.pop() {
+            for entry in self.read_dir(&uri, OPTIONS, ..).await? {
+                let path = relative_path.join(entry.name_or_err()?);
+                if entry.is_dir() {
+                    let uri = inherit_top_tree_uri(entry.uri_or_err()?.clone(), &uri);
+                    stack.push((uri, path.clone()));
+                }
+                lens.insert(path, entry.file_len());
+            }
+        }
+
+        Ok(lens)
+    }
+
+    #[maybe_async]
+    pub fn find_duplicates(
+        &self,
+        roots: &[FileUri],
+        options: FindDuplicatesOptions,
+        mut on_progress: impl FnMut(FindDuplicatesPhase, u64, u64) + Send,
+    ) -> Result<Vec<DuplicateGroup>> {
+
+        // 結果を基に重複を削除できるように、衝突しうるハッシュではなく SHA-256 で比較する
+        const ALGORITHM: HashAlgorithm = HashAlgorithm::Sha256;
+
+        // ルートが重なっている場合に同じファイルを重複とみなさないように、
+        // 異なるツリー URI を経由していても同じドキュメントであれば除く
+        let mut seen = std::collections::HashSet::new();
+        let mut by_len = std::collections::BTreeMap::<u64, Vec<Entry>>::new();
+        for root in roots {
+            for entry in self.read_files_recursively(root).await? {
+                let len = entry.file_len_or_err()?;
+                if options.min_len <= len && seen.insert(EntryKey::new(entry.uri())) {
+                    by_len.entry(len).or_default().push(entry);
+                }
+            }
+        }
+
+        let candidates = by_len.into_iter()
+            .filter(|(_, entries)| 2 <= entries.len())
+            .collect::<Vec<_>>();
+
+        let total = candidates.iter().map(|(_, entries)| entries.len() as u64).sum();
+        let mut done = 0;
+        let mut partial_groups = Vec::new();
+        for (len, entries) in candidates {
+            let mut by_hash = std::collections::BTreeMap::<Vec<u8>, Vec<Entry>>::new();
+            for entry in entries {
+                let hash = self.hash_file_prefix(entry.uri(), ALGORITHM, Some(options.partial_hash_len)).await?;
+                by_hash.entry(hash).or_default().push(entry);
+                done += 1;
+                on_progress(FindDuplicatesPhase::PartialHash, done, total);
+            }
+            partial_groups.extend(by_hash.into_values()
+                .filter(|entries| 2 <= entries.len())
+                .map(|entries| (len, entries)));
+        }
+
+        // 全体が部分ハッシュの範囲に収まるファイルは、もう一度読む必要がない
+        let total = partial_groups.iter()
+            .filter(|(len, _)| options.partial_hash_len < *len)
+            .map(|(_, entries)| entries.len() as u64)
+            .sum();
+        let mut done = 0;
+        let mut groups = Vec::new();
+        for (len, entries) in partial_groups {
+            if len <= options.partial_hash_len {
+                groups.push(DuplicateGroup { len, entries });
+                continue
+            }
+
+            let mut by_hash = std::collections::BTreeMap::<Vec<u8>, Vec<Entry>>::new();
+            for entry in entries {
+                let hash = self.hash_file_prefix(entry.uri(), ALGORITHM, None).await?;
+                by_hash.entry(hash).or_default().push(entry);
+                done += 1;
+                on_progress(FindDuplicatesPhase::FullHash, done, total);
+            }
+            groups.extend(by_hash.into_values()
+                .filter(|entries| 2 <= entries.len())
+                .map(|entries| DuplicateGroup { len, entries }));
+        }
+
+        groups.sort_by_key(|group| std::cmp::Reverse(group.len));
+        Ok(groups)
+    }
+
+    #[maybe_async]
+    pub fn snapshot_tree(&self, uri: &FileUri, options: SnapshotTreeOptions) -> Result<TreeManifest> {
+        let mut manifest = TreeManifest::default();
+        let mut stack = vec![(uri.clone(), std::path::PathBuf::new())];
+
+        while let Some((dir, relative_path)) = stack.pop() {
+            for entry in self.read_dir_snapshot(&dir).await? {
+                let path = relative_path.join(entry.name());
+                let manifest_entry = match &entry {
+                    Entry::File { uri, last_modified, len, mime_type, .. } => {
+                        let hash = match options.hash {
+                            true => Some(self.hash_file(uri, options.hash_algorithm).await?),
+                            false => None,
+                        };
+                        let hash = hash.map(|hash| hash.iter().map(|b| format!("{b:02x}")).collect());
+                        ManifestEntry::File { last_modified: *last_modified, len: *len, mime_type: mime_type.clone(), hash }
+                    },
+                    Entry::Dir { uri, last_modified, .. } => {
+                        stack.push((uri.clone(), path.clone()));
+                        ManifestEntry::Dir { last_modified: *last_modified }
+                    },
+                };
+                manifest.entries.insert(path, manifest_entry);
+            }
+        }
+
+        Ok(manifest)
+    }
+
+    /// ディレクトリ以下の全てのファイルを返す。
+    #[maybe_async]
+    fn read_files_recursively(&self, dir: &FileUri) -> Result<Vec<Entry>> {
+        let mut files = Vec::new();
+        let mut stack = vec![dir.clone()];
+
+        while let Some(dir) = stack.pop() {
+            for entry in self.read_dir_snapshot(&dir).await? {
+                match entry.is_dir() {
+                    true => stack.push(entry.uri().clone()),
+                    false => files.push(entry),
+                }
+            }
+        }
+
+        Ok(files)
+    }
+
     #[maybe_async]
     pub fn get_file_thumbnail(
         &self, 
@@ -156,7 +1431,7 @@ impl<'a, R: tauri::Runtime> Impls<'a, R> {
             return run_blocking(move || Ok(std::fs::metadata(&path)?.is_dir())).await
         }
 
-        Ok(self.get_entry_type(&uri).await?.is_dir())
+        Ok(self.get_entry_type(uri).await?.is_dir())
     }
 
     #[maybe_async]
@@ -165,7 +1440,7 @@ impl<'a, R: tauri::Runtime> Impls<'a, R> {
             return run_blocking(move || Ok(std::fs::metadata(&path)?.is_file())).await
         }
         
-        Ok(self.get_entry_type(&uri).await?.is_file())
+        Ok(self.get_entry_type(uri).await?.is_file())
     }
 
     #[maybe_async]
@@ -580,4 +1855,20 @@ impl<'a, R: tauri::Runtime> Impls<'a, R> {
 
         self.get_media_store_file_path(uri).await
     }
-}
\ No newline at end of file
+}
+
+
+/// 子孫の URI にも元のディレクトリの document_top_tree_uri を引き継ぐ
+pub(crate) fn inherit_top_tree_uri(mut uri: FileUri, parent: &FileUri) -> FileUri {
+    if uri.document_top_tree_uri.is_none() {
+        uri.document_top_tree_uri = parent.document_top_tree_uri.clone();
+    }
+    uri
+}
+
+fn remove_action(dest: Entry, relative_path: std::path::PathBuf) -> SyncAction {
+    match dest {
+        Entry::File { uri, .. } => SyncAction::RemoveFile { dest: uri, relative_path },
+        Entry::Dir { uri, .. } => SyncAction::RemoveDir { dest: uri, relative_path },
+    }
+}
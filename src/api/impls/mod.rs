@@ -16,40 +16,29 @@ macro_rules! impl_de {
     };
 }
 
-macro_rules! fn_get_or_init {
-    ($name:ident, $T:ty) => {
-
-        fn $name(init: impl FnOnce() -> Result<$T>) -> Result<&'static $T> {
-            static VALUE: std::sync::OnceLock<$T> = std::sync::OnceLock::new();
-
-            Ok(match VALUE.get() {
-                Some(value) => value,
-                None => {
-                    VALUE.set(init()?).ok();
-                    VALUE.get().expect("Should call 'set' before 'get'")
-                }
-            })
-        }     
-    };
-}
-
 mod ext;
 mod raw;
 
+pub(crate) use ext::inherit_top_tree_uri;
+
 use serde::{de::DeserializeOwned, Serialize};
 use std::collections::{HashMap, VecDeque};
 use std::hash::Hash;
 use std::borrow::Borrow;
 use crate::*;
+use super::Handle;
 use sync_async::sync_async;
 
 
 #[sync_async]
 pub struct Impls<'a, R: tauri::Runtime> {
-    pub handle: &'a tauri::plugin::PluginHandle<R>
+    pub handle: &'a Handle<R>
 }
 
-#[sync_async]
+#[sync_async(
+    use(if_async) async_utils::run_blocking;
+    use(if_sync) sync_utils::run_blocking;
+)]
 impl<'a, R: tauri::Runtime> Impls<'a, R> {
 
     #[maybe_async]
@@ -59,11 +48,37 @@ impl<'a, R: tauri::Runtime> Impls<'a, R> {
         payload: impl Serialize
     ) -> Result<D> {
         
+        #[cfg(target_os = "android")] {
+            if let Some(plugin) = &self.handle.plugin {
+                return Self::invoke_plugin(plugin, command, payload).await
+            }
+        }
+        #[cfg(feature = "custom-backend")] {
+            if let Some(backend) = &self.handle.backend {
+                let backend = std::sync::Arc::clone(backend);
+                let command = command.to_string();
+                let payload = serde_json::to_value(payload)?;
+                let value = run_blocking(move || backend.invoke(&command, payload)).await?;
+                return Ok(serde_json::from_value(value)?)
+            }
+        }
+
+        Err(Error::NOT_ANDROID)
+    }
+
+    #[cfg(target_os = "android")]
+    #[maybe_async]
+    fn invoke_plugin<D: DeserializeOwned>(
+        plugin: &tauri::plugin::PluginHandle<R>,
+        command: &str,
+        payload: impl Serialize
+    ) -> Result<D> {
+
         #[if_sync] {
-            self.handle.run_mobile_plugin(command, payload).map_err(Into::into)
+            plugin.run_mobile_plugin(command, payload).map_err(Into::into)
         }
         #[if_async] {
-            self.handle.run_mobile_plugin_async(command, payload).await.map_err(Into::into)
+            plugin.run_mobile_plugin_async(command, payload).await.map_err(Into::into)
         }
     }
 
@@ -74,6 +89,38 @@ impl<'a, R: tauri::Runtime> Impls<'a, R> {
         payload: impl Serialize
     ) -> Result<D> {
         
-        self.handle.run_mobile_plugin(command, payload).map_err(Into::into)
+        #[cfg(target_os = "android")] {
+            if let Some(plugin) = &self.handle.plugin {
+                return plugin.run_mobile_plugin(command, payload).map_err(Into::into)
+            }
+        }
+        #[cfg(feature = "custom-backend")] {
+            if let Some(backend) = &self.handle.backend {
+                let payload = serde_json::to_value(payload)?;
+                let value = backend.invoke(command, payload)?;
+                return Ok(serde_json::from_value(value)?)
+            }
+        }
+
+        Err(Error::NOT_ANDROID)
     }
-}
\ No newline at end of file
+}
+
+/// アプリ起動中に変更されることのない値のキャッシュ。
+/// [`Handle`] ごとに保持される。
+#[derive(Default)]
+pub(crate) struct Cache {
+    consts: std::sync::OnceLock<raw::Consts>,
+    private_dir_paths: std::sync::OnceLock<raw::PrivateDirPaths>,
+    is_legacy_storage: std::sync::OnceLock<bool>,
+}
+
+fn get_or_try_init<T>(cell: &std::sync::OnceLock<T>, init: impl FnOnce() -> Result<T>) -> Result<&T> {
+    Ok(match cell.get() {
+        Some(value) => value,
+        None => {
+            cell.set(init()?).ok();
+            cell.get().expect("Should call 'set' before 'get'")
+        }
+    })
+}
@@ -146,6 +146,17 @@ impl<'a, R: tauri::Runtime> Impls<'a, R> {
             .await
     }
 
+    /// 同じ provider 内などでネイティブに移動できない場合は None を返す。
+    #[maybe_async]
+    pub fn move_entry_natively(&self, uri: &FileUri, dest_dir: &FileUri) -> Result<Option<FileUri>> {
+        impl_se!(struct Req<'a> { uri: &'a FileUri, dest_dir: &'a FileUri });
+        impl_de!(struct Res { uri: Option<FileUri> });
+
+        self.invoke::<Res>("moveEntry", Req { uri, dest_dir })
+            .await
+            .map(|v| v.uri)
+    }
+
     #[maybe_async]
     pub fn remove_file(&self, uri: &FileUri) -> Result<()> {
         impl_se!(struct Req<'a> { uri: &'a FileUri });
@@ -457,17 +468,21 @@ impl<'a, R: tauri::Runtime> Impls<'a, R> {
     }
 
     #[always_sync]
-    pub fn consts(&self) -> Result<&'static Consts> {
-        get_or_init_const(|| self.invoke_sync::<Consts>("getConsts", ""))
+    pub fn consts(&self) -> Result<&'a Consts> {
+        get_or_try_init(
+            &self.handle.cache.consts, 
+            || self.invoke_sync::<Consts>("getConsts", "")
+        )
     }
 
     #[always_sync]
     pub fn private_dir_path(
         &self, 
         dir: PrivateDir
-    ) -> Result<&'static std::path::PathBuf> {
+    ) -> Result<&'a std::path::PathBuf> {
 
-        let paths = get_or_init_private_dir_paths(
+        let paths = get_or_try_init(
+            &self.handle.cache.private_dir_paths,
             || self.invoke_sync::<PrivateDirPaths>("getPrivateBaseDirAbsolutePaths", "")
         )?;
 
@@ -777,7 +792,7 @@ impl<'a, R: tauri::Runtime> Impls<'a, R> {
 
     #[always_sync]
     pub fn is_legacy_storage(&self) -> Result<bool> {
-        let is_legacy_storage = get_or_init_is_legacy_storage(move || {
+        let is_legacy_storage = get_or_try_init(&self.handle.cache.is_legacy_storage, move || {
             impl_de!(struct Res { value: bool });
 
             self.invoke_sync::<Res>("isLegacyStorage", ()).map(|res| res.value)
@@ -1088,17 +1103,13 @@ impl<'a, R: tauri::Runtime> Impls<'a, R> {
     }
 }
 
-fn_get_or_init!(get_or_init_is_legacy_storage, bool);
-fn_get_or_init!(get_or_init_const, Consts);
-fn_get_or_init!(get_or_init_private_dir_paths, PrivateDirPaths);
-
 static MAP_OF_EXT_AND_MIME_TYPE: std::sync::LazyLock<std::sync::Mutex<BoundedHashMap<String, Option<String>>>> = std::sync::LazyLock::new(
     || std::sync::Mutex::new(BoundedHashMap::with_bound(1000))
 );
 
 #[derive(serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
-struct PrivateDirPaths {
+pub struct PrivateDirPaths {
     data: std::path::PathBuf, 
     cache: std::path::PathBuf, 
     no_backup_data: std::path::PathBuf, 
@@ -1,6 +1,7 @@
-#[cfg(target_os = "android")]
+#[cfg(afs_impls)]
 mod impls;
 
+mod handle;
 mod android_fs;
 mod file_opener;
 mod file_picker;
@@ -9,10 +10,14 @@ mod private_storage;
 mod public_storage;
 mod utils;
 mod progress_notification_guard;
+mod walk_dir;
+mod read_dir;
 
 pub(crate) mod models;
 pub(crate) mod consts;
 
+pub(crate) use handle::Handle;
+
 pub mod api_async {
     pub use crate::api::android_fs::AsyncAndroidFs as AndroidFs;
     pub use crate::api::file_opener::AsyncFileOpener as FileOpener;
@@ -22,6 +27,8 @@ pub mod api_async {
     pub use crate::api::public_storage::AsyncPublicStorage as PublicStorage;
     pub use crate::api::utils::AsyncUtils as Utils;
     pub use crate::api::progress_notification_guard::AsyncProgressNotificationGuard as ProgressNotificationGuard;
+    pub use crate::api::walk_dir::AsyncWalkDir as WalkDir;
+    pub use crate::api::read_dir::AsyncReadDir as ReadDir;
 }
 
 pub mod api_sync {
@@ -33,4 +40,6 @@ pub mod api_sync {
     pub use crate::api::public_storage::SyncPublicStorage as PublicStorage;
     pub use crate::api::utils::SyncUtils as Utils;
     pub use crate::api::progress_notification_guard::SyncProgressNotificationGuard as ProgressNotificationGuard;
+    pub use crate::api::walk_dir::SyncWalkDir as WalkDir;
+    pub use crate::api::read_dir::SyncReadDir as ReadDir;
 }
\ No newline at end of file
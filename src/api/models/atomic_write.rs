@@ -0,0 +1,100 @@
+#[allow(unused_imports)]
+use crate::*;
+
+
+/// 一時ファイルに書き込み、完了後に対象のファイルと置き換える書き込みの状態。
+#[cfg(afs_impls)]
+#[derive(Debug)]
+pub(crate) struct AtomicWrite {
+    pub target: FileUri,
+    pub temp: AtomicWriteTemp,
+}
+
+/// [`AtomicWrite`] の一時ファイル。
+#[cfg(afs_impls)]
+#[derive(Debug)]
+pub(crate) enum AtomicWriteTemp {
+
+    /// 対象のファイルと同じディレクトリのパス。rename で置き換える。
+    Path(std::path::PathBuf),
+
+    /// 対象のファイルと同じディレクトリの SAF のドキュメント。
+    /// 名前の変更で置き換え、できない場合は内容をコピーする。
+    Sibling(FileUri),
+
+    /// キャッシュディレクトリのパス。内容をコピーする。
+    Staging(std::path::PathBuf),
+}
+
+#[cfg(afs_impls)]
+impl AtomicWriteTemp {
+
+    pub fn uri(&self) -> FileUri {
+        match self {
+            Self::Path(path) | Self::Staging(path) => FileUri::from_path(path),
+            Self::Sibling(uri) => uri.clone(),
+        }
+    }
+}
+
+/// ExternalStorageProvider のツリー内のドキュメントの場合、その親ディレクトリの URI を返す。
+/// ドキュメント ID がパスに基づくので、ID の最後の要素を取り除けば親になる。
+#[cfg(afs_impls)]
+pub(crate) fn external_storage_provider_parent_uri(uri: &FileUri) -> Option<FileUri> {
+    const PREFIX: &str = "content://com.android.externalstorage.documents/tree/";
+
+    let tree_uri = uri.document_top_tree_uri.as_ref()?;
+    if !uri.uri.starts_with(PREFIX) {
+        return None
+    }
+
+    let (tree, document_id) = uri.uri.split_once("/document/")?;
+    let parent_id = match document_id.rfind("%2F").or_else(|| document_id.rfind("%2f")) {
+        Some(i) => &document_id[..i],
+        // ボリュームの直下の場合、親は "primary:" のようなボリュームのルート
+        None => document_id.find("%3A").or_else(|| document_id.find("%3a")).map(|i| &document_id[..i + 3])?,
+    };
+    if parent_id == document_id {
+        return None
+    }
+
+    Some(FileUri {
+        uri: format!("{tree}/document/{parent_id}"),
+        document_top_tree_uri: Some(tree_uri.clone()),
+    })
+}
+
+/// 置き換えのための一時ファイルの名前を返す。
+/// 同時に行われる書き込みと衝突しないように、プロセス内で一意な接尾辞を付ける。
+#[cfg(afs_impls)]
+pub(crate) fn atomic_write_temp_name(name: &str, extension: &str) -> String {
+    static COUNTER: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+
+    let count = COUNTER.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or(0);
+
+    format!(".{name}.{}-{count}-{nanos:x}.{extension}", std::process::id())
+}
+
+
+#[cfg(all(test, afs_impls))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_external_storage_provider_parent_uri() {
+        let tree = "content://com.android.externalstorage.documents/tree/primary%3ADocuments";
+        let uri = |document_id: &str| FileUri {
+            uri: format!("{tree}/document/{document_id}"),
+            document_top_tree_uri: Some(tree.to_string()),
+        };
+
+        assert_eq!(external_storage_provider_parent_uri(&uri("primary%3ADocuments%2Fsub%2Fa.txt")), Some(uri("primary%3ADocuments%2Fsub")));
+        assert_eq!(external_storage_provider_parent_uri(&uri("primary%3ADocuments")), Some(uri("primary%3A")));
+        assert_eq!(external_storage_provider_parent_uri(&uri("primary%3A")), None);
+        assert_eq!(external_storage_provider_parent_uri(&FileUri::from_uri("content://media/external/file/1")), None);
+    }
+}
@@ -0,0 +1,166 @@
+use serde::{Deserialize, Serialize};
+use crate::*;
+
+
+/// Options for [`AndroidFs::copy_dir`](crate::api::api_sync::AndroidFs::copy_dir).
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CopyDirOptions {
+
+    /// What to do if a file with the same name already exists in the destination.
+    ///
+    /// By default, this is [`CopyConflictPolicy::Fail`].
+    pub on_conflict: CopyConflictPolicy,
+}
+
+impl Default for CopyDirOptions {
+
+    fn default() -> Self {
+        Self {
+            on_conflict: CopyConflictPolicy::Fail,
+        }
+    }
+}
+
+/// Policy of [`CopyDirOptions::on_conflict`].
+///
+/// Existing directories are always merged,
+/// so this applies to files and to directories that conflict with existing files.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Deserialize, Serialize)]
+#[non_exhaustive]
+pub enum CopyConflictPolicy {
+
+    /// Leaves the existing entry as it is and does not copy the source entry.
+    Skip,
+
+    /// Replaces the contents of the existing file with the source file.
+    /// A directory cannot overwrite an existing file, so it results in an error.
+    Overwrite,
+
+    /// Copies the source entry with a new name such as `a (1).txt`.
+    Rename,
+
+    /// Aborts the copy and returns an error.
+    Fail,
+}
+
+/// Result of an entry copied by [`AndroidFs::copy_dir`](crate::api::api_sync::AndroidFs::copy_dir).
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct CopyDirEntry {
+
+    /// URI of the source entry.
+    pub src: FileUri,
+
+    /// Path of the source entry relative to the source directory, e.g. `sub/file.txt`.
+    pub relative_path: std::path::PathBuf,
+
+    /// Whether the source entry is a directory.
+    pub is_dir: bool,
+
+    /// Result of copying the entry.
+    pub result: Result<CopyDirOutcome>,
+}
+
+/// Outcome of [`CopyDirEntry`].
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum CopyDirOutcome {
+
+    /// The entry was newly created in the destination.
+    ///
+    /// `relative_path` is the path relative to the destination directory.
+    /// This may differ from the source one, since the file provider may sanitize names.
+    Copied {
+        dest: FileUri,
+        relative_path: std::path::PathBuf,
+    },
+
+    /// The existing file was overwritten.
+    Overwritten {
+        dest: FileUri,
+        relative_path: std::path::PathBuf,
+    },
+
+    /// The existing directory was used as the destination of the children.
+    Merged {
+        dest: FileUri,
+        relative_path: std::path::PathBuf,
+    },
+
+    /// The entry was created with a new name because of a conflict.
+    Renamed {
+        dest: FileUri,
+        relative_path: std::path::PathBuf,
+    },
+
+    /// The entry was not copied because of a conflict.
+    /// If a directory, its children are not copied either.
+    Skipped,
+}
+
+impl CopyDirOutcome {
+
+    /// URI of the destination entry.
+    /// If skipped, this is None.
+    pub fn dest(&self) -> Option<&FileUri> {
+        match self {
+            Self::Copied { dest, .. }
+            | Self::Overwritten { dest, .. }
+            | Self::Merged { dest, .. }
+            | Self::Renamed { dest, .. } => Some(dest),
+            Self::Skipped => None,
+        }
+    }
+
+    /// Path of the destination entry relative to the destination directory.
+    /// If skipped, this is None.
+    pub fn relative_path(&self) -> Option<&std::path::Path> {
+        match self {
+            Self::Copied { relative_path, .. }
+            | Self::Overwritten { relative_path, .. }
+            | Self::Merged { relative_path, .. }
+            | Self::Renamed { relative_path, .. } => Some(relative_path),
+            Self::Skipped => None,
+        }
+    }
+}
+
+/// Returns a name not contained in `exists`, such as `a (1).txt` for `a.txt`.
+#[cfg(afs_impls)]
+pub(crate) fn unique_name(name: &str, exists: impl Fn(&str) -> bool) -> String {
+    if !exists(name) {
+        return name.to_string()
+    }
+
+    let (stem, ext) = match name.rsplit_once('.') {
+        Some((stem, ext)) if !stem.is_empty() => (stem, Some(ext)),
+        _ => (name, None),
+    };
+
+    (1..)
+        .map(|i| match ext {
+            Some(ext) => format!("{stem} ({i}).{ext}"),
+            None => format!("{stem} ({i})"),
+        })
+        .find(|name| !exists(name))
+        .expect("Should find unused name")
+}
+
+
+#[cfg(all(test, afs_impls))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unique_name() {
+        let names = ["a.txt", "a (1).txt", "b", ".hidden"];
+        let exists = |name: &str| names.contains(&name);
+
+        assert_eq!(unique_name("c.txt", exists), "c.txt");
+        assert_eq!(unique_name("a.txt", exists), "a (2).txt");
+        assert_eq!(unique_name("b", exists), "b (1)");
+        assert_eq!(unique_name(".hidden", exists), ".hidden (1)");
+    }
+}
@@ -0,0 +1,58 @@
+use serde::{Deserialize, Serialize};
+use crate::*;
+
+
+/// Options for [`AndroidFs::find_duplicates_with_options`](crate::api::api_sync::AndroidFs::find_duplicates_with_options).
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct FindDuplicatesOptions {
+
+    /// Files shorter than this length in bytes are ignored.
+    ///
+    /// By default, this is `1`, so empty files are ignored.
+    pub min_len: u64,
+
+    /// Length in bytes from the start of a file that is hashed
+    /// before the entire file is hashed.
+    /// Files that differ in this part are excluded without reading the rest.
+    ///
+    /// By default, this is `4096`.
+    pub partial_hash_len: u64,
+}
+
+impl Default for FindDuplicatesOptions {
+
+    fn default() -> Self {
+        Self {
+            min_len: 1,
+            partial_hash_len: 4096,
+        }
+    }
+}
+
+/// Group of files with the same contents returned by [`AndroidFs::find_duplicates`](crate::api::api_sync::AndroidFs::find_duplicates).
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct DuplicateGroup {
+
+    /// Length of each file in bytes.
+    pub len: u64,
+
+    /// Files with the same contents.
+    /// This has two or more entries, and all of them are [`Entry::File`].
+    pub entries: Vec<Entry>,
+}
+
+/// Phase of [`AndroidFs::find_duplicates_with_options`](crate::api::api_sync::AndroidFs::find_duplicates_with_options)
+/// passed to the progress callback.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Deserialize, Serialize)]
+#[non_exhaustive]
+pub enum FindDuplicatesPhase {
+
+    /// Hashing the start of the files with the same length.
+    PartialHash,
+
+    /// Hashing the entire files with the same partial hash.
+    FullHash,
+}
@@ -279,6 +279,25 @@ impl OptionalEntry {
             Self::Dir { .. } => None,
         }
     }
+
+    /// 指定されたフィールドのみを残す
+    #[cfg(afs_impls)]
+    pub(crate) fn retain_fields(self, options: EntryOptions) -> Self {
+        match self {
+            Self::File { uri, name, last_modified, len, mime_type } => Self::File {
+                uri: uri.filter(|_| options.uri),
+                name: name.filter(|_| options.name),
+                last_modified: last_modified.filter(|_| options.last_modified),
+                len: len.filter(|_| options.len),
+                mime_type: mime_type.filter(|_| options.mime_type),
+            },
+            Self::Dir { uri, name, last_modified } => Self::Dir {
+                uri: uri.filter(|_| options.uri),
+                name: name.filter(|_| options.name),
+                last_modified: last_modified.filter(|_| options.last_modified),
+            },
+        }
+    }
  }
 
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Deserialize, Serialize)]
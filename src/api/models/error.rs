@@ -74,10 +74,14 @@ enum InnerError {
     #[error(transparent)]
     PluginInvoke(tauri::plugin::mobile::PluginInvokeError),
 
-    #[cfg(target_os = "android")]
+    #[cfg(afs_impls)]
     #[error(transparent)]
     Base64Decode(base64::DecodeError),
 
+    #[cfg(afs_impls)]
+    #[error(transparent)]
+    Zip(zip::result::ZipError),
+
     #[error(transparent)]
     Io(std::io::Error),
 
@@ -128,9 +132,15 @@ macro_rules! impl_into_err_from_inner {
 #[cfg(target_os = "android")]
 impl_into_err_from_inner!(tauri::plugin::mobile::PluginInvokeError, e => crate::Error { inner: InnerError::PluginInvoke(e) });
 
-#[cfg(target_os = "android")]
+#[cfg(afs_impls)]
 impl_into_err_from_inner!(base64::DecodeError, e => crate::Error { inner: InnerError::Base64Decode(e) });
 
+#[cfg(afs_impls)]
+impl_into_err_from_inner!(zip::result::ZipError, e => match e {
+    zip::result::ZipError::Io(e) => crate::Error { inner: InnerError::Io(e) },
+    e => crate::Error { inner: InnerError::Zip(e) },
+});
+
 impl_into_err_from_inner!(std::io::Error, e => crate::Error { inner: InnerError::Io(e) });
 impl_into_err_from_inner!(std::fmt::Error, e => crate::Error { inner: InnerError::Fmt(e) });
 impl_into_err_from_inner!(std::num::ParseIntError, e => crate::Error { inner: InnerError::ParseInt(e) });
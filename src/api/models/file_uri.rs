@@ -123,6 +123,68 @@ impl From<FileUri> for tauri_plugin_fs::FilePath {
 }
 
 
+/// エントリを識別するための値。  
+/// document URI は、ツリー URI を経由しているかどうかに関わらず provider と document ID で比較する。
+#[cfg(afs_impls)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub(crate) enum EntryKey {
+    Path(std::path::PathBuf),
+    Document { authority: String, document_id: String },
+    Other(String),
+}
+
+#[cfg(afs_impls)]
+impl EntryKey {
+
+    pub fn new(uri: &FileUri) -> Self {
+        if let Some(path) = uri.to_path() {
+            return Self::Path(path)
+        }
+
+        let document = uri.uri.strip_prefix("content://")
+            .and_then(|rest| rest.split_once('/'))
+            .and_then(|(authority, path)| {
+                // `tree/{id}`, `document/{id}`, `tree/{id}/document/{id}` の形式
+                let encoded = match path.rfind("document/") {
+                    Some(i) if i == 0 || path[..i].ends_with('/') => &path[(i + "document/".len())..],
+                    _ => path.strip_prefix("tree/")?,
+                };
+                let document_id = percent_encoding::percent_decode_str(encoded.split('/').next()?)
+                    .decode_utf8()
+                    .ok()?
+                    .into_owned();
+
+                Some(Self::Document { authority: authority.to_string(), document_id })
+            });
+
+        document.unwrap_or_else(|| Self::Other(uri.uri.clone()))
+    }
+
+    /// `self` が `dir` 自身、もしくはその子孫であると分かる場合に true を返す。
+    /// 
+    /// 子孫であるかは、パスの場合と、document ID がパスに基づく ExternalStorageProvider の場合のみ判定できる。
+    /// それ以外の provider では document ID が一致する場合のみ true となる。
+    pub fn is_same_or_inside(&self, dir: &Self) -> bool {
+        const EXTERNAL_STORAGE_PROVIDER: &str = "com.android.externalstorage.documents";
+
+        match (self, dir) {
+            (Self::Path(path), Self::Path(dir)) => path.starts_with(dir),
+            (
+                Self::Document { authority, document_id },
+                Self::Document { authority: dir_authority, document_id: dir_id }
+            ) if authority == dir_authority => {
+                if document_id == dir_id {
+                    return true
+                }
+                // "primary:" のようなボリュームのルート、もしくは "primary:Documents" のようなディレクトリ
+                authority == EXTERNAL_STORAGE_PROVIDER && document_id.strip_prefix(dir_id.as_str())
+                    .is_some_and(|rest| dir_id.ends_with(':') || rest.starts_with('/'))
+            },
+            (a, b) => a == b,
+        }
+    }
+}
+
 fn android_file_uri_to_path(uri: impl AsRef<str>) -> std::path::PathBuf {
     let uri = uri.as_ref();
     let path_part = uri.strip_prefix("file://").unwrap_or(uri);
@@ -168,6 +230,30 @@ mod tests {
         assert_eq!(android_file_uri_to_path(&uri), path);
     }
 
+    #[cfg(afs_impls)]
+    #[test]
+    fn test_entry_key() {
+        let tree = "content://com.android.externalstorage.documents/tree/primary%3ADocs";
+        let key = |uri: &str| EntryKey::new(&FileUri::from_uri(uri));
+        let docs = key(tree);
+        let sub = key(&format!("{tree}/document/primary%3ADocs%2Fsub"));
+
+        assert_eq!(docs, key("content://com.android.externalstorage.documents/document/primary%3ADocs"));
+        assert!(sub.is_same_or_inside(&docs));
+        assert!(docs.is_same_or_inside(&docs));
+        assert!(!docs.is_same_or_inside(&sub));
+        assert!(!key(&format!("{tree}/document/primary%3ADocs2")).is_same_or_inside(&docs));
+        assert!(docs.is_same_or_inside(&key("content://com.android.externalstorage.documents/tree/primary%3A")));
+
+        // document ID がパスに基づかない provider では一致のみ判定する
+        let other = |id: &str| key(&format!("content://com.example.documents/tree/1/document/{id}"));
+        assert!(other("12").is_same_or_inside(&other("12")));
+        assert!(!other("12").is_same_or_inside(&other("1")));
+
+        assert!(key("file:///data/a/b").is_same_or_inside(&key("file:///data/a")));
+        assert!(!key("file:///data/ab").is_same_or_inside(&key("file:///data/a")));
+    }
+
     #[test]
     fn test_unicode_characters() {
         let path = Path::new("/sdcard/ダウンロード");
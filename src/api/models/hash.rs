@@ -0,0 +1,148 @@
+use serde::{Deserialize, Serialize};
+
+
+/// Hash algorithm for [`AndroidFs::hash`](crate::api::api_sync::AndroidFs::hash).
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Deserialize, Serialize)]
+#[non_exhaustive]
+pub enum HashAlgorithm {
+
+    /// SHA-256.  
+    /// The digest is 32 bytes.
+    #[serde(rename = "SHA-256")]
+    Sha256,
+
+    /// SHA-1.  
+    /// The digest is 20 bytes.  
+    /// This is not collision resistant, so use it only for compatibility.
+    #[serde(rename = "SHA-1")]
+    Sha1,
+
+    /// MD5.  
+    /// The digest is 16 bytes.  
+    /// This is not collision resistant, so use it only for compatibility.
+    #[serde(rename = "MD5")]
+    Md5,
+
+    /// CRC-32 (IEEE 802.3), the same as ZIP and gzip.  
+    /// The digest is 4 bytes in big endian.
+    #[serde(rename = "CRC32")]
+    Crc32,
+
+    /// XXH64 with seed 0.  
+    /// The digest is 8 bytes in big endian.
+    #[serde(rename = "XXH64")]
+    Xxh64,
+}
+
+/// [`HashAlgorithm`] のハッシュを逐次計算する。
+#[cfg(afs_impls)]
+pub(crate) enum Hasher {
+    Sha256(sha2::Sha256),
+    Sha1(sha1::Sha1),
+    Md5(md5::Md5),
+    Crc32(crc32fast::Hasher),
+    Xxh64(xxhash_rust::xxh64::Xxh64),
+}
+
+#[cfg(afs_impls)]
+impl Hasher {
+
+    pub fn new(algorithm: HashAlgorithm) -> Self {
+        use sha2::Digest as _;
+
+        match algorithm {
+            HashAlgorithm::Sha256 => Self::Sha256(sha2::Sha256::new()),
+            HashAlgorithm::Sha1 => Self::Sha1(sha1::Sha1::new()),
+            HashAlgorithm::Md5 => Self::Md5(md5::Md5::new()),
+            HashAlgorithm::Crc32 => Self::Crc32(crc32fast::Hasher::new()),
+            HashAlgorithm::Xxh64 => Self::Xxh64(xxhash_rust::xxh64::Xxh64::new(0)),
+        }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        use sha2::Digest as _;
+
+        match self {
+            Self::Sha256(h) => h.update(data),
+            Self::Sha1(h) => h.update(data),
+            Self::Md5(h) => h.update(data),
+            Self::Crc32(h) => h.update(data),
+            Self::Xxh64(h) => h.update(data),
+        }
+    }
+
+    /// ダイジェストを返す。CRC32 と xxHash は big endian のバイト列にする。
+    pub fn finalize(self) -> Vec<u8> {
+        use sha2::Digest as _;
+
+        match self {
+            Self::Sha256(h) => h.finalize().to_vec(),
+            Self::Sha1(h) => h.finalize().to_vec(),
+            Self::Md5(h) => h.finalize().to_vec(),
+            Self::Crc32(h) => h.finalize().to_be_bytes().to_vec(),
+            Self::Xxh64(h) => h.digest().to_be_bytes().to_vec(),
+        }
+    }
+}
+
+/// [`std::io::copy`] でファイルを渡せるようにする。
+#[cfg(afs_impls)]
+impl std::io::Write for Hasher {
+
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+
+#[cfg(all(test, afs_impls))]
+mod tests {
+    use super::*;
+
+    fn hex(algorithm: HashAlgorithm, chunks: &[&[u8]]) -> String {
+        let mut hasher = Hasher::new(algorithm);
+        for chunk in chunks {
+            hasher.update(chunk);
+        }
+        hasher.finalize().iter().map(|b| format!("{b:02x}")).collect()
+    }
+
+    #[test]
+    fn test_hasher() {
+        let long = (0..1000u32).map(|i| (i % 251) as u8).collect::<Vec<_>>();
+        let cases: &[(HashAlgorithm, &[u8], &str)] = &[
+            (HashAlgorithm::Sha256, b"", "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"),
+            (HashAlgorithm::Sha256, b"abc", "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"),
+            (HashAlgorithm::Sha256, b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq", "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1"),
+            (HashAlgorithm::Sha1, b"", "da39a3ee5e6b4b0d3255bfef95601890afd80709"),
+            (HashAlgorithm::Sha1, b"abc", "a9993e364706816aba3e25717850c26c9cd0d89d"),
+            (HashAlgorithm::Sha1, b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq", "84983e441c3bd26ebaae4aa1f95129e5e54670f1"),
+            (HashAlgorithm::Md5, b"", "d41d8cd98f00b204e9800998ecf8427e"),
+            (HashAlgorithm::Md5, b"abc", "900150983cd24fb0d6963f7d28e17f72"),
+            (HashAlgorithm::Md5, b"12345678901234567890123456789012345678901234567890123456789012345678901234567890", "57edf4a22be3c955ac49da2e2107b67a"),
+            (HashAlgorithm::Crc32, b"", "00000000"),
+            (HashAlgorithm::Crc32, b"123456789", "cbf43926"),
+            (HashAlgorithm::Xxh64, b"", "ef46db3751d8e999"),
+            (HashAlgorithm::Xxh64, b"a", "d24ec4f1a98c6e5b"),
+            (HashAlgorithm::Xxh64, b"abc", "44bc2cf5ad770999"),
+            (HashAlgorithm::Xxh64, b"Nobody inspects the spammish repetition", "fbcea83c8a378bf1"),
+        ];
+
+        for (algorithm, input, expected) in cases {
+            assert_eq!(&hex(*algorithm, &[input]), expected, "{algorithm:?} {:?}", String::from_utf8_lossy(input));
+        }
+
+        // 分割して渡しても結果は変わらない
+        for algorithm in [HashAlgorithm::Sha256, HashAlgorithm::Sha1, HashAlgorithm::Md5, HashAlgorithm::Crc32, HashAlgorithm::Xxh64] {
+            let whole = hex(algorithm, &[&long]);
+            let (a, b) = long.split_at(37);
+            let (b, c) = b.split_at(500);
+            assert_eq!(hex(algorithm, &[a, b, c]), whole, "{algorithm:?}");
+        }
+    }
+}
@@ -0,0 +1,50 @@
+/// Byte offsets of every `interval` lines of a text file,
+/// built by [`AndroidFs::build_line_index`](crate::api::api_sync::AndroidFs::build_line_index).
+///
+/// This allows jumping to a line of a huge text file without reading it from the beginning.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct LineIndex {
+    pub(crate) interval: u64,
+    pub(crate) encoding: String,
+    pub(crate) file_len: u64,
+    pub(crate) file_modified_ms: Option<u64>,
+    pub(crate) line_count: u64,
+
+    /// `offsets[i]` は `i * interval` 行目の先頭のバイト位置
+    pub(crate) offsets: Vec<u64>,
+}
+
+impl LineIndex {
+
+    /// Number of lines between the recorded offsets.
+    pub fn interval(&self) -> u64 {
+        self.interval
+    }
+
+    /// Name of the encoding used to find line breaks, such as `"UTF-8"` or `"UTF-16LE"`.
+    pub fn encoding(&self) -> &str {
+        &self.encoding
+    }
+
+    /// Byte length of the file when this index was built.
+    pub fn file_len(&self) -> u64 {
+        self.file_len
+    }
+
+    /// Number of lines in the file.
+    /// A line break at the end of the file does not start a new empty line.
+    pub fn line_count(&self) -> u64 {
+        self.line_count
+    }
+
+    /// Returns the byte offset of the nearest recorded line at or before the `line` (0-based),
+    /// and the number of lines to skip from there to reach the `line`.
+    pub fn position(&self, line: u64) -> (u64, u64) {
+        let i = usize::min(
+            usize::try_from(line / self.interval).unwrap_or(usize::MAX),
+            self.offsets.len().saturating_sub(1)
+        );
+        let offset = self.offsets.get(i).copied().unwrap_or(0);
+        (offset, line - i as u64 * self.interval)
+    }
+}
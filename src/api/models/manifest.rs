@@ -0,0 +1,244 @@
+use serde::{Deserialize, Serialize};
+use crate::*;
+
+
+/// Options for [`AndroidFs::snapshot_tree_with_options`](crate::api::api_sync::AndroidFs::snapshot_tree_with_options).
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SnapshotTreeOptions {
+
+    /// If true, the contents of each file are hashed and stored in the `hash` of [`ManifestEntry::File`].
+    /// This is accurate but reads all files.
+    ///
+    /// By default, this is false.
+    pub hash: bool,
+
+    /// Algorithm used when `hash` is true.
+    /// Manifests can be compared only if they were taken with the same algorithm.
+    ///
+    /// By default, this is [`HashAlgorithm::Sha256`].
+    pub hash_algorithm: HashAlgorithm,
+}
+
+impl Default for SnapshotTreeOptions {
+
+    fn default() -> Self {
+        Self {
+            hash: false,
+            hash_algorithm: HashAlgorithm::Sha256,
+        }
+    }
+}
+
+/// Manifest of a directory tree returned by [`AndroidFs::snapshot_tree`](crate::api::api_sync::AndroidFs::snapshot_tree).
+///
+/// This does not contain URIs, so it can be saved and compared with a manifest
+/// of the same tree taken later or on another file provider.
+/// The root directory itself is not contained.
+#[derive(Debug, Clone, Default, Hash, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TreeManifest {
+
+    /// Entries keyed on the path relative to the root directory, such as `sub/file.txt`.
+    pub entries: std::collections::BTreeMap<std::path::PathBuf, ManifestEntry>,
+}
+
+impl TreeManifest {
+
+    /// Same as `serde_json::to_string(...)`
+    pub fn to_json_string(&self) -> Result<String> {
+        serde_json::to_string(self).map_err(Into::into)
+    }
+
+    /// Same as `serde_json::from_str(...)`
+    pub fn from_json_str(json: impl AsRef<str>) -> Result<Self> {
+        serde_json::from_str(json.as_ref()).map_err(Into::into)
+    }
+}
+
+/// Entry of [`TreeManifest`].
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ManifestEntry {
+
+    #[non_exhaustive]
+    #[serde(rename_all = "camelCase")]
+    File {
+        last_modified: std::time::SystemTime,
+        len: u64,
+        mime_type: String,
+
+        /// Hash of the contents as a hexadecimal string.
+        /// This is present only if [`SnapshotTreeOptions::hash`] was true,
+        /// and is computed with [`SnapshotTreeOptions::hash_algorithm`].
+        hash: Option<String>,
+    },
+
+    #[non_exhaustive]
+    #[serde(rename_all = "camelCase")]
+    Dir {
+        last_modified: std::time::SystemTime,
+    },
+}
+
+impl ManifestEntry {
+
+    pub fn is_file(&self) -> bool {
+        matches!(self, Self::File { .. })
+    }
+
+    pub fn is_dir(&self) -> bool {
+        matches!(self, Self::Dir { .. })
+    }
+}
+
+/// Change between two manifests returned by [`AndroidFs::diff_manifests`](crate::api::api_sync::AndroidFs::diff_manifests).
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum ManifestChange {
+
+    /// An entry exists only in the new manifest.
+    Added {
+        relative_path: std::path::PathBuf,
+        entry: ManifestEntry,
+    },
+
+    /// An entry exists only in the old manifest.
+    Removed {
+        relative_path: std::path::PathBuf,
+        entry: ManifestEntry,
+    },
+
+    /// An entry exists in both manifests but differs.
+    Modified {
+        relative_path: std::path::PathBuf,
+        old: ManifestEntry,
+        new: ManifestEntry,
+    },
+}
+
+impl ManifestChange {
+
+    pub fn relative_path(&self) -> &std::path::Path {
+        match self {
+            Self::Added { relative_path, .. }
+            | Self::Removed { relative_path, .. }
+            | Self::Modified { relative_path, .. } => relative_path,
+        }
+    }
+}
+
+/// Returns the changes from the manifest `old` to `new` in the order of the relative path.
+pub(crate) fn diff_manifests(old: &TreeManifest, new: &TreeManifest) -> Vec<ManifestChange> {
+    let mut changes = Vec::new();
+
+    for (path, old_entry) in &old.entries {
+        match new.entries.get(path) {
+            None => changes.push(ManifestChange::Removed {
+                relative_path: path.clone(),
+                entry: old_entry.clone(),
+            }),
+            Some(new_entry) if is_modified(old_entry, new_entry) => changes.push(ManifestChange::Modified {
+                relative_path: path.clone(),
+                old: old_entry.clone(),
+                new: new_entry.clone(),
+            }),
+            Some(_) => {},
+        }
+    }
+    for (path, new_entry) in &new.entries {
+        if !old.entries.contains_key(path) {
+            changes.push(ManifestChange::Added {
+                relative_path: path.clone(),
+                entry: new_entry.clone(),
+            });
+        }
+    }
+
+    changes.sort_by(|a, b| a.relative_path().cmp(b.relative_path()));
+    changes
+}
+
+fn is_modified(old: &ManifestEntry, new: &ManifestEntry) -> bool {
+    match (old, new) {
+        // ディレクトリの最終更新日時は中身の変更でも変わるので比較しない
+        (ManifestEntry::Dir { .. }, ManifestEntry::Dir { .. }) => false,
+        (
+            ManifestEntry::File { len: old_len, last_modified: old_last_modified, hash: old_hash, .. },
+            ManifestEntry::File { len: new_len, last_modified: new_last_modified, hash: new_hash, .. },
+        ) => {
+            if old_len != new_len {
+                return true
+            }
+            // コピーすると最終更新日時は変わるため、ハッシュがあればそれだけで比較する
+            match (old_hash, new_hash) {
+                (Some(old_hash), Some(new_hash)) => old_hash != new_hash,
+                _ => old_last_modified != new_last_modified,
+            }
+        },
+        _ => true,
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(secs: u64, len: u64, hash: Option<&str>) -> ManifestEntry {
+        ManifestEntry::File {
+            last_modified: std::time::UNIX_EPOCH + std::time::Duration::from_secs(secs),
+            len,
+            mime_type: "text/plain".to_string(),
+            hash: hash.map(ToString::to_string),
+        }
+    }
+
+    fn dir(secs: u64) -> ManifestEntry {
+        ManifestEntry::Dir { last_modified: std::time::UNIX_EPOCH + std::time::Duration::from_secs(secs) }
+    }
+
+    fn manifest(entries: &[(&str, ManifestEntry)]) -> TreeManifest {
+        TreeManifest {
+            entries: entries.iter().map(|(path, e)| (path.into(), e.clone())).collect(),
+        }
+    }
+
+    #[test]
+    fn test_diff_manifests() {
+        let old = manifest(&[
+            ("a.txt", file(1, 1, None)),
+            ("b.txt", file(1, 1, None)),
+            ("c.txt", file(1, 1, Some("00"))),
+            ("d.txt", file(1, 1, Some("00"))),
+            ("sub", dir(1)),
+            ("sub/e.txt", file(1, 1, None)),
+            ("x", file(1, 1, None)),
+        ]);
+        let new = manifest(&[
+            ("a.txt", file(1, 1, None)),
+            ("b.txt", file(2, 1, None)),
+            ("c.txt", file(2, 1, Some("00"))),
+            ("d.txt", file(1, 1, Some("01"))),
+            ("sub", dir(2)),
+            ("sub/f.txt", file(1, 1, None)),
+            ("x", dir(1)),
+        ]);
+
+        let changes = diff_manifests(&old, &new);
+        let summary = changes.iter()
+            .map(|c| match c {
+                ManifestChange::Added { relative_path, .. } => format!("Added {}", relative_path.display()),
+                ManifestChange::Removed { relative_path, .. } => format!("Removed {}", relative_path.display()),
+                ManifestChange::Modified { relative_path, .. } => format!("Modified {}", relative_path.display()),
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(summary, ["Modified b.txt", "Modified d.txt", "Removed sub/e.txt", "Added sub/f.txt", "Modified x"]);
+        assert!(diff_manifests(&new, &new).is_empty());
+
+        let json = new.to_json_string().unwrap();
+        assert_eq!(TreeManifest::from_json_str(json).unwrap(), new);
+    }
+}
@@ -1,21 +1,46 @@
+mod atomic_write;
+mod copy;
 mod dir;
+mod duplicates;
 mod entry;
 mod error;
 mod file_uri;
 mod file_picker;
 mod file_access;
+mod hash;
 mod image;
+mod line_index;
+mod manifest;
 mod notification;
+mod read_dir;
+mod stats;
 mod storage_volume;
+mod sync;
+mod walk;
+mod watch;
+mod zip;
 
+#[cfg(afs_impls)]
+pub(crate) use atomic_write::*;
+pub use copy::*;
 pub use dir::*;
+pub use duplicates::*;
 pub use error::*;
 pub use entry::*;
 pub use file_uri::*;
 pub use file_picker::*;
 pub use file_access::*;
+pub use hash::*;
 pub use image::*;
+pub use line_index::*;
+pub use manifest::*;
 pub use notification::*;
+pub use read_dir::*;
+pub use stats::*;
 pub use storage_volume::*;
+pub use sync::*;
+pub use walk::*;
+pub use watch::*;
+pub use zip::*;
 
 pub type Result<T> = std::result::Result<T, crate::Error>;
\ No newline at end of file
@@ -0,0 +1,253 @@
+use serde::{Deserialize, Serialize};
+use crate::*;
+
+
+/// Options for [`AndroidFs::read_dir_with_options`](crate::api::api_sync::AndroidFs::read_dir_with_options)
+/// and [`AndroidFs::read_dir_with_options_and_range`](crate::api::api_sync::AndroidFs::read_dir_with_options_and_range).
+///
+/// If sort keys or filters are specified, all entries of the directory are read,
+/// and the range is applied after sorting and filtering.
+///
+/// [`EntryOptions`] can be converted into this without sort keys and filters.
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ReadDirOptions {
+
+    /// Fields to be retrieved for each entry.
+    /// Fields required for sorting and filtering are retrieved internally regardless of this.
+    ///
+    /// By default, this is [`EntryOptions::ALL`].
+    pub entry_options: EntryOptions,
+
+    /// Sort keys in order of priority.
+    /// Entries that are equal in all keys keep the order of the file provider.
+    ///
+    /// By default, this is empty.
+    pub sort_by: Vec<ReadDirSort>,
+
+    /// If `Some`, only entries of this type are returned.
+    ///
+    /// By default, this is `None`.
+    pub entry_type: Option<WalkEntryType>,
+
+    /// If not empty, only files whose MIME type matches any of these are returned.
+    /// Wildcards such as `image/*` and `*/*` are supported.
+    /// Since directories have no MIME type, they are not returned if this is not empty.
+    ///
+    /// By default, this is empty.
+    pub mime_types: Vec<String>,
+
+    /// If `Some`, only entries whose name contains this are returned.
+    /// This is case-insensitive.
+    ///
+    /// By default, this is `None`.
+    pub name_contains: Option<String>,
+
+    /// If `Some`, only entries whose name matches this glob pattern, such as `*.txt`, are returned.
+    ///
+    /// By default, this is `None`.
+    pub name_pattern: Option<String>,
+}
+
+impl Default for ReadDirOptions {
+
+    fn default() -> Self {
+        Self {
+            entry_options: EntryOptions::ALL,
+            sort_by: Vec::new(),
+            entry_type: None,
+            mime_types: Vec::new(),
+            name_contains: None,
+            name_pattern: None,
+        }
+    }
+}
+
+impl From<EntryOptions> for ReadDirOptions {
+
+    fn from(entry_options: EntryOptions) -> Self {
+        Self { entry_options, ..Default::default() }
+    }
+}
+
+/// Sort key of [`ReadDirOptions::sort_by`].
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadDirSort {
+
+    pub key: ReadDirSortKey,
+
+    /// If true, the order is reversed.
+    #[serde(default)]
+    pub descending: bool,
+}
+
+impl ReadDirSort {
+
+    pub fn ascending(key: ReadDirSortKey) -> Self {
+        Self { key, descending: false }
+    }
+
+    pub fn descending(key: ReadDirSortKey) -> Self {
+        Self { key, descending: true }
+    }
+}
+
+/// Key of [`ReadDirSort`].
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Deserialize, Serialize)]
+#[non_exhaustive]
+pub enum ReadDirSortKey {
+
+    /// Name in natural order, such as `a2.txt` before `a10.txt`.
+    /// This is case-insensitive.
+    Name,
+
+    /// Last modified time.
+    LastModified,
+
+    /// Length of a file in bytes.
+    /// Directories are treated as smaller than any file.
+    Len,
+
+    /// Directories before files.
+    DirsFirst,
+}
+
+#[cfg(afs_impls)]
+impl ReadDirOptions {
+
+    pub(crate) fn has_sort_or_filter(&self) -> bool {
+        !self.sort_by.is_empty()
+            || self.entry_type.is_some()
+            || !self.mime_types.is_empty()
+            || self.name_contains.is_some()
+            || self.name_pattern.is_some()
+    }
+
+    /// [`ReadDirOptions::entry_options`] に加えて、ソートとフィルタに必要なフィールドを含めたオプション
+    pub(crate) fn read_entry_options(&self) -> EntryOptions {
+        let ops = self.entry_options;
+        let by = |key| self.sort_by.iter().any(|s| s.key == key);
+
+        EntryOptions {
+            uri: ops.uri,
+            name: ops.name || by(ReadDirSortKey::Name) || self.name_contains.is_some() || self.name_pattern.is_some(),
+            last_modified: ops.last_modified || by(ReadDirSortKey::LastModified),
+            len: ops.len || by(ReadDirSortKey::Len),
+            mime_type: ops.mime_type || !self.mime_types.is_empty(),
+        }
+    }
+
+    pub(crate) fn matches(&self, entry: &OptionalEntry, name_pattern: Option<&glob::Pattern>) -> bool {
+        let type_matches = match self.entry_type {
+            Some(WalkEntryType::File) => entry.is_file(),
+            Some(WalkEntryType::Dir) => entry.is_dir(),
+            None => true,
+        };
+        let name_contains = match &self.name_contains {
+            Some(s) => entry.name().is_some_and(|name| name.to_lowercase().contains(&s.to_lowercase())),
+            None => true,
+        };
+        let name_matches = match name_pattern {
+            Some(pattern) => entry.name().is_some_and(|name| pattern.matches(name)),
+            None => true,
+        };
+
+        type_matches
+            && name_contains
+            && name_matches
+            && matches_mime_types(&self.mime_types, entry.file_mime_type())
+    }
+
+    pub(crate) fn compare(&self, a: &OptionalEntry, b: &OptionalEntry) -> std::cmp::Ordering {
+        use std::cmp::Ordering;
+
+        for sort in &self.sort_by {
+            let ord = match sort.key {
+                ReadDirSortKey::Name => match (a.name(), b.name()) {
+                    (Some(a), Some(b)) => natural_cmp(a, b),
+                    (a, b) => a.cmp(&b),
+                },
+                ReadDirSortKey::LastModified => a.last_modified().cmp(&b.last_modified()),
+                ReadDirSortKey::Len => a.file_len().cmp(&b.file_len()),
+                ReadDirSortKey::DirsFirst => b.is_dir().cmp(&a.is_dir()),
+            };
+            let ord = if sort.descending { ord.reverse() } else { ord };
+
+            if ord != Ordering::Equal {
+                return ord
+            }
+        }
+
+        Ordering::Equal
+    }
+}
+
+/// Compares names in natural order.
+/// Runs of digits are compared as numbers, and other characters are compared case-insensitively.
+/// If they are equal, the names are compared as they are.
+#[cfg(afs_impls)]
+pub(crate) fn natural_cmp(a: &str, b: &str) -> std::cmp::Ordering {
+    use std::cmp::Ordering;
+
+    fn take_digits<'a>(s: &'a str, chars: &mut std::iter::Peekable<std::str::CharIndices<'a>>) -> &'a str {
+        let start = chars.peek().map(|(i, _)| *i).unwrap_or(s.len());
+        let mut end = start;
+        while let Some((i, c)) = chars.peek().copied() {
+            if !c.is_ascii_digit() {
+                break
+            }
+            end = i + c.len_utf8();
+            chars.next();
+        }
+        &s[start..end]
+    }
+
+    let mut a_chars = a.char_indices().peekable();
+    let mut b_chars = b.char_indices().peekable();
+
+    loop {
+        let (x, y) = match (a_chars.peek(), b_chars.peek()) {
+            (None, None) => break,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some((_, x)), Some((_, y))) => (*x, *y),
+        };
+
+        let ord = match x.is_ascii_digit() && y.is_ascii_digit() {
+            true => {
+                let x = take_digits(a, &mut a_chars);
+                let y = take_digits(b, &mut b_chars);
+                let x_trimmed = x.trim_start_matches('0');
+                let y_trimmed = y.trim_start_matches('0');
+
+                x_trimmed.len().cmp(&y_trimmed.len())
+                    .then_with(|| x_trimmed.cmp(y_trimmed))
+            },
+            false => {
+                a_chars.next();
+                b_chars.next();
+                x.to_lowercase().cmp(y.to_lowercase())
+            },
+        };
+
+        if ord != Ordering::Equal {
+            return ord
+        }
+    }
+
+    a.cmp(b)
+}
+
+
+#[cfg(all(test, afs_impls))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_natural_cmp() {
+        let mut names = vec!["a10.txt", "A2.txt", "a1.txt", "b.txt", "a02.txt", "a.txt", "a1b"];
+        names.sort_by(|a, b| natural_cmp(a, b));
+        assert_eq!(names, ["a.txt", "a1.txt", "a1b", "A2.txt", "a02.txt", "a10.txt", "b.txt"]);
+    }
+}
@@ -0,0 +1,81 @@
+use serde::{Deserialize, Serialize};
+
+
+/// Options for [`AndroidFs::dir_stats_with_options`](crate::api::api_sync::AndroidFs::dir_stats_with_options).
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DirStatsOptions {
+
+    /// Maximum depth to descend.
+    /// Children of the root directory have depth `1`.
+    /// If `None`, there is no limit.
+    ///
+    /// By default, this is `None`.
+    pub max_depth: Option<u32>,
+
+    /// Number of entries to be read from a directory at once.
+    ///
+    /// By default, this is `256`.
+    pub page_size: u64,
+}
+
+impl Default for DirStatsOptions {
+
+    fn default() -> Self {
+        Self {
+            max_depth: None,
+            page_size: 256,
+        }
+    }
+}
+
+/// Usage statistics of a directory returned by [`AndroidFs::dir_stats`](crate::api::api_sync::AndroidFs::dir_stats).
+///
+/// The root directory itself is not counted.
+#[derive(Debug, Clone, Default, Hash, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct DirStats {
+
+    /// Total length of all files in bytes.
+    pub total_len: u64,
+
+    /// Number of files.
+    pub file_count: u64,
+
+    /// Number of directories.
+    pub dir_count: u64,
+
+    /// Statistics of files for each MIME type, e.g. `image/png`.
+    pub mime_types: std::collections::BTreeMap<String, MimeTypeStats>,
+}
+
+/// Statistics of files with a MIME type in [`DirStats`].
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct MimeTypeStats {
+
+    /// Total length of the files in bytes.
+    pub total_len: u64,
+
+    /// Number of the files.
+    pub file_count: u64,
+}
+
+#[cfg(afs_impls)]
+impl DirStats {
+
+    pub(crate) fn add_file(&mut self, len: u64, mime_type: &str) {
+        self.total_len = self.total_len.saturating_add(len);
+        self.file_count += 1;
+
+        let stats = self.mime_types.entry(mime_type.to_string()).or_default();
+        stats.total_len = stats.total_len.saturating_add(len);
+        stats.file_count += 1;
+    }
+
+    pub(crate) fn add_dir(&mut self) {
+        self.dir_count += 1;
+    }
+}
@@ -0,0 +1,80 @@
+use serde::{Deserialize, Serialize};
+use crate::*;
+
+
+/// Options for [`AndroidFs::sync_dir`](crate::api::api_sync::AndroidFs::sync_dir).
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SyncOptions {
+
+    /// If true, files with the same length are compared by their contents
+    /// instead of the last modified time.
+    /// This is accurate but reads both files.
+    ///
+    /// By default, this is false.
+    pub compare_contents: bool,
+
+    /// If true, entries in the destination that do not exist in the source are removed.
+    /// This also allows replacing a file with a directory of the same name and vice versa.
+    ///
+    /// By default, this is false.
+    pub delete_extras: bool,
+
+    /// If true, nothing is changed and only the planned actions are returned.
+    ///
+    /// By default, this is false.
+    pub dry_run: bool,
+}
+
+/// Action of [`AndroidFs::sync_dir`](crate::api::api_sync::AndroidFs::sync_dir).
+///
+/// `relative_path` is the path relative to both the source and destination directories.
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum SyncAction {
+
+    /// Creates a directory that exists only in the source.
+    CreateDir {
+        src: FileUri,
+        relative_path: std::path::PathBuf,
+    },
+
+    /// Copies a file that exists only in the source.
+    CreateFile {
+        src: FileUri,
+        relative_path: std::path::PathBuf,
+    },
+
+    /// Overwrites a file in the destination that differs from the source.
+    UpdateFile {
+        src: FileUri,
+        dest: FileUri,
+        relative_path: std::path::PathBuf,
+    },
+
+    /// Removes a file in the destination.
+    RemoveFile {
+        dest: FileUri,
+        relative_path: std::path::PathBuf,
+    },
+
+    /// Removes a directory and its contents in the destination.
+    RemoveDir {
+        dest: FileUri,
+        relative_path: std::path::PathBuf,
+    },
+}
+
+impl SyncAction {
+
+    pub fn relative_path(&self) -> &std::path::Path {
+        match self {
+            Self::CreateDir { relative_path, .. }
+            | Self::CreateFile { relative_path, .. }
+            | Self::UpdateFile { relative_path, .. }
+            | Self::RemoveFile { relative_path, .. }
+            | Self::RemoveDir { relative_path, .. } => relative_path,
+        }
+    }
+}
@@ -0,0 +1,158 @@
+use serde::{Deserialize, Serialize};
+use crate::*;
+
+
+/// Options for [`AndroidFs::walk_dir`](crate::api::api_sync::AndroidFs::walk_dir).
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct WalkOptions {
+
+    /// Maximum depth to descend.
+    /// Children of the root directory have depth `1`.
+    /// If `None`, there is no limit.
+    ///
+    /// By default, this is `None`.
+    pub max_depth: Option<u32>,
+
+    /// Fields to be retrieved for each entry.
+    ///
+    /// By default, this is [`EntryOptions::ALL`].
+    pub entry_options: EntryOptions,
+
+    /// Order in which directories are yielded relative to their contents.
+    ///
+    /// By default, this is [`WalkOrder::PreOrder`].
+    pub order: WalkOrder,
+
+    /// If `Some`, only entries of this type are yielded.
+    /// Directories are still descended even if they are not yielded.
+    ///
+    /// By default, this is `None`.
+    pub entry_type: Option<WalkEntryType>,
+
+    /// If not empty, only files whose MIME type matches any of these are yielded.
+    /// Wildcards such as `image/*` and `*/*` are supported.
+    /// Since directories have no MIME type, they are not yielded if this is not empty.
+    ///
+    /// By default, this is empty.
+    pub mime_types: Vec<String>,
+
+    /// If `Some`, only entries whose name matches this glob pattern, such as `*.txt`, are yielded.
+    /// Directories are still descended even if they are not yielded.
+    ///
+    /// By default, this is `None`.
+    pub name_pattern: Option<String>,
+
+    /// Number of entries to be read from a directory at once.
+    ///
+    /// By default, this is `256`.
+    pub page_size: u64,
+}
+
+impl Default for WalkOptions {
+
+    fn default() -> Self {
+        Self {
+            max_depth: None,
+            entry_options: EntryOptions::ALL,
+            order: WalkOrder::PreOrder,
+            entry_type: None,
+            mime_types: Vec::new(),
+            name_pattern: None,
+            page_size: 256,
+        }
+    }
+}
+
+/// Order of [`AndroidFs::walk_dir`](crate::api::api_sync::AndroidFs::walk_dir).
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Deserialize, Serialize)]
+#[non_exhaustive]
+pub enum WalkOrder {
+
+    /// A directory is yielded before its contents.
+    PreOrder,
+
+    /// A directory is yielded after its contents.
+    /// This is useful for removing directories.
+    PostOrder,
+}
+
+/// Type filter of [`WalkOptions`] and [`ReadDirOptions`].
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Deserialize, Serialize)]
+#[non_exhaustive]
+pub enum WalkEntryType {
+    File,
+    Dir,
+}
+
+/// Entry yielded by [`AndroidFs::walk_dir`](crate::api::api_sync::AndroidFs::walk_dir).
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct WalkEntry {
+
+    /// Entry with the fields specified by [`WalkOptions::entry_options`].
+    pub entry: OptionalEntry,
+
+    /// Depth from the root directory.
+    /// Children of the root directory have depth `1`.
+    pub depth: u32,
+
+    /// Path relative to the root directory, e.g. `sub/file.txt`.
+    pub relative_path: std::path::PathBuf,
+}
+
+#[cfg_attr(not(afs_impls), allow(unused))]
+impl WalkOptions {
+
+    pub(crate) fn matches_mime_type(&self, mime_type: Option<&str>) -> bool {
+        matches_mime_types(&self.mime_types, mime_type)
+    }
+}
+
+/// Returns true if `patterns` is empty or `mime_type` matches any of them.
+/// Wildcards such as `image/*` and `*/*` are supported.
+#[cfg_attr(not(afs_impls), allow(unused))]
+pub(crate) fn matches_mime_types(patterns: &[String], mime_type: Option<&str>) -> bool {
+    if patterns.is_empty() {
+        return true
+    }
+
+    let Some(mime_type) = mime_type else {
+        return false
+    };
+
+    patterns.iter().any(|pattern| {
+        let pattern = pattern.trim();
+        if pattern == "*/*" || pattern == "*" {
+            return true
+        }
+        match pattern.strip_suffix("/*") {
+            Some(top) => mime_type
+                .split_once('/')
+                .is_some_and(|(t, _)| t.eq_ignore_ascii_case(top)),
+            None => pattern.eq_ignore_ascii_case(mime_type),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_mime_type() {
+        let options = |mime_types: &[&str]| WalkOptions {
+            mime_types: mime_types.iter().map(|s| s.to_string()).collect(),
+            ..Default::default()
+        };
+
+        assert!(options(&[]).matches_mime_type(None));
+        assert!(options(&[]).matches_mime_type(Some("text/plain")));
+        assert!(!options(&["*/*"]).matches_mime_type(None));
+        assert!(options(&["*/*"]).matches_mime_type(Some("text/plain")));
+        assert!(options(&["image/*"]).matches_mime_type(Some("image/png")));
+        assert!(!options(&["image/*"]).matches_mime_type(Some("video/mp4")));
+        assert!(options(&["video/mp4", "TEXT/PLAIN"]).matches_mime_type(Some("text/plain")));
+    }
+}
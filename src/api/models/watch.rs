@@ -0,0 +1,170 @@
+use serde::{Deserialize, Serialize};
+use crate::*;
+
+
+/// Event of [`AndroidFs::watch_dir`](crate::api::api_sync::AndroidFs::watch_dir).
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum DirWatchEvent {
+
+    /// An entry was added to the directory.
+    Created {
+        entry: Entry,
+    },
+
+    /// An entry was removed from the directory.
+    Removed {
+        entry: Entry,
+    },
+
+    /// The length or last modified time of an entry changed.
+    Modified {
+        old: Entry,
+        new: Entry,
+    },
+
+    /// An entry was renamed.
+    ///
+    /// If the file provider changes the URI on renaming,
+    /// an entry that was removed and an entry that was added with the same type,
+    /// length and last modified time are treated as renamed.
+    Renamed {
+        old: Entry,
+        new: Entry,
+    },
+}
+
+/// Guard of [`AndroidFs::watch_dir`](crate::api::api_sync::AndroidFs::watch_dir).
+///
+/// The watching stops when this is dropped.
+#[derive(Debug)]
+pub struct DirWatchGuard {
+    #[allow(unused)]
+    stop: std::sync::mpsc::Sender<()>,
+}
+
+impl DirWatchGuard {
+
+    #[cfg(afs_impls)]
+    pub(crate) fn new(stop: std::sync::mpsc::Sender<()>) -> Self {
+        Self { stop }
+    }
+
+    /// Stops the watching.
+    /// This is the same as dropping this.
+    pub fn stop(self) {}
+}
+
+/// Returns the events from the snapshot `old` to `new` of a directory.
+///
+/// Entries are keyed on the URI.
+/// The events are in the order of Removed, Renamed, Modified and Created.
+#[cfg(afs_impls)]
+pub(crate) fn diff_dir_snapshots(old: &[Entry], new: &[Entry]) -> Vec<DirWatchEvent> {
+    use std::collections::HashMap;
+
+    let old_by_uri = old.iter().map(|e| (e.uri(), e)).collect::<HashMap<_, _>>();
+    let new_by_uri = new.iter().map(|e| (e.uri(), e)).collect::<HashMap<_, _>>();
+
+    let removed = old.iter()
+        .filter(|e| !new_by_uri.contains_key(e.uri()))
+        .collect::<Vec<_>>();
+
+    let created = new.iter()
+        .filter(|e| !old_by_uri.contains_key(e.uri()))
+        .collect::<Vec<_>>();
+
+    let mut renamed = Vec::new();
+    let mut modified = Vec::new();
+
+    for new in new {
+        let Some(old) = old_by_uri.get(new.uri()) else {
+            continue
+        };
+        if old.name() != new.name() {
+            renamed.push(DirWatchEvent::Renamed { old: (*old).clone(), new: new.clone() });
+        }
+        if old.file_len() != new.file_len() || old.last_modified() != new.last_modified() {
+            modified.push(DirWatchEvent::Modified { old: (*old).clone(), new: new.clone() });
+        }
+    }
+
+    // URI が変わる名前の変更は削除と追加の組として現れるため、
+    // 内容が同じものがそれぞれ一つだけの場合は名前の変更とみなす
+    let key = |e: &Entry| (e.is_dir(), e.file_len(), e.last_modified());
+    let pairs = removed.iter()
+        .enumerate()
+        .filter_map(|(i, old)| {
+            let k = key(old);
+            let is_unique = |entries: &[&Entry]| entries.iter().filter(|e| key(e) == k).count() == 1;
+            if !is_unique(&removed) || !is_unique(&created) {
+                return None
+            }
+            created.iter().position(|e| key(e) == k).map(|j| (i, j))
+        })
+        .collect::<Vec<_>>();
+
+    for &(i, j) in &pairs {
+        renamed.push(DirWatchEvent::Renamed { old: removed[i].clone(), new: created[j].clone() });
+    }
+
+    let removed = removed.iter()
+        .enumerate()
+        .filter(|(i, _)| pairs.iter().all(|(paired, _)| paired != i))
+        .map(|(_, e)| DirWatchEvent::Removed { entry: (*e).clone() });
+
+    let created = created.iter()
+        .enumerate()
+        .filter(|(j, _)| pairs.iter().all(|(_, paired)| paired != j))
+        .map(|(_, e)| DirWatchEvent::Created { entry: (*e).clone() });
+
+    removed
+        .chain(renamed)
+        .chain(modified)
+        .chain(created)
+        .collect()
+}
+
+
+#[cfg(all(test, afs_impls))]
+mod tests {
+    use super::*;
+
+    fn file(uri: &str, name: &str, len: u64, secs: u64) -> Entry {
+        Entry::File {
+            uri: FileUri::from_uri(uri),
+            name: name.to_string(),
+            last_modified: std::time::UNIX_EPOCH + std::time::Duration::from_secs(secs),
+            len,
+            mime_type: "text/plain".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_diff_dir_snapshots() {
+        let old = [
+            file("content://a", "a.txt", 1, 1),
+            file("content://b", "b.txt", 2, 2),
+            file("content://c", "c.txt", 3, 3),
+            file("content://d", "d.txt", 4, 4),
+            file("content://e", "e.txt", 5, 5),
+        ];
+        let new = [
+            file("content://a", "a.txt", 1, 1),
+            file("content://b", "b2.txt", 2, 2),
+            file("content://c", "c.txt", 30, 30),
+            file("content://d2", "d2.txt", 4, 4),
+            file("content://f", "f.txt", 6, 6),
+        ];
+
+        assert_eq!(diff_dir_snapshots(&old, &new), [
+            DirWatchEvent::Removed { entry: old[4].clone() },
+            DirWatchEvent::Renamed { old: old[1].clone(), new: new[1].clone() },
+            DirWatchEvent::Renamed { old: old[3].clone(), new: new[3].clone() },
+            DirWatchEvent::Modified { old: old[2].clone(), new: new[2].clone() },
+            DirWatchEvent::Created { entry: new[4].clone() },
+        ]);
+        assert!(diff_dir_snapshots(&new, &new).is_empty());
+    }
+}
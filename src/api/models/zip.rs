@@ -0,0 +1,126 @@
+use serde::{Deserialize, Serialize};
+use crate::*;
+
+
+/// Options for [`AndroidFs::create_zip`](crate::api::api_sync::AndroidFs::create_zip).
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CreateZipOptions {
+
+    /// Compression method of files.  
+    /// Directories are always stored.
+    ///
+    /// By default, this is [`ZipCompression::Deflated`].
+    pub compression: ZipCompression,
+}
+
+impl Default for CreateZipOptions {
+
+    fn default() -> Self {
+        Self {
+            compression: ZipCompression::Deflated,
+        }
+    }
+}
+
+/// Compression method of a zip entry.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Deserialize, Serialize)]
+#[non_exhaustive]
+pub enum ZipCompression {
+
+    /// No compression.  
+    /// This is suitable for files that are already compressed, such as images and videos.
+    Stored,
+
+    /// Deflate, supported by all zip tools.
+    Deflated,
+}
+
+/// Options for [`AndroidFs::extract_zip`](crate::api::api_sync::AndroidFs::extract_zip).
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ExtractZipOptions {
+
+    /// Glob patterns of paths in the archive, such as `*.txt` and `photos/**/*.jpg`.
+    /// `*` does not match `/`, and `**` matches zero or more directories.  
+    /// If not empty, only entries that match any of them are extracted.
+    ///
+    /// By default, this is empty.
+    pub include: Vec<String>,
+
+    /// Glob patterns of paths in the archive, in the same format as [`ExtractZipOptions::include`].  
+    /// Entries that match any of them are not extracted.
+    ///
+    /// By default, this is empty.
+    pub exclude: Vec<String>,
+
+    /// Maximum total uncompressed bytes of the entries to extract.  
+    /// This protects against zip bombs.
+    /// If the sizes recorded in the archive exceed this, nothing is extracted,
+    /// and an entry that turns out to be larger than its recorded size results in an error.
+    ///
+    /// By default, this is `Some(4 GiB)`.
+    pub max_total_len: Option<u64>,
+}
+
+impl Default for ExtractZipOptions {
+
+    fn default() -> Self {
+        Self {
+            include: Vec::new(),
+            exclude: Vec::new(),
+            max_total_len: Some(4 * 1024 * 1024 * 1024),
+        }
+    }
+}
+
+/// Result of an entry extracted by [`AndroidFs::extract_zip`](crate::api::api_sync::AndroidFs::extract_zip).
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct ExtractZipEntry {
+
+    /// Path of the entry in the archive, e.g. `sub/file.txt`.
+    /// A directory ends with `/`.
+    pub name: String,
+
+    /// Whether the entry is a directory.
+    pub is_dir: bool,
+
+    /// Uncompressed bytes of the entry recorded in the archive.
+    pub len: u64,
+
+    /// Result of extracting the entry.
+    pub result: Result<ExtractZipOutcome>,
+}
+
+/// Outcome of [`ExtractZipEntry`].
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum ExtractZipOutcome {
+
+    /// The entry was created in the destination.
+    ///
+    /// `relative_path` is the path relative to the destination directory.
+    /// This may differ from the name in the archive,
+    /// since the file provider may sanitize names or append a sequential number to avoid conflicts.
+    Extracted {
+        dest: FileUri,
+        relative_path: std::path::PathBuf,
+    },
+
+    /// The entry was not extracted because of [`ExtractZipOptions::include`] or [`ExtractZipOptions::exclude`].
+    Skipped,
+}
+
+impl ExtractZipOutcome {
+
+    /// URI of the destination entry.
+    /// If skipped, this is None.
+    pub fn dest(&self) -> Option<&FileUri> {
+        match self {
+            Self::Extracted { dest, .. } => Some(dest),
+            Self::Skipped => None,
+        }
+    }
+}
@@ -16,15 +16,11 @@ use super::*;
 /// ```
 #[sync_async]
 pub struct PrivateStorage<'a, R: tauri::Runtime> {
-    #[cfg(target_os = "android")]
-    pub(crate) handle: &'a tauri::plugin::PluginHandle<R>,
-
-    #[cfg(not(target_os = "android"))]
     #[allow(unused)]
-    pub(crate) handle: &'a std::marker::PhantomData<fn() -> R>,
+    pub(crate) handle: &'a Handle<R>,
 }
 
-#[cfg(target_os = "android")]
+#[cfg(afs_impls)]
 #[sync_async(
     use(if_sync) impls::SyncImpls as Impls;
     use(if_async) impls::AsyncImpls as Impls;
@@ -73,10 +69,10 @@ impl<'a, R: tauri::Runtime> PrivateStorage<'a, R> {
         dir: PrivateDir
     ) -> Result<std::path::PathBuf> {
 
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().private_dir_path(dir).map(Clone::clone)
         }
     }
@@ -89,10 +85,10 @@ impl<'a, R: tauri::Runtime> PrivateStorage<'a, R> {
         relative_path: impl AsRef<std::path::Path>
     ) -> Result<FileUri> {
 
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             let mut path = self.resolve_path(dir).await?;
             path.push(relative_path.as_ref());
             Ok(path.into())
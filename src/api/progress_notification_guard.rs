@@ -5,14 +5,14 @@ use super::*;
 
 #[sync_async]
 pub struct ProgressNotificationGuard<R: tauri::Runtime> {
-    #[cfg(target_os = "android")]
+    #[cfg(afs_impls)]
     inner: Inner<R>,
 
-    #[cfg(not(target_os = "android"))]
+    #[cfg(not(afs_impls))]
     inner: std::marker::PhantomData<fn() -> R>,
 }
 
-#[cfg(target_os = "android")]
+#[cfg(afs_impls)]
 #[sync_async(
     use(if_sync) impls::SyncImpls as Impls;
     use(if_async) impls::AsyncImpls as Impls;
@@ -27,7 +27,7 @@ impl<R: tauri::Runtime> ProgressNotificationGuard<R> {
         sub_text: Option<String>,
         progress: Option<u64>,
         progress_max: Option<u64>,
-        handle: tauri::plugin::PluginHandle<R>,
+        handle: Handle<R>,
     ) -> Result<Self> {
 
         let impls = Impls { handle: &handle };
@@ -89,60 +89,60 @@ impl<R: tauri::Runtime> ProgressNotificationGuard<R> {
 
     #[always_sync]
     pub fn title(&self) -> Option<String> {
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             None
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.inner.lock_current_state().title.clone()
         }
     }
 
     #[always_sync]
     pub fn text(&self) -> Option<String> {
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             None
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.inner.lock_current_state().text.clone()
         }
     }
 
     #[always_sync]
     pub fn sub_text(&self) -> Option<String> {
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             None
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.inner.lock_current_state().sub_text.clone()
         }
     }
 
     #[always_sync]
     pub fn progress(&self) -> Option<u64> {
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             None
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.inner.lock_current_state().progress
         }
     }
 
     #[always_sync]
     pub fn progress_max(&self) -> Option<u64> {
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             None
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.inner.lock_current_state().progress_max
         }
     }
 
     #[maybe_async]
     pub fn update_progress_by(&self, addend: u64) -> Result<()> {
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Ok(())
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             {
                 let mut state = self.inner.lock_current_state();
                 state.progress = Some(state.progress.unwrap_or(0).saturating_add(addend));
@@ -155,10 +155,10 @@ impl<R: tauri::Runtime> ProgressNotificationGuard<R> {
 
     #[maybe_async]
     pub fn update_progress(&self, progress: Option<u64>) -> Result<()> {
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Ok(())
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             {
                 let mut state = self.inner.lock_current_state();
                 state.progress = progress;
@@ -171,10 +171,10 @@ impl<R: tauri::Runtime> ProgressNotificationGuard<R> {
 
     #[maybe_async]
     pub fn update_progress_max(&self, progress_max: Option<u64>) -> Result<()> {
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Ok(())
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             {
                 let mut state = self.inner.lock_current_state();
                 state.progress_max = progress_max;
@@ -187,10 +187,10 @@ impl<R: tauri::Runtime> ProgressNotificationGuard<R> {
 
     #[maybe_async]
     pub fn update_title(&self, title: Option<&str>) -> Result<()> {
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Ok(())
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             {
                 let mut state = self.inner.lock_current_state();
                 state.title = title.map(|s| s.to_string());
@@ -203,10 +203,10 @@ impl<R: tauri::Runtime> ProgressNotificationGuard<R> {
 
     #[maybe_async]
     pub fn update_text(&self, text: Option<&str>) -> Result<()> {
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Ok(())
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
            {
                 let mut state = self.inner.lock_current_state();
                 state.text = text.map(|s| s.to_string());
@@ -219,10 +219,10 @@ impl<R: tauri::Runtime> ProgressNotificationGuard<R> {
 
     #[maybe_async]
     pub fn update_sub_text(&self, sub_text: Option<&str>) -> Result<()> {
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Ok(())
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             {
                 let mut state = self.inner.lock_current_state();
                 state.sub_text = sub_text.map(|s| s.to_string());
@@ -243,10 +243,10 @@ impl<R: tauri::Runtime> ProgressNotificationGuard<R> {
         progress_max: Option<u64>,
     ) -> Result<()> {
 
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Ok(())
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             {
                 let mut state = self.inner.lock_current_state();
                 *state = CurrentState {
@@ -272,7 +272,7 @@ impl<R: tauri::Runtime> ProgressNotificationGuard<R> {
         share_src: Option<&FileUri>
     ) {
 
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             let title = title.map(|s| s.to_string());
             let text = text.map(|s| s.to_string());
             let sub_text = sub_text.map(|s| s.to_string());
@@ -298,7 +298,7 @@ impl<R: tauri::Runtime> ProgressNotificationGuard<R> {
         share_src: impl 'static + Send + FnOnce() -> Option<FileUri>,
     ) {
         
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             if let Some(drop_behavior) = self.inner.lock_drop_behavior().as_mut() {
                 *drop_behavior = DropBehavior::Complete { 
                     title: Some(Box::new(title)),
@@ -318,7 +318,7 @@ impl<R: tauri::Runtime> ProgressNotificationGuard<R> {
         sub_text: Option<&str>
     ) {
 
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             let title = title.map(|s| s.to_string());
             let text = text.map(|s| s.to_string());
             let sub_text = sub_text.map(|s| s.to_string());
@@ -341,7 +341,7 @@ impl<R: tauri::Runtime> ProgressNotificationGuard<R> {
         sub_text: impl 'static + Send + FnOnce() -> Option<String>,
     ) {
         
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             if let Some(drop_behavior) = self.inner.lock_drop_behavior().as_mut() {
                 *drop_behavior = DropBehavior::Fail { 
                     title: Some(Box::new(title)),
@@ -354,7 +354,7 @@ impl<R: tauri::Runtime> ProgressNotificationGuard<R> {
 
     #[always_sync]
     pub fn set_drop_behavior_to_cancel(&self) {
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             if let Some(drop_behavior) = self.inner.lock_drop_behavior().as_mut() {
                 *drop_behavior = DropBehavior::Cancel;
             }
@@ -370,10 +370,10 @@ impl<R: tauri::Runtime> ProgressNotificationGuard<R> {
         share_src: Option<&FileUri>,
     ) -> Result<()> {
 
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Ok(())
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.finish_notification(title, text, sub_text, share_src, false).await
         }
     }
@@ -386,26 +386,26 @@ impl<R: tauri::Runtime> ProgressNotificationGuard<R> {
         sub_text: Option<&str>,
     ) -> Result<()> {
 
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Ok(())
         }
-        #[cfg(target_os = "android")] { 
+        #[cfg(afs_impls)] { 
             self.finish_notification(title, text, sub_text, None, true).await
         }
     }
 
     #[maybe_async]
     pub fn cancel(self) -> Result<()> {
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Ok(())
         }
-        #[cfg(target_os = "android")] { 
+        #[cfg(afs_impls)] { 
             self.cancel_notification().await
         }
     }
     
     
-    #[cfg(target_os = "android")] 
+    #[cfg(afs_impls)] 
     #[maybe_async]
     fn finish_notification(
         self,
@@ -430,7 +430,7 @@ impl<R: tauri::Runtime> ProgressNotificationGuard<R> {
         Ok(())
     }
 
-    #[cfg(target_os = "android")] 
+    #[cfg(afs_impls)] 
     #[maybe_async]
     fn cancel_notification(self) -> Result<()> {  
         self.impls().cancel_notification(self.inner.id).await?;
@@ -438,7 +438,7 @@ impl<R: tauri::Runtime> ProgressNotificationGuard<R> {
         Ok(())
     }
 
-    #[cfg(target_os = "android")] 
+    #[cfg(afs_impls)] 
     #[maybe_async]
     fn update_notification(&self) -> Result<()> {
         let state = self.inner.lock_current_state().clone();
@@ -459,16 +459,16 @@ impl<R: tauri::Runtime> ProgressNotificationGuard<R> {
 }
 
 
-#[cfg(target_os = "android")]
+#[cfg(afs_impls)]
 struct Inner<R: tauri::Runtime> {
     id: i32,
     icon: ProgressNotificationIcon,
     drop_behavior: std::sync::Mutex<Option<DropBehavior>>,
     current_state: std::sync::Mutex<CurrentState>,
-    handle: tauri::plugin::PluginHandle<R>,
+    handle: Handle<R>,
 }
 
-#[cfg(target_os = "android")]
+#[cfg(afs_impls)]
 enum DropBehavior {
     Complete {
         title: Option<Box<dyn Send + 'static + FnOnce() -> Option<String>>>,
@@ -484,7 +484,7 @@ enum DropBehavior {
     Cancel
 }
 
-#[cfg(target_os = "android")]
+#[cfg(afs_impls)]
 #[derive(Clone)]
 struct CurrentState {
     title: Option<String>,
@@ -494,7 +494,7 @@ struct CurrentState {
     progress_max: Option<u64>,
 }
 
-#[cfg(target_os = "android")]
+#[cfg(afs_impls)]
 impl<R: tauri::Runtime> Inner<R> {
 
     fn lock_drop_behavior<'a>(&'a self) -> std::sync::MutexGuard<'a, Option<DropBehavior>> {
@@ -506,7 +506,7 @@ impl<R: tauri::Runtime> Inner<R> {
     }
 }
 
-#[cfg(target_os = "android")]
+#[cfg(afs_impls)]
 impl<R: tauri::Runtime> Drop for Inner<R> {
 
     fn drop(&mut self) {
@@ -552,7 +552,7 @@ impl<R: tauri::Runtime> Drop for Inner<R> {
     }
 }
 
-#[cfg(target_os = "android")]
+#[cfg(afs_impls)]
 fn normalize_progress_and_max(
     progress: Option<u64>,
     progress_max: Option<u64>,
@@ -16,15 +16,11 @@ use super::*;
 /// ```
 #[sync_async]
 pub struct PublicStorage<'a, R: tauri::Runtime> {
-    #[cfg(target_os = "android")]
-    pub(crate) handle: &'a tauri::plugin::PluginHandle<R>,
-
-    #[cfg(not(target_os = "android"))]
     #[allow(unused)]
-    pub(crate) handle: &'a std::marker::PhantomData<fn() -> R>,
+    pub(crate) handle: &'a Handle<R>,
 }
 
-#[cfg(target_os = "android")]
+#[cfg(afs_impls)]
 #[sync_async(
     use(if_sync) impls::SyncImpls as Impls;
     use(if_async) impls::AsyncImpls as Impls;
@@ -69,10 +65,10 @@ impl<'a, R: tauri::Runtime> PublicStorage<'a, R> {
     /// All Android versions
     #[maybe_async]
     pub fn request_permission(&self) -> Result<bool> {
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().request_storage_permission_for_public_storage().await
         }
     }
@@ -100,10 +96,10 @@ impl<'a, R: tauri::Runtime> PublicStorage<'a, R> {
     /// All Android versions.
     #[maybe_async]
     pub fn check_permission(&self) -> Result<bool> {
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().check_storage_permission_for_public_storage().await
         }
     }
@@ -133,10 +129,10 @@ impl<'a, R: tauri::Runtime> PublicStorage<'a, R> {
     /// All Android version.  
     #[maybe_async]
     pub fn get_volumes(&self) -> Result<Vec<StorageVolume>> {
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().get_available_storage_volumes_for_public_storage().await
         }
     }
@@ -161,10 +157,10 @@ impl<'a, R: tauri::Runtime> PublicStorage<'a, R> {
     /// All Android version.
     #[maybe_async]
     pub fn get_primary_volume(&self) -> Result<Option<StorageVolume>> {
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().get_primary_storage_volume_if_available_for_public_storage().await
         }
     }
@@ -240,10 +236,10 @@ impl<'a, R: tauri::Runtime> PublicStorage<'a, R> {
         mime_type: Option<&str>
     ) -> Result<FileUri> {
 
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().create_new_file_in_public_storage(
                 volume_id, 
                 base_dir, 
@@ -332,10 +328,10 @@ impl<'a, R: tauri::Runtime> PublicStorage<'a, R> {
         mime_type: Option<&str>
     ) -> Result<FileUri> {
 
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().create_new_file_in_public_storage(
                 volume_id, 
                 base_dir, 
@@ -395,10 +391,10 @@ impl<'a, R: tauri::Runtime> PublicStorage<'a, R> {
         relative_path: impl AsRef<std::path::Path>, 
     ) -> Result<()> {
 
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().create_dir_all_in_public_storage(volume_id, base_dir, relative_path).await
         }
     }
@@ -470,10 +466,10 @@ impl<'a, R: tauri::Runtime> PublicStorage<'a, R> {
         contents: impl AsRef<[u8]>
     ) -> Result<FileUri> {
 
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().write_new_file_in_public_storage(volume_id, base_dir, relative_path, mime_type, contents).await
         }
     }
@@ -514,10 +510,10 @@ impl<'a, R: tauri::Runtime> PublicStorage<'a, R> {
         uri: &FileUri,
     ) -> Result<()> {
 
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().scan_file_in_public_storage(uri, false).await
         }
     }
@@ -550,10 +546,10 @@ impl<'a, R: tauri::Runtime> PublicStorage<'a, R> {
         mime_type: Option<&str>
     ) -> Result<FileUri> {
 
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().scan_file_by_path_in_public_storage(path, mime_type).await
         }
     }
@@ -585,10 +581,10 @@ impl<'a, R: tauri::Runtime> PublicStorage<'a, R> {
     /// - <https://developer.android.com/training/data-storage/shared/media?hl=en#toggle-pending-status>
     #[maybe_async]
     pub fn set_pending(&self, uri: &FileUri, is_pending: bool) -> Result<()> {
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().set_file_pending_in_public_storage(uri, is_pending).await
         }
     }
@@ -619,10 +615,10 @@ impl<'a, R: tauri::Runtime> PublicStorage<'a, R> {
         uri: &FileUri,
     ) -> Result<std::path::PathBuf> {
 
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().get_file_path_in_public_storage(uri).await
         }
     }
@@ -717,10 +713,10 @@ impl<'a, R: tauri::Runtime> PublicStorage<'a, R> {
         base_dir: impl Into<PublicDir>,
     ) -> Result<std::path::PathBuf> {
 
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().resolve_dir_path_in_public_storage(volume_id, base_dir).await
         }
     }
@@ -767,10 +763,10 @@ impl<'a, R: tauri::Runtime> PublicStorage<'a, R> {
         create_dir_all: bool
     ) -> Result<FileUri> {
 
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().resolve_initial_location_in_public_storage(volume_id, base_dir, relative_path, create_dir_all).await
         }
     }
@@ -784,10 +780,10 @@ impl<'a, R: tauri::Runtime> PublicStorage<'a, R> {
     /// All Android version.
     #[always_sync]
     pub fn is_audiobooks_dir_available(&self) -> Result<bool> {
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             Ok(self.impls().consts()?.env_dir_audiobooks.is_some())
         }
     }
@@ -801,10 +797,10 @@ impl<'a, R: tauri::Runtime> PublicStorage<'a, R> {
     /// All Android version.
     #[always_sync]
     pub fn is_recordings_dir_available(&self) -> Result<bool> {
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             Ok(self.impls().consts()?.env_dir_recordings.is_some())
         }
     }
@@ -823,10 +819,10 @@ impl<'a, R: tauri::Runtime> PublicStorage<'a, R> {
         uri: &FileUri,
     ) -> Result<()> {
 
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().scan_file_in_public_storage(uri, true).await
         }
     }
@@ -840,10 +836,10 @@ impl<'a, R: tauri::Runtime> PublicStorage<'a, R> {
         uri: &FileUri,
     ) -> Result<()> {
 
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().scan_file_in_public_storage_for_result(uri, true).await
         }
     }
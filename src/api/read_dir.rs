@@ -0,0 +1,266 @@
+use sync_async::sync_async;
+use crate::*;
+#[allow(unused_imports)]
+use super::*;
+
+
+/// Lazy iterator over the entries of a directory.
+/// This is created by [`AndroidFs::read_dir_paged`].
+///
+/// Entries are read page by page when they are needed,
+/// so this can be used for a directory with a large number of entries.
+/// The async version also implements [`futures_core::Stream`].
+///
+/// # Examples
+/// ```no_run
+/// fn example(app: &tauri::AppHandle, dir: &tauri_plugin_android_fs::FileUri) -> tauri_plugin_android_fs::Result<()> {
+///     use tauri_plugin_android_fs::{AndroidFsExt as _, EntryOptions};
+///
+///     let api = app.android_fs();
+///
+///     for entry in api.read_dir_paged(dir, EntryOptions::ALL, 500)? {
+///         let entry = entry?;
+///         println!("{:?}", entry.name());
+///     }
+///     Ok(())
+/// }
+/// ```
+#[sync_async]
+pub struct ReadDir<R: tauri::Runtime> {
+    #[cfg(afs_impls)]
+    inner: Inner<R>,
+
+    #[cfg(not(afs_impls))]
+    #[allow(unused)]
+    inner: std::marker::PhantomData<fn() -> R>,
+}
+
+#[cfg(afs_impls)]
+#[sync_async(
+    use(if_sync) impls::SyncImpls as Impls;
+    use(if_async) impls::AsyncImpls as Impls;
+)]
+impl<R: tauri::Runtime> ReadDir<R> {
+
+    #[always_sync]
+    pub(crate) fn new(uri: &FileUri, options: EntryOptions, page_size: u64, handle: Handle<R>) -> Result<Self> {
+        if page_size == 0 {
+            return Err(Error::invalid_value("page_size: must not be zero"))
+        }
+
+        Ok(Self {
+            inner: Inner {
+                uri: uri.clone(),
+                options,
+                page_size,
+                offset: 0,
+                is_exhausted: false,
+                entries: std::collections::VecDeque::new(),
+                pending_page: None,
+                handle,
+            }
+        })
+    }
+
+    #[always_sync]
+    fn impls(&self) -> Impls<'_, R> {
+        Impls { handle: &self.inner.handle }
+    }
+
+    #[maybe_async]
+    fn fill_page(&mut self) -> Result<()> {
+        let range = self.inner.next_page_range();
+        let result = self.impls()
+            .read_dir(&self.inner.uri, self.inner.options, range).await
+            .map(|entries| entries.collect());
+
+        self.inner.on_page(result)
+    }
+
+    #[maybe_async]
+    fn next_page_inner(&mut self) -> Option<Result<Vec<OptionalEntry>>> {
+        if self.inner.entries.is_empty() {
+            if self.inner.is_exhausted {
+                return None
+            }
+            if let Err(err) = self.fill_page().await {
+                return Some(Err(err))
+            }
+            if self.inner.entries.is_empty() {
+                return None
+            }
+        }
+
+        Some(Ok(self.inner.entries.drain(..).collect()))
+    }
+
+    #[maybe_async]
+    fn next_entry_inner(&mut self) -> Option<Result<OptionalEntry>> {
+        if self.inner.entries.is_empty() && !self.inner.is_exhausted {
+            if let Err(err) = self.fill_page().await {
+                return Some(Err(err))
+            }
+        }
+
+        self.inner.entries.pop_front().map(Ok)
+    }
+}
+
+#[sync_async]
+impl<R: tauri::Runtime> ReadDir<R> {
+
+    /// Returns the next entry.
+    /// If all entries have been yielded, this returns `None`.
+    ///
+    /// If a page cannot be read, this returns the error once and then ends.
+    #[maybe_async]
+    pub fn next_entry(&mut self) -> Option<Result<OptionalEntry>> {
+        #[cfg(not(afs_impls))] {
+            None
+        }
+        #[cfg(afs_impls)] {
+            self.next_entry_inner().await
+        }
+    }
+
+    /// Returns the remaining entries of the current page,
+    /// or reads the next page if there are none.
+    /// If all entries have been yielded, this returns `None`.
+    ///
+    /// If a page cannot be read, this returns the error once and then ends.
+    #[maybe_async]
+    pub fn next_page(&mut self) -> Option<Result<Vec<OptionalEntry>>> {
+        #[cfg(not(afs_impls))] {
+            None
+        }
+        #[cfg(afs_impls)] {
+            self.next_page_inner().await
+        }
+    }
+
+    /// Reads all the remaining entries.
+    #[maybe_async]
+    pub fn collect_entries(mut self) -> Result<Vec<OptionalEntry>> {
+        let mut entries = Vec::new();
+        while let Some(page) = self.next_page().await {
+            entries.extend(page?);
+        }
+        Ok(entries)
+    }
+}
+
+impl<R: tauri::Runtime> Iterator for SyncReadDir<R> {
+    type Item = Result<OptionalEntry>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_entry()
+    }
+}
+
+// フィールドを pin された状態で扱うことはないため、R に関わらず Unpin にできる
+impl<R: tauri::Runtime> Unpin for AsyncReadDir<R> {}
+
+impl<R: tauri::Runtime> futures_core::Stream for AsyncReadDir<R> {
+    type Item = Result<OptionalEntry>;
+
+    fn poll_next(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>
+    ) -> std::task::Poll<Option<Self::Item>> {
+
+        #[cfg(not(afs_impls))] {
+            let _ = cx;
+            std::task::Poll::Ready(None)
+        }
+        #[cfg(afs_impls)] {
+            self.get_mut().inner.poll_next_entry(cx)
+        }
+    }
+}
+
+
+#[cfg(afs_impls)]
+type PageFuture = std::pin::Pin<Box<dyn std::future::Future<Output = Result<Vec<OptionalEntry>>> + Send>>;
+
+#[cfg(afs_impls)]
+struct Inner<R: tauri::Runtime> {
+    uri: FileUri,
+    options: EntryOptions,
+    page_size: u64,
+    offset: u64,
+    is_exhausted: bool,
+    entries: std::collections::VecDeque<OptionalEntry>,
+
+    /// Stream として poll されている途中のページの読み込み
+    pending_page: Option<PageFuture>,
+
+    handle: Handle<R>,
+}
+
+#[cfg(afs_impls)]
+impl<R: tauri::Runtime> Inner<R> {
+
+    fn next_page_range(&self) -> std::ops::Range<u64> {
+        self.offset..self.offset.saturating_add(self.page_size)
+    }
+
+    /// 読み込んだページを反映する。
+    /// エラーの場合はそれ以降を読まない。
+    fn on_page(&mut self, result: Result<Vec<OptionalEntry>>) -> Result<()> {
+        match result {
+            Ok(entries) => {
+                let len = entries.len() as u64;
+                self.is_exhausted = len < self.page_size;
+                self.offset = self.offset.saturating_add(len);
+                self.entries.extend(entries);
+                Ok(())
+            },
+            Err(err) => {
+                self.is_exhausted = true;
+                Err(err)
+            }
+        }
+    }
+
+    fn poll_next_entry(
+        &mut self,
+        cx: &mut std::task::Context<'_>
+    ) -> std::task::Poll<Option<Result<OptionalEntry>>> {
+
+        use std::task::Poll;
+
+        loop {
+            if let Some(entry) = self.entries.pop_front() {
+                return Poll::Ready(Some(Ok(entry)))
+            }
+            if self.is_exhausted {
+                return Poll::Ready(None)
+            }
+
+            let page = match &mut self.pending_page {
+                Some(page) => page,
+                None => self.pending_page.insert(self.new_page_future()),
+            };
+            let result = std::task::ready!(page.as_mut().poll(cx));
+            self.pending_page = None;
+
+            if let Err(err) = self.on_page(result) {
+                return Poll::Ready(Some(Err(err)))
+            }
+        }
+    }
+
+    /// self を借用しないページの読み込みを作成する
+    fn new_page_future(&self) -> PageFuture {
+        let handle = self.handle.clone();
+        let uri = self.uri.clone();
+        let options = self.options;
+        let range = self.next_page_range();
+
+        Box::pin(async move {
+            impls::AsyncImpls { handle: &handle }
+                .read_dir(&uri, options, range).await
+                .map(|entries| entries.collect())
+        })
+    }
+}
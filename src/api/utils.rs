@@ -6,15 +6,11 @@ use super::*;
 /// API of utils.
 #[sync_async]
 pub struct Utils<'a, R: tauri::Runtime> {
-    #[cfg(target_os = "android")]
-    pub(crate) handle: &'a tauri::plugin::PluginHandle<R>,
-
-    #[cfg(not(target_os = "android"))]
     #[allow(unused)]
-    pub(crate) handle: &'a std::marker::PhantomData<fn() -> R>,
+    pub(crate) handle: &'a Handle<R>,
 }
 
-#[cfg(target_os = "android")]
+#[cfg(afs_impls)]
 #[sync_async(
     use(if_sync) impls::SyncImpls as Impls;
     use(if_async) impls::AsyncImpls as Impls;
@@ -68,10 +64,10 @@ impl<'a, R: tauri::Runtime> Utils<'a, R> {
         progress_max: Option<u64>,
     ) -> Result<ProgressNotificationGuard<R>> {
 
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             ProgressNotificationGuard::with_new_notification(
                 icon,
                 title.map(|s| s.to_string()), 
@@ -86,10 +82,10 @@ impl<'a, R: tauri::Runtime> Utils<'a, R> {
 
     #[maybe_async]
     pub fn cancel_all_notifications(&self) -> Result<()> {
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().cancel_all_notifications().await
         }
     }
@@ -100,20 +96,20 @@ impl<'a, R: tauri::Runtime> Utils<'a, R> {
     /// the permissions will be declared automatically.
     #[maybe_async]
     pub fn request_notification_permission(&self) -> Result<bool> {
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().request_notification_permission().await
         }
     }
 
     #[maybe_async]
     pub fn check_notification_permission(&self) -> Result<bool> {
-        #[cfg(not(target_os = "android"))] {
+        #[cfg(not(afs_impls))] {
             Err(Error::NOT_ANDROID)
         }
-        #[cfg(target_os = "android")] {
+        #[cfg(afs_impls)] {
             self.impls().check_notification_permission().await
         }
     }
@@ -0,0 +1,402 @@
+use sync_async::sync_async;
+use crate::*;
+#[allow(unused_imports)]
+use super::*;
+
+
+/// Recursive iterator over the entries of a directory tree.
+/// This is created by [`AndroidFs::walk_dir`].
+///
+/// Entries are read from each directory page by page,
+/// so this can be used for a large directory tree without loading it all at once.
+/// The async version also implements [`futures_core::Stream`].
+///
+/// # Examples
+/// ```no_run
+/// fn example(app: &tauri::AppHandle, dir: &tauri_plugin_android_fs::FileUri) -> tauri_plugin_android_fs::Result<()> {
+///     use tauri_plugin_android_fs::{AndroidFsExt as _, WalkOptions};
+///
+///     let api = app.android_fs();
+///     let options = WalkOptions {
+///         name_pattern: Some("*.txt".into()),
+///         ..Default::default()
+///     };
+///
+///     for entry in api.walk_dir(dir, options)? {
+///         let entry = entry?;
+///         println!("{}", entry.relative_path.display());
+///     }
+///     Ok(())
+/// }
+/// ```
+#[sync_async]
+pub struct WalkDir<R: tauri::Runtime> {
+    #[cfg(afs_impls)]
+    inner: Inner<R>,
+
+    #[cfg(not(afs_impls))]
+    #[allow(unused)]
+    inner: std::marker::PhantomData<fn() -> R>,
+}
+
+#[cfg(afs_impls)]
+#[sync_async(
+    use(if_sync) impls::SyncImpls as Impls;
+    use(if_async) impls::AsyncImpls as Impls;
+)]
+impl<R: tauri::Runtime> WalkDir<R> {
+
+    #[always_sync]
+    pub(crate) fn new(root: &FileUri, options: WalkOptions, handle: Handle<R>) -> Result<Self> {
+        if options.page_size == 0 {
+            return Err(Error::invalid_value("page_size: must not be zero"))
+        }
+
+        let name_pattern = options.name_pattern
+            .as_deref()
+            .map(glob::Pattern::new)
+            .transpose()
+            .map_err(|e| Error::with(format!("invalid name_pattern: {e}")))?;
+
+        let mut stack = Vec::new();
+        if options.max_depth != Some(0) {
+            stack.push(Frame::new(root.clone(), 1, std::path::PathBuf::new(), None));
+        }
+
+        Ok(Self {
+            inner: Inner {
+                root: root.clone(),
+                name_pattern,
+                descend_filter: None,
+                options,
+                stack,
+                pending_page: None,
+                handle,
+            }
+        })
+    }
+
+    /// Sets a filter of directories to descend into.
+    /// It receives the relative path of a directory.
+    #[always_sync]
+    pub(crate) fn with_descend_filter(
+        mut self,
+        filter: impl Fn(&std::path::Path) -> bool + Send + Sync + 'static
+    ) -> Self {
+
+        self.inner.descend_filter = Some(Box::new(filter));
+        self
+    }
+
+    #[always_sync]
+    fn impls(&self) -> Impls<'_, R> {
+        Impls { handle: &self.inner.handle }
+    }
+
+    #[maybe_async]
+    fn next_inner(&mut self) -> Option<Result<WalkEntry>> {
+        loop {
+            if let Some((uri, range)) = self.inner.next_page_request() {
+                let result = self.impls()
+                    .read_dir(&uri, self.inner.read_dir_options(), range).await
+                    .map(|entries| entries.collect());
+
+                if let Err(err) = self.inner.on_page(result) {
+                    return Some(Err(err))
+                }
+                continue
+            }
+
+            if self.inner.stack.is_empty() {
+                return None
+            }
+            if let Some(item) = self.inner.next_from_page() {
+                return Some(item)
+            }
+        }
+    }
+}
+
+#[sync_async]
+impl<R: tauri::Runtime> WalkDir<R> {
+
+    /// Returns the next entry.
+    /// If all entries have been yielded, this returns `None`.
+    ///
+    /// If a directory cannot be read, this returns the error once,
+    /// and the walk continues with the remaining entries.
+    #[maybe_async]
+    pub fn next_entry(&mut self) -> Option<Result<WalkEntry>> {
+        #[cfg(not(afs_impls))] {
+            None
+        }
+        #[cfg(afs_impls)] {
+            self.next_inner().await
+        }
+    }
+
+    /// Reads all the remaining entries.
+    #[maybe_async]
+    pub fn collect_entries(mut self) -> Result<Vec<WalkEntry>> {
+        let mut entries = Vec::new();
+        while let Some(entry) = self.next_entry().await {
+            entries.push(entry?);
+        }
+        Ok(entries)
+    }
+}
+
+impl<R: tauri::Runtime> Iterator for SyncWalkDir<R> {
+    type Item = Result<WalkEntry>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_entry()
+    }
+}
+
+// フィールドを pin された状態で扱うことはないため、R に関わらず Unpin にできる
+impl<R: tauri::Runtime> Unpin for AsyncWalkDir<R> {}
+
+impl<R: tauri::Runtime> futures_core::Stream for AsyncWalkDir<R> {
+    type Item = Result<WalkEntry>;
+
+    fn poll_next(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>
+    ) -> std::task::Poll<Option<Self::Item>> {
+
+        #[cfg(not(afs_impls))] {
+            let _ = cx;
+            std::task::Poll::Ready(None)
+        }
+        #[cfg(afs_impls)] {
+            self.get_mut().inner.poll_next_entry(cx)
+        }
+    }
+}
+
+
+#[cfg(afs_impls)]
+type DescendFilter = Box<dyn Fn(&std::path::Path) -> bool + Send + Sync>;
+
+#[cfg(afs_impls)]
+type PageFuture = std::pin::Pin<Box<dyn std::future::Future<Output = Result<std::collections::VecDeque<OptionalEntry>>> + Send>>;
+
+#[cfg(afs_impls)]
+struct Inner<R: tauri::Runtime> {
+    options: WalkOptions,
+    name_pattern: Option<glob::Pattern>,
+    descend_filter: Option<DescendFilter>,
+    root: FileUri,
+    stack: Vec<Frame>,
+
+    /// Stream として poll されている途中のページの読み込み
+    pending_page: Option<PageFuture>,
+
+    handle: Handle<R>,
+}
+
+#[cfg(afs_impls)]
+struct Frame {
+    uri: FileUri,
+
+    /// 子エントリの深さ
+    depth: u32,
+
+    relative_path: std::path::PathBuf,
+    entries: std::collections::VecDeque<OptionalEntry>,
+    offset: u64,
+    is_exhausted: bool,
+
+    /// PostOrder で子エントリの後に返す、このディレクトリ自身
+    pending: Option<WalkEntry>,
+}
+
+#[cfg(afs_impls)]
+impl Frame {
+
+    fn new(
+        uri: FileUri,
+        depth: u32,
+        relative_path: std::path::PathBuf,
+        pending: Option<WalkEntry>
+    ) -> Self {
+
+        Self {
+            uri,
+            depth,
+            relative_path,
+            entries: std::collections::VecDeque::new(),
+            offset: 0,
+            is_exhausted: false,
+            pending,
+        }
+    }
+}
+
+#[cfg(afs_impls)]
+impl<R: tauri::Runtime> Inner<R> {
+
+    /// 走査とフィルタに必要なフィールドを含めたオプション
+    fn read_dir_options(&self) -> EntryOptions {
+        EntryOptions {
+            uri: true,
+            name: true,
+            mime_type: self.options.entry_options.mime_type || !self.options.mime_types.is_empty(),
+            ..self.options.entry_options
+        }
+    }
+
+    /// 現在のディレクトリの次のページを読み込む必要がある場合、その URI と範囲を返す
+    fn next_page_request(&self) -> Option<(FileUri, std::ops::Range<u64>)> {
+        let frame = self.stack.last()?;
+        if !frame.entries.is_empty() || frame.is_exhausted {
+            return None
+        }
+
+        let start = frame.offset;
+        Some((frame.uri.clone(), start..start.saturating_add(self.options.page_size)))
+    }
+
+    /// 読み込んだページを現在のディレクトリに反映する。
+    /// 
+    /// エラーの場合は、読めなかったディレクトリを飛ばして続行できるようにする。
+    /// PostOrder で後に返すこのディレクトリ自身は、次の呼び出しで返す。
+    fn on_page(&mut self, result: Result<std::collections::VecDeque<OptionalEntry>>) -> Result<()> {
+        let page_size = self.options.page_size;
+        let Some(frame) = self.stack.last_mut() else {
+            return Ok(())
+        };
+
+        match result {
+            Ok(entries) => {
+                let len = entries.len() as u64;
+                frame.is_exhausted = len < page_size;
+                frame.offset = frame.offset.saturating_add(len);
+                frame.entries = entries;
+                Ok(())
+            },
+            Err(err) => {
+                frame.is_exhausted = true;
+                Err(err)
+            }
+        }
+    }
+
+    fn poll_next_entry(
+        &mut self,
+        cx: &mut std::task::Context<'_>
+    ) -> std::task::Poll<Option<Result<WalkEntry>>> {
+
+        use std::task::Poll;
+
+        loop {
+            if self.pending_page.is_none() {
+                if let Some((uri, range)) = self.next_page_request() {
+                    self.pending_page = Some(self.new_page_future(uri, range));
+                }
+            }
+            if let Some(page) = &mut self.pending_page {
+                let result = std::task::ready!(page.as_mut().poll(cx));
+                self.pending_page = None;
+
+                if let Err(err) = self.on_page(result) {
+                    return Poll::Ready(Some(Err(err)))
+                }
+                continue
+            }
+
+            if self.stack.is_empty() {
+                return Poll::Ready(None)
+            }
+            if let Some(item) = self.next_from_page() {
+                return Poll::Ready(Some(item))
+            }
+        }
+    }
+
+    /// self を借用しないページの読み込みを作成する
+    fn new_page_future(&self, uri: FileUri, range: std::ops::Range<u64>) -> PageFuture {
+        let handle = self.handle.clone();
+        let options = self.read_dir_options();
+
+        Box::pin(async move {
+            impls::AsyncImpls { handle: &handle }
+                .read_dir(&uri, options, range).await
+                .map(|entries| entries.collect())
+        })
+    }
+
+    /// 読み込み済みのページから次のエントリを取り出す。
+    /// 返すべきエントリがまだ無い場合は None を返す。
+    fn next_from_page(&mut self) -> Option<Result<WalkEntry>> {
+        let frame = self.stack.last_mut()?;
+
+        let Some(entry) = frame.entries.pop_front() else {
+            let frame = self.stack.pop()?;
+            return frame.pending.map(Ok)
+        };
+
+        let depth = frame.depth;
+        let relative_path = frame.relative_path.join(entry.name().unwrap_or_default());
+        let should_descend = entry.is_dir()
+            && self.options.max_depth.map_or(true, |max| depth < max)
+            && self.descend_filter.as_ref().map_or(true, |f| f(&relative_path));
+        let child_uri = match should_descend {
+            true => match entry.uri() {
+                Some(uri) => Some(impls::inherit_top_tree_uri(uri.clone(), &self.root)),
+                None => return Some(Err(Error::missing_value("uri"))),
+            },
+            false => None,
+        };
+
+        let item = match self.matches(&entry) {
+            true => Some(WalkEntry {
+                entry: self.project(entry),
+                depth,
+                relative_path: relative_path.clone(),
+            }),
+            false => None,
+        };
+
+        if let Some(child_uri) = child_uri {
+            return match self.options.order {
+                WalkOrder::PostOrder => {
+                    self.stack.push(Frame::new(child_uri, depth + 1, relative_path, item));
+                    None
+                },
+                WalkOrder::PreOrder => {
+                    self.stack.push(Frame::new(child_uri, depth + 1, relative_path, None));
+                    item.map(Ok)
+                },
+            }
+        }
+
+        item.map(Ok)
+    }
+
+    fn matches(&self, entry: &OptionalEntry) -> bool {
+        let type_matches = match self.options.entry_type {
+            Some(WalkEntryType::File) => entry.is_file(),
+            Some(WalkEntryType::Dir) => entry.is_dir(),
+            None => true,
+        };
+        let name_matches = match &self.name_pattern {
+            Some(pattern) => entry.name().is_some_and(|name| pattern.matches(name)),
+            None => true,
+        };
+
+        type_matches && name_matches && self.options.matches_mime_type(entry.file_mime_type())
+    }
+
+    /// [`WalkOptions::entry_options`] で指定されたフィールドのみを残し、
+    /// ルートディレクトリの document_top_tree_uri を引き継ぐ
+    fn project(&self, entry: OptionalEntry) -> OptionalEntry {
+        let mut entry = entry.retain_fields(self.options.entry_options);
+        let (OptionalEntry::File { uri, .. } | OptionalEntry::Dir { uri, .. }) = &mut entry;
+        if let Some(uri) = uri {
+            *uri = impls::inherit_top_tree_uri(uri.clone(), &self.root);
+        }
+        entry
+    }
+}
@@ -0,0 +1,923 @@
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+use crate::*;
+use super::AndroidFsBackend;
+
+
+const EXTERNAL_STORAGE_PROVIDER: &str = "content://com.android.externalstorage.documents";
+const MEDIA_STORE_PROVIDER: &str = "content://media";
+const PRIMARY_VOLUME_UID: &str = "primary";
+const PRIMARY_MEDIA_STORE_VOLUME_NAME: &str = "external_primary";
+const APP_ID: &str = "host.tauri.android_fs";
+
+/// [`AndroidFsBackend`] that emulates Android file system in a sandbox directory by [`std::fs`].
+///
+/// This is intended to run the APIs off-device, e.g. for unit tests on CI.
+///
+/// # Layout
+/// - `<root>/private/{data, cache, no_backup_data}` :  
+///   Directories of [`PrivateDir`].  
+///
+/// - `<root>/storage/emulated/0` :  
+///   Top directory of the primary storage volume.  
+///
+/// - `<root>/storage/<uid>` :  
+///   Top directory of the removable storage volume added by [`HostBackend::with_removable_volume`].  
+///
+/// # URIs
+/// - `file://` URIs are mapped to the paths as they are.  
+/// - `content://com.android.externalstorage.documents/...` URIs are mapped to the paths in the storage volumes.  
+///   They can be obtained by [`HostBackend::tree_uri`] or [`HostBackend::document_uri`].  
+/// - `content://media/...` URIs are fake MediaStore URIs created by [`PublicStorage`](crate::api::api_sync::PublicStorage).  
+///   They are valid only while this backend is alive.  
+///
+/// # Note
+/// File pickers return the URIs added by [`HostBackend::push_picker_result`] in order,
+/// or are treated as canceled by the user if none.
+/// Notifications, thumbnails and app choosers do nothing.
+///
+/// # Examples
+/// ```no_run
+/// use tauri_plugin_android_fs::{AndroidFsExt as _, backend::HostBackend};
+///
+/// let backend = std::sync::Arc::new(HostBackend::new("/tmp/afs-sandbox"));
+///
+/// tauri::Builder::default()
+///     .plugin(tauri_plugin_android_fs::init_with_backend(backend.clone()))
+///     .setup(move |app| {
+///         backend.push_picker_result([backend.tree_uri(None, "Documents")]);
+///
+///         let api = app.android_fs();
+///         let dir = api.file_picker().pick_dir(None, false)?.unwrap();
+///         let file = api.create_new_file(&dir, "hello.txt", None)?;
+///         api.write(&file, "Hello")?;
+///         Ok(())
+///     })
+///     .run(tauri::generate_context!())
+///     .expect("error while running tauri application");
+/// ```
+pub struct HostBackend {
+    root: PathBuf,
+    api_level: i32,
+    volumes: Vec<Volume>,
+    state: std::sync::Mutex<State>,
+}
+
+struct Volume {
+    uid: String,
+    description: String,
+    is_primary: bool,
+}
+
+#[derive(Default)]
+struct State {
+    media_store: HashMap<u64, MediaStoreEntry>,
+    next_media_store_id: u64,
+    next_notification_id: i32,
+    picker_results: VecDeque<Vec<FileUri>>,
+    persisted_uris: Vec<FileUri>,
+}
+
+struct MediaStoreEntry {
+    path: PathBuf,
+}
+
+impl HostBackend {
+
+    /// Creates a new backend whose sandbox directory is `root`.
+    /// Directories are created on demand.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            api_level: api_level::ANDROID_15,
+            volumes: vec![Volume {
+                uid: PRIMARY_VOLUME_UID.to_string(),
+                description: "Internal shared storage".to_string(),
+                is_primary: true,
+            }],
+            state: std::sync::Mutex::new(State::default()),
+        }
+    }
+
+    /// Sets the API level of the fake device.
+    /// By default, this is [`api_level::ANDROID_15`].
+    pub fn with_api_level(mut self, api_level: i32) -> Self {
+        self.api_level = api_level;
+        self
+    }
+
+    /// Adds a fake removable storage volume, such as SD card.
+    ///
+    /// # Args
+    /// - ***uid*** :  
+    ///   ID of the volume, e.g. `1234-ABCD`.  
+    ///
+    /// - ***description*** :  
+    ///   User-visible description of the volume, e.g. `SD card`.  
+    pub fn with_removable_volume(mut self, uid: impl Into<String>, description: impl Into<String>) -> Self {
+        self.volumes.push(Volume {
+            uid: uid.into(),
+            description: description.into(),
+            is_primary: false,
+        });
+        self
+    }
+
+    /// Gets the sandbox directory.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Builds a fake URI of the directory, like one obtained from the directory picker.
+    /// This does not check whether the directory exists.
+    ///
+    /// # Args
+    /// - ***volume_uid*** :  
+    ///   ID of the storage volume. If `None`, the primary storage volume is used.  
+    ///
+    /// - ***relative_path*** :  
+    ///   Relative path of the directory from the top directory of the storage volume.  
+    pub fn tree_uri(&self, volume_uid: Option<&str>, relative_path: impl AsRef<Path>) -> FileUri {
+        let document_id = build_document_id(volume_uid, relative_path.as_ref());
+        let tree = format!("{EXTERNAL_STORAGE_PROVIDER}/tree/{}", encode_android_uri_component(&document_id));
+
+        FileUri {
+            uri: format!("{tree}/document/{}", encode_android_uri_component(&document_id)),
+            document_top_tree_uri: Some(tree),
+        }
+    }
+
+    /// Builds a fake URI of the file or directory, like one obtained from the file picker.
+    /// This does not check whether the entry exists.
+    ///
+    /// # Args
+    /// - ***volume_uid*** :  
+    ///   ID of the storage volume. If `None`, the primary storage volume is used.  
+    ///
+    /// - ***relative_path*** :  
+    ///   Relative path of the entry from the top directory of the storage volume.  
+    pub fn document_uri(&self, volume_uid: Option<&str>, relative_path: impl AsRef<Path>) -> FileUri {
+        let document_id = build_document_id(volume_uid, relative_path.as_ref());
+        FileUri::from_uri(format!("{EXTERNAL_STORAGE_PROVIDER}/document/{}", encode_android_uri_component(&document_id)))
+    }
+
+    /// Adds the URIs that will be returned by the next file or directory picker.
+    /// For pickers that return a single URI, the first one is used.
+    pub fn push_picker_result(&self, uris: impl IntoIterator<Item = FileUri>) {
+        self.lock_state().picker_results.push_back(uris.into_iter().collect());
+    }
+
+    /// Resolves the path in the sandbox directory that the URI points to.
+    pub fn resolve_path(&self, uri: &FileUri) -> Result<PathBuf> {
+        if let Some(path) = uri.to_path() {
+            return Ok(path)
+        }
+        if let Some(rest) = uri.uri.strip_prefix(EXTERNAL_STORAGE_PROVIDER) {
+            let document_id = parse_document_id(rest)
+                .ok_or_else(|| Error::with(format!("invalid document URI: {}", uri.uri)))?;
+
+            let (uid, relative_path) = document_id.split_once(':').unwrap_or((&document_id, ""));
+            let volume = self.volumes.iter()
+                .find(|v| v.uid == uid)
+                .ok_or_else(|| Error::with(format!("storage volume not found: {uid}")))?;
+
+            let mut path = self.volume_top_dir(volume);
+            if !relative_path.is_empty() {
+                path.push(validate_relative_path(Path::new(relative_path))?);
+            }
+            return Ok(path)
+        }
+        if let Some(rest) = uri.uri.strip_prefix(MEDIA_STORE_PROVIDER) {
+            let id = rest.rsplit('/').next()
+                .and_then(|id| id.parse::<u64>().ok())
+                .ok_or_else(|| Error::with(format!("invalid MediaStore URI: {}", uri.uri)))?;
+
+            return self.lock_state().media_store.get(&id)
+                .map(|e| e.path.clone())
+                .ok_or_else(|| Error::with(format!("MediaStore entry not found: {}", uri.uri)))
+        }
+
+        Err(Error::with(format!("unsupported URI: {}", uri.uri)))
+    }
+
+    fn lock_state(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn private_dir_path(&self, dir: PrivateDir) -> PathBuf {
+        let name = match dir {
+            PrivateDir::Data => "data",
+            PrivateDir::Cache => "cache",
+            PrivateDir::NoBackupData => "no_backup_data",
+        };
+        self.root.join("private").join(name)
+    }
+
+    fn volume_top_dir(&self, volume: &Volume) -> PathBuf {
+        match volume.is_primary {
+            true => self.root.join("storage").join("emulated").join("0"),
+            false => self.root.join("storage").join(&volume.uid),
+        }
+    }
+
+    fn media_store_volume_name(&self, volume: &Volume) -> Option<String> {
+        if self.api_level < api_level::ANDROID_10 {
+            return None
+        }
+        match volume.is_primary {
+            true => Some(PRIMARY_MEDIA_STORE_VOLUME_NAME.to_string()),
+            false => Some(volume.uid.to_lowercase()),
+        }
+    }
+
+    /// 同じ provider で path を指す URI を作成する。
+    fn build_uri(&self, base: &FileUri, path: &Path) -> Result<FileUri> {
+        if base.is_file_scheme() {
+            return Ok(FileUri::from_path(path))
+        }
+        if base.uri.starts_with(EXTERNAL_STORAGE_PROVIDER) {
+            let (volume, relative_path) = self.volumes.iter()
+                .find_map(|v| path.strip_prefix(self.volume_top_dir(v)).ok().map(|p| (v, p)))
+                .ok_or_else(|| Error::with(format!("out of storage volumes: {path:?}")))?;
+
+            let document_id = build_document_id(Some(&volume.uid), relative_path);
+            let document_id = encode_android_uri_component(&document_id);
+
+            return Ok(match &base.document_top_tree_uri {
+                Some(tree) => FileUri {
+                    uri: format!("{tree}/document/{document_id}"),
+                    document_top_tree_uri: Some(tree.clone()),
+                },
+                None => FileUri::from_uri(format!("{EXTERNAL_STORAGE_PROVIDER}/document/{document_id}")),
+            })
+        }
+
+        Err(Error::with(format!("unsupported URI: {}", base.uri)))
+    }
+
+    fn entry_json(
+        &self,
+        uri: Option<FileUri>,
+        path: &Path,
+        name: bool,
+        last_modified: bool,
+        len: bool,
+    ) -> Result<Value> {
+
+        let metadata = std::fs::metadata(path)?;
+        let mut obj = serde_json::Map::new();
+
+        if let Some(uri) = uri {
+            obj.insert("uri".into(), serde_json::to_value(uri)?);
+        }
+        if name {
+            obj.insert("name".into(), json!(file_name(path)));
+        }
+        if last_modified {
+            let millis = metadata.modified().ok()
+                .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                .map(|d| d.as_millis() as i64)
+                .unwrap_or(0);
+
+            obj.insert("lastModified".into(), json!(millis));
+        }
+        if metadata.is_file() {
+            obj.insert("mimeType".into(), json!(mime_type_from_name(&file_name(path))));
+            if len {
+                obj.insert("len".into(), json!(metadata.len()));
+            }
+        }
+
+        Ok(Value::Object(obj))
+    }
+
+    fn open_fd(&self, uri: &FileUri, mode: &str) -> Result<std::os::fd::RawFd> {
+        use std::os::fd::IntoRawFd;
+
+        let path = self.resolve_path(uri)?;
+        let mut options = std::fs::OpenOptions::new();
+        match mode {
+            "r" => options.read(true),
+            "w" => options.write(true),
+            "wt" => options.write(true).truncate(true),
+            "wa" => options.append(true),
+            "rw" => options.read(true).write(true),
+            "rwt" => options.read(true).write(true).truncate(true),
+            _ => return Err(Error::with(format!("unsupported mode: {mode}")))
+        };
+        // file:// の場合は書き込み可能なモードでファイルが作成される。
+        if uri.is_file_scheme() && mode != "r" {
+            options.create(true);
+        }
+
+        if path.is_dir() {
+            return Err(Error::with(format!("not a file: {}", uri.uri)))
+        }
+
+        Ok(options.open(path)?.into_raw_fd())
+    }
+
+    fn create_file(&self, dir: &FileUri, relative_path: &str) -> Result<(FileUri, PathBuf)> {
+        let dir_path = self.resolve_path(dir)?;
+        let relative_path = validate_relative_path(Path::new(relative_path))?;
+        let path = unique_path(&dir_path.join(relative_path));
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::OpenOptions::new().write(true).create_new(true).open(&path)?;
+
+        let uri = self.build_uri(dir, &path)?;
+        let relative_path = path.strip_prefix(&dir_path).unwrap_or(&path).to_path_buf();
+        Ok((uri, relative_path))
+    }
+
+    fn create_dir_all(&self, dir: &FileUri, relative_path: &str) -> Result<FileUri> {
+        let path = self.resolve_path(dir)?.join(validate_relative_path(Path::new(relative_path))?);
+        std::fs::create_dir_all(&path)?;
+        self.build_uri(dir, &path)
+    }
+
+    fn create_new_media_store_file(
+        &self,
+        volume_name: Option<&str>,
+        relative_path: &Path,
+        mime_type: Option<&str>,
+    ) -> Result<FileUri> {
+
+        let volume = self.volumes.iter()
+            .find(|v| match volume_name {
+                Some(name) => self.media_store_volume_name(v).as_deref() == Some(name),
+                None => v.is_primary,
+            })
+            .ok_or_else(|| Error::with(format!("MediaStore volume not found: {volume_name:?}")))?;
+
+        let path = unique_path(&self.volume_top_dir(volume).join(validate_relative_path(relative_path)?));
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::OpenOptions::new().write(true).create_new(true).open(&path)?;
+
+        let mime_type = mime_type
+            .map(|m| m.to_string())
+            .unwrap_or_else(|| mime_type_from_name(&file_name(&path)));
+
+        Ok(self.insert_media_store_entry(volume, path, &mime_type))
+    }
+
+    fn insert_media_store_entry(&self, volume: &Volume, path: PathBuf, mime_type: &str) -> FileUri {
+        let collection = match mime_type.split_once('/').map(|(t, _)| t) {
+            Some("image") => "images/media",
+            Some("video") => "video/media",
+            Some("audio") => "audio/media",
+            _ => "file",
+        };
+        let volume_name = self.media_store_volume_name(volume)
+            .unwrap_or_else(|| "external".to_string());
+
+        let mut state = self.lock_state();
+        state.next_media_store_id += 1;
+        let id = state.next_media_store_id;
+        state.media_store.insert(id, MediaStoreEntry { path });
+
+        FileUri::from_uri(format!("{MEDIA_STORE_PROVIDER}/{volume_name}/{collection}/{id}"))
+    }
+
+    fn volume_json(&self, volume: &Volume) -> Value {
+        let top = self.volume_top_dir(volume);
+        let app_data = top.join("Android").join("data").join(APP_ID);
+        let app_media = top.join("Android").join("media").join(APP_ID);
+        for dir in [&top, &app_data, &app_media] {
+            std::fs::create_dir_all(dir).ok();
+        }
+
+        json!({
+            "description": volume.description,
+            "isPrimary": volume.is_primary,
+            "isRemovable": !volume.is_primary,
+            "isStable": volume.is_primary,
+            "isEmulated": volume.is_primary,
+            "isReadonly": false,
+            "isAvailableForAppStorage": true,
+            "isAvailableForPublicStorage": true,
+            "id": {
+                "topDirPath": top,
+                "appDataDirPath": app_data.join("files"),
+                "appCacheDirPath": app_data.join("cache"),
+                "appMediaDirPath": app_media,
+                "uid": Some(&volume.uid).filter(|_| !volume.is_primary),
+                "mediaStoreVolumeName": self.media_store_volume_name(volume),
+                "storageUuid": Value::Null,
+            }
+        })
+    }
+
+    fn pop_picker_result(&self) -> Vec<FileUri> {
+        self.lock_state().picker_results.pop_front().unwrap_or_default()
+    }
+
+    fn is_persisted(&self, uri: &FileUri) -> bool {
+        self.lock_state().persisted_uris.iter().any(|p| {
+            p.uri == uri.uri || (p.document_top_tree_uri.is_some() && p.document_top_tree_uri == uri.document_top_tree_uri)
+        })
+    }
+}
+
+impl AndroidFsBackend for HostBackend {
+
+    fn invoke(&self, command: &str, payload: Value) -> Result<Value> {
+        match command {
+            "getConsts" => Ok(json!({
+                "buildVersionSdkInt": self.api_level,
+                "envDirPictures": "Pictures",
+                "envDirDcim": "DCIM",
+                "envDirMovies": "Movies",
+                "envDirMusic": "Music",
+                "envDirAlarms": "Alarms",
+                "envDirNotifications": "Notifications",
+                "envDirPodcasts": "Podcasts",
+                "envDirRingtones": "Ringtones",
+                "envDirDocuments": "Documents",
+                "envDirDownload": "Download",
+                "envDirRecordings": Some("Recordings").filter(|_| api_level::ANDROID_12 <= self.api_level),
+                "envDirAudiobooks": Some("Audiobooks").filter(|_| api_level::ANDROID_10 <= self.api_level),
+                "mediaStorePrimaryVolumeName": Some(PRIMARY_MEDIA_STORE_VOLUME_NAME).filter(|_| api_level::ANDROID_10 <= self.api_level),
+            })),
+            "getPrivateBaseDirAbsolutePaths" => {
+                let paths = [PrivateDir::Data, PrivateDir::Cache, PrivateDir::NoBackupData]
+                    .map(|dir| self.private_dir_path(dir));
+
+                for path in &paths {
+                    std::fs::create_dir_all(path)?;
+                }
+
+                let [data, cache, no_backup_data] = paths;
+                Ok(json!({ "data": data, "cache": cache, "noBackupData": no_backup_data }))
+            },
+            "isLegacyStorage" => Ok(json!({ "value": self.api_level <= api_level::ANDROID_9 })),
+            "requestLegacyStoragePermission" => Ok(json!({ "granted": true, "prompted": false })),
+            "checkLegacyStoragePermission" => Ok(json!({ "granted": true })),
+            "requestNotificationPermission" => Ok(json!({ "granted": true, "prompted": false })),
+            "hasNotificationPermission" => Ok(json!({ "granted": true })),
+            "startProgressNotification" => {
+                let mut state = self.lock_state();
+                state.next_notification_id += 1;
+                Ok(json!({ "id": state.next_notification_id }))
+            },
+            "updateProgressNotification"
+            | "finishProgressNotification"
+            | "cancelNotification"
+            | "cancelAllNotifications" => Ok(Value::Null),
+            "getMimeTypeFromExtension" => {
+                #[derive(serde::Deserialize)]
+                struct Args { extension: String }
+
+                let args = parse_args::<Args>(payload)?;
+                let mime_type = mime_type_from_extension(&args.extension);
+                Ok(json!({ "mimeType": mime_type }))
+            },
+            "getName" => {
+                let path = self.resolve_path(&parse_uri_arg(payload)?)?;
+                Ok(json!({ "name": file_name(&path) }))
+            },
+            "getMimeType" => {
+                let path = self.resolve_path(&parse_uri_arg(payload)?)?;
+                let metadata = std::fs::metadata(&path)?;
+                let value = Some(mime_type_from_name(&file_name(&path))).filter(|_| metadata.is_file());
+                Ok(json!({ "value": value }))
+            },
+            "getLen" => {
+                let uri = parse_uri_arg(payload)?;
+                let metadata = std::fs::metadata(self.resolve_path(&uri)?)?;
+                if !metadata.is_file() {
+                    return Err(Error::with(format!("This is not a file: {}", uri.uri)))
+                }
+                Ok(json!({ "len": metadata.len() }))
+            },
+            "getMetadata" => {
+                let uri = parse_uri_arg(payload)?;
+                let path = self.resolve_path(&uri)?;
+                self.entry_json(Some(uri), &path, true, true, true)
+            },
+            "readDir" => {
+                #[derive(serde::Deserialize)]
+                #[serde(rename_all = "camelCase")]
+                struct Args { uri: FileUri, options: Options, offset: Option<String>, limit: Option<String> }
+                #[derive(serde::Deserialize)]
+                #[serde(rename_all = "camelCase")]
+                struct Options { uri: bool, name: bool, last_modified: bool, len: bool }
+
+                let args = parse_args::<Args>(payload)?;
+                let offset = args.offset.as_deref().map(str::parse::<u64>).transpose()?.unwrap_or(0);
+                let limit = args.limit.as_deref().map(str::parse::<u64>).transpose()?;
+                let dir = self.resolve_path(&args.uri)?;
+
+                // 実際の file provider では順序は不定だが、ここではテストのために名前順にする。
+                let mut paths = std::fs::read_dir(&dir)?
+                    .map(|e| e.map(|e| e.path()))
+                    .collect::<std::io::Result<Vec<_>>>()?;
+                paths.sort();
+
+                let entries = paths.into_iter()
+                    .skip(usize::try_from(offset).unwrap_or(usize::MAX))
+                    .take(limit.and_then(|l| usize::try_from(l).ok()).unwrap_or(usize::MAX))
+                    .map(|path| {
+                        let uri = match args.options.uri {
+                            true => Some(self.build_uri(&args.uri, &path)?),
+                            false => None,
+                        };
+                        self.entry_json(uri, &path, args.options.name, args.options.last_modified, args.options.len)
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+
+                Ok(json!({ "entries": entries }))
+            },
+            "createFile" => {
+                #[derive(serde::Deserialize)]
+                #[serde(rename_all = "camelCase")]
+                struct Args { dir: FileUri, relative_path: String }
+
+                let args = parse_args::<Args>(payload)?;
+                let (uri, _) = self.create_file(&args.dir, &args.relative_path)?;
+                Ok(serde_json::to_value(uri)?)
+            },
+            "createFileAndReturnRelativePath" => {
+                #[derive(serde::Deserialize)]
+                #[serde(rename_all = "camelCase")]
+                struct Args { dir: FileUri, relative_path: String }
+
+                let args = parse_args::<Args>(payload)?;
+                let (uri, relative_path) = self.create_file(&args.dir, &args.relative_path)?;
+                Ok(json!({ "uri": uri, "relativePath": relative_path }))
+            },
+            "createDirAll" => {
+                #[derive(serde::Deserialize)]
+                #[serde(rename_all = "camelCase")]
+                struct Args { dir: FileUri, relative_path: String }
+
+                let args = parse_args::<Args>(payload)?;
+                let uri = self.create_dir_all(&args.dir, &args.relative_path)?;
+                Ok(serde_json::to_value(uri)?)
+            },
+            "createDirAllAndReturnRelativePath" => {
+                #[derive(serde::Deserialize)]
+                #[serde(rename_all = "camelCase")]
+                struct Args { dir: FileUri, relative_path: String }
+
+                let args = parse_args::<Args>(payload)?;
+                let uri = self.create_dir_all(&args.dir, &args.relative_path)?;
+                Ok(json!({ "uri": uri, "relativePath": args.relative_path }))
+            },
+            "deleteFile" => {
+                let uri = parse_uri_arg(payload)?;
+                let path = self.resolve_path(&uri)?;
+                if !path.is_file() {
+                    return Err(Error::with(format!("This is not file: {}", uri.uri)))
+                }
+                std::fs::remove_file(&path)?;
+                self.lock_state().media_store.retain(|_, e| e.path != path);
+                Ok(Value::Null)
+            },
+            "deleteEmptyDir" => {
+                let uri = parse_uri_arg(payload)?;
+                std::fs::remove_dir(self.resolve_path(&uri)?)?;
+                Ok(Value::Null)
+            },
+            "deleteDirAll" => {
+                let uri = parse_uri_arg(payload)?;
+                let path = self.resolve_path(&uri)?;
+                if !path.is_dir() {
+                    return Err(Error::with(format!("This is not dir: {}", uri.uri)))
+                }
+                std::fs::remove_dir_all(&path)?;
+                self.lock_state().media_store.retain(|_, e| !e.path.starts_with(&path));
+                Ok(Value::Null)
+            },
+            "rename" => {
+                #[derive(serde::Deserialize)]
+                #[serde(rename_all = "camelCase")]
+                struct Args { uri: FileUri, new_name: String }
+
+                let args = parse_args::<Args>(payload)?;
+                let path = self.resolve_path(&args.uri)?;
+                let parent = path.parent().ok_or_else(|| Error::with("cannot rename root"))?;
+                let new_path = unique_path(&parent.join(validate_relative_path(Path::new(&args.new_name))?));
+                std::fs::rename(&path, &new_path)?;
+
+                if let Some(e) = self.lock_state().media_store.values_mut().find(|e| e.path == path) {
+                    e.path = new_path;
+                    return Ok(serde_json::to_value(args.uri)?)
+                }
+                Ok(serde_json::to_value(self.build_uri(&args.uri, &new_path)?)?)
+            },
+            "moveEntry" => {
+                #[derive(serde::Deserialize)]
+                #[serde(rename_all = "camelCase")]
+                struct Args { uri: FileUri, dest_dir: FileUri }
+
+                let args = parse_args::<Args>(payload)?;
+
+                // 実機と同様に、同じ provider 内でのみネイティブに移動できる
+                let is_same_provider = (args.uri.is_file_scheme() && args.dest_dir.is_file_scheme())
+                    || (args.uri.uri.starts_with(EXTERNAL_STORAGE_PROVIDER) && args.dest_dir.uri.starts_with(EXTERNAL_STORAGE_PROVIDER));
+                if !is_same_provider {
+                    return Ok(json!({ "uri": null }))
+                }
+
+                let path = self.resolve_path(&args.uri)?;
+                let new_path = self.resolve_path(&args.dest_dir)?.join(file_name(&path));
+                if new_path.exists() {
+                    return Err(Error::with(format!("File already exists: {new_path:?}")))
+                }
+                // 実機の File.renameTo と同様に、異なるファイルシステム間などで失敗した場合は移動できないものとする
+                if std::fs::rename(&path, &new_path).is_err() {
+                    return Ok(json!({ "uri": null }))
+                }
+                Ok(json!({ "uri": self.build_uri(&args.dest_dir, &new_path)? }))
+            },
+            "findSafFileUri" | "findSafDirUri" => {
+                #[derive(serde::Deserialize)]
+                #[serde(rename_all = "camelCase")]
+                struct Args { parent_uri: FileUri, relative_path: PathBuf }
+
+                let args = parse_args::<Args>(payload)?;
+                let path = self.resolve_path(&args.parent_uri)?.join(validate_relative_path(&args.relative_path)?);
+                let is_expected_type = match command {
+                    "findSafFileUri" => path.is_file(),
+                    _ => path.is_dir(),
+                };
+                if !is_expected_type {
+                    return Err(Error::with(format!("entry not found: {:?}", args.relative_path)))
+                }
+                Ok(serde_json::to_value(self.build_uri(&args.parent_uri, &path)?)?)
+            },
+            "getFileDescriptor" => {
+                #[derive(serde::Deserialize)]
+                struct Args { uri: FileUri, mode: String }
+
+                let args = parse_args::<Args>(payload)?;
+                Ok(json!({ "fd": self.open_fd(&args.uri, &args.mode)? }))
+            },
+            "getFileDescriptorWithFallback" => {
+                #[derive(serde::Deserialize)]
+                struct Args { uri: FileUri, modes: Vec<String> }
+
+                let args = parse_args::<Args>(payload)?;
+                let mut last_err = Error::with("modes must not be empty");
+                for mode in args.modes {
+                    match self.open_fd(&args.uri, &mode) {
+                        Ok(fd) => return Ok(json!({ "fd": fd, "mode": mode })),
+                        Err(e) => last_err = e,
+                    }
+                }
+                Err(last_err)
+            },
+            "getFileResourceForContentProtocol" => {
+                let uri = parse_uri_arg(payload)?;
+                let path = self.resolve_path(&uri)?;
+                let len = std::fs::metadata(&path).ok().map(|m| m.len());
+                let mime_type = mime_type_from_name(&file_name(&path));
+                Ok(json!({ "fd": self.open_fd(&uri, "r")?, "len": len, "mimeType": mime_type }))
+            },
+            "getThumbnail" => Ok(json!({ "bytes": Value::Null })),
+            "getThumbnailToFile" => Ok(json!({ "value": false })),
+            "getAvailableStorageVolumes" => {
+                let volumes = self.volumes.iter().map(|v| self.volume_json(v)).collect::<Vec<_>>();
+                Ok(json!({ "volumes": volumes }))
+            },
+            "getPrimaryStorageVolumeIfAvailable" => {
+                let volume = self.volumes.iter().find(|v| v.is_primary).map(|v| self.volume_json(v));
+                Ok(json!({ "volume": volume }))
+            },
+            "checkStorageVolumeAvailableByPath" => {
+                #[derive(serde::Deserialize)]
+                struct Args { path: PathBuf }
+
+                let args = parse_args::<Args>(payload)?;
+                let value = self.volumes.iter().any(|v| args.path.starts_with(self.volume_top_dir(v)));
+                Ok(json!({ "value": value }))
+            },
+            "checkMediaStoreVolumeNameAvailable" => {
+                #[derive(serde::Deserialize)]
+                #[serde(rename_all = "camelCase")]
+                struct Args { media_store_volume_name: String }
+
+                let args = parse_args::<Args>(payload)?;
+                let value = self.volumes.iter()
+                    .any(|v| self.media_store_volume_name(v).as_deref() == Some(&args.media_store_volume_name));
+                Ok(json!({ "value": value }))
+            },
+            "createNewMediaStoreFile" => {
+                #[derive(serde::Deserialize)]
+                #[serde(rename_all = "camelCase")]
+                struct Args { volume_name: Option<String>, relative_path: PathBuf, mime_type: Option<String> }
+
+                let args = parse_args::<Args>(payload)?;
+                let uri = self.create_new_media_store_file(
+                    args.volume_name.as_deref(),
+                    &args.relative_path,
+                    args.mime_type.as_deref()
+                )?;
+                Ok(json!({ "uri": uri }))
+            },
+            "setMediaStoreFilePending" | "scanMediaStoreFile" | "scanMediaStoreFileForResult" => {
+                self.resolve_path(&parse_uri_arg(payload)?)?;
+                Ok(Value::Null)
+            },
+            "scanFileToMediaStoreByPath" => {
+                #[derive(serde::Deserialize)]
+                #[serde(rename_all = "camelCase")]
+                struct Args { path: PathBuf, mime_type: Option<String> }
+
+                let args = parse_args::<Args>(payload)?;
+                let volume = self.volumes.iter()
+                    .find(|v| args.path.starts_with(self.volume_top_dir(v)))
+                    .ok_or_else(|| Error::with(format!("out of storage volumes: {:?}", args.path)))?;
+
+                let mime_type = args.mime_type.unwrap_or_else(|| mime_type_from_name(&file_name(&args.path)));
+                let uri = self.insert_media_store_entry(volume, args.path, &mime_type);
+                Ok(json!({ "uri": uri }))
+            },
+            "getMediaStoreFileAbsolutePath" => {
+                let path = self.resolve_path(&parse_uri_arg(payload)?)?;
+                Ok(json!({ "path": path }))
+            },
+            "showOpenFileDialog" | "showOpenVisualMediaDialog" | "showOpenContentDialog" => {
+                Ok(json!({ "uris": self.pop_picker_result() }))
+            },
+            "showManageDirDialog" | "showSaveFileDialog" => {
+                Ok(json!({ "uri": self.pop_picker_result().into_iter().next() }))
+            },
+            "isVisualMediaDialogAvailable" => Ok(json!({ "value": true })),
+            "shareFiles" | "viewFile" | "viewDir" | "editFile" => Ok(Value::Null),
+            "getPickerUriPermission" => Ok(json!({ "canRead": true, "canWrite": true })),
+            "getPersistedPickerUriPermission" => {
+                let persisted = self.is_persisted(&parse_uri_arg(payload)?);
+                Ok(json!({ "canRead": persisted, "canWrite": persisted }))
+            },
+            "persistPickerUriPermission" => {
+                let uri = parse_uri_arg(payload)?;
+                let mut state = self.lock_state();
+                if !state.persisted_uris.contains(&uri) {
+                    state.persisted_uris.push(uri);
+                }
+                Ok(Value::Null)
+            },
+            "releasePersistedPickerUriPermission" => {
+                let uri = parse_uri_arg(payload)?;
+                let mut state = self.lock_state();
+                let len = state.persisted_uris.len();
+                state.persisted_uris.retain(|u| u != &uri);
+                Ok(json!({ "isReleased": state.persisted_uris.len() != len }))
+            },
+            "releaseAllPersistedPickerUriPermissions" => {
+                self.lock_state().persisted_uris.clear();
+                Ok(Value::Null)
+            },
+            "getAllPersistedPickerUriPermissions" => {
+                let uris = self.lock_state().persisted_uris.clone();
+                let items = uris.into_iter()
+                    .map(|uri| {
+                        let d = self.resolve_path(&uri).map(|p| p.is_dir()).unwrap_or(false);
+                        json!({ "uri": uri, "r": true, "w": true, "d": d })
+                    })
+                    .collect::<Vec<_>>();
+
+                Ok(json!({ "items": items }))
+            },
+            _ => Err(Error::with(format!("unsupported command by HostBackend: {command}")))
+        }
+    }
+}
+
+
+fn parse_args<T: DeserializeOwned>(payload: Value) -> Result<T> {
+    serde_json::from_value(payload).map_err(Into::into)
+}
+
+fn parse_uri_arg(payload: Value) -> Result<FileUri> {
+    #[derive(serde::Deserialize)]
+    struct Args { uri: FileUri }
+
+    parse_args::<Args>(payload).map(|args| args.uri)
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+fn build_document_id(volume_uid: Option<&str>, relative_path: &Path) -> String {
+    let relative_path = relative_path.to_string_lossy();
+    format!("{}:{}", volume_uid.unwrap_or(PRIMARY_VOLUME_UID), relative_path.trim_matches('/'))
+}
+
+/// `/tree/{id}`, `/document/{id}`, `/tree/{id}/document/{id}`, `/root/{id}` から document ID を取得する。
+fn parse_document_id(path: &str) -> Option<String> {
+    let encoded = match path.rfind("/document/") {
+        Some(i) => &path[(i + "/document/".len())..],
+        None => path.strip_prefix("/tree/").or_else(|| path.strip_prefix("/root/"))?,
+    };
+    let encoded = encoded.split('/').next()?;
+
+    percent_encoding::percent_decode_str(encoded)
+        .decode_utf8()
+        .ok()
+        .map(|id| id.into_owned())
+}
+
+/// 同じ名前のエントリが既に存在する場合、連番を追加した path を返す。
+fn unique_path(path: &Path) -> PathBuf {
+    if !path.exists() {
+        return path.to_path_buf()
+    }
+
+    let stem = path.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default();
+    let ext = path.extension().map(|s| s.to_string_lossy().into_owned());
+
+    (1..)
+        .map(|i| match &ext {
+            Some(ext) => path.with_file_name(format!("{stem}({i}).{ext}")),
+            None => path.with_file_name(format!("{stem}({i})")),
+        })
+        .find(|p| !p.exists())
+        .expect("Should find unused name")
+}
+
+fn mime_type_from_name(name: &str) -> String {
+    name.rsplit_once('.')
+        .and_then(|(_, ext)| mime_type_from_extension(ext))
+        .unwrap_or_else(|| "application/octet-stream".to_string())
+}
+
+fn mime_type_from_extension(ext: &str) -> Option<String> {
+    let mime_type = match ext.trim_start_matches('.').to_lowercase().as_str() {
+        "txt" => "text/plain",
+        "html" | "htm" => "text/html",
+        "css" => "text/css",
+        "js" => "text/javascript",
+        "csv" => "text/csv",
+        "md" => "text/markdown",
+        "xml" => "text/xml",
+        "json" => "application/json",
+        "pdf" => "application/pdf",
+        "zip" => "application/zip",
+        "gz" => "application/gzip",
+        "apk" => "application/vnd.android.package-archive",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "bmp" => "image/bmp",
+        "svg" => "image/svg+xml",
+        "heic" => "image/heic",
+        "mp4" => "video/mp4",
+        "webm" => "video/webm",
+        "mkv" => "video/x-matroska",
+        "3gp" => "video/3gpp",
+        "mp3" => "audio/mpeg",
+        "m4a" => "audio/mp4",
+        "ogg" => "audio/ogg",
+        "wav" => "audio/x-wav",
+        "flac" => "audio/flac",
+        "aac" => "audio/aac",
+        _ => return None,
+    };
+    Some(mime_type.to_string())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_document_id() {
+        assert_eq!(parse_document_id("/tree/primary%3ADocs").as_deref(), Some("primary:Docs"));
+        assert_eq!(parse_document_id("/tree/primary%3ADocs%2Fa.txt").as_deref(), Some("primary:Docs/a.txt"));
+        assert_eq!(parse_document_id("/tree/primary%3ADocs/document/primary%3ADocs%2Fa").as_deref(), Some("primary:Docs/a"));
+        assert_eq!(parse_document_id("/document/1234-ABCD%3A").as_deref(), Some("1234-ABCD:"));
+        assert_eq!(parse_document_id("/root/primary").as_deref(), Some("primary"));
+        assert_eq!(parse_document_id("/unknown"), None);
+    }
+
+    #[test]
+    fn test_uri_round_trip() {
+        let backend = HostBackend::new("/sandbox").with_removable_volume("1234-ABCD", "SD card");
+        let top = PathBuf::from("/sandbox/storage/emulated/0");
+
+        let tree = backend.tree_uri(None, "Documents");
+        assert_eq!(tree.uri, "content://com.android.externalstorage.documents/tree/primary%3ADocuments/document/primary%3ADocuments");
+        assert_eq!(backend.resolve_path(&tree).unwrap(), top.join("Documents"));
+
+        let child = backend.build_uri(&tree, &top.join("Documents/a b.txt")).unwrap();
+        assert_eq!(child.document_top_tree_uri, tree.document_top_tree_uri);
+        assert_eq!(backend.resolve_path(&child).unwrap(), top.join("Documents/a b.txt"));
+
+        let sd = backend.document_uri(Some("1234-ABCD"), "Music");
+        assert_eq!(backend.resolve_path(&sd).unwrap(), PathBuf::from("/sandbox/storage/1234-ABCD/Music"));
+    }
+}
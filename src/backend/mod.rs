@@ -0,0 +1,51 @@
+//! Backends that can be used instead of the Kotlin side of this plugin.
+//!
+//! See [`crate::init_with_backend`].
+
+#[cfg(unix)]
+mod host;
+
+#[cfg(unix)]
+pub use host::*;
+
+mod replay;
+pub use replay::*;
+
+use crate::*;
+
+
+/// Backend that handles invocations from the Rust side of this plugin.
+///
+/// Every API of [`crate::api::api_sync`] and [`crate::api::api_async`] is implemented
+/// by invoking commands of the Kotlin side of this plugin,
+/// such as `readDir` or `getFileDescriptor`.
+/// Implementing this trait allows them to be handled by another implementation,
+/// e.g. [`HostBackend`] for running the APIs off-device.
+///
+/// # Note
+/// The commands and their payloads are the contract between the Rust and Kotlin sides of this plugin,
+/// and are not covered by semver.
+pub trait AndroidFsBackend: Send + Sync + 'static {
+
+    /// Handles the command.
+    ///
+    /// # Args
+    /// - ***command*** :  
+    ///   Name of the Kotlin command, e.g. `readDir`.  
+    ///
+    /// - ***payload*** :  
+    ///   Arguments of the command serialized as JSON.  
+    ///
+    /// # Returns
+    /// Result of the command serialized as JSON.
+    /// File descriptors are passed as raw fd numbers,
+    /// and the ownership of them is transferred to the caller.
+    fn invoke(&self, command: &str, payload: serde_json::Value) -> Result<serde_json::Value>;
+}
+
+impl<T: AndroidFsBackend> AndroidFsBackend for std::sync::Arc<T> {
+
+    fn invoke(&self, command: &str, payload: serde_json::Value) -> Result<serde_json::Value> {
+        (**self).invoke(command, payload)
+    }
+}
@@ -0,0 +1,302 @@
+use std::collections::VecDeque;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::*;
+use super::AndroidFsBackend;
+
+
+/// Invocation of a Kotlin command and its result.
+///
+/// # JSON
+///
+/// ```json
+/// {
+///     "command": "getName",
+///     "payload": { "uri": { "uri": "content://...", "documentTopTreeUri": null } },
+///     "ok": { "name": "a.txt" }
+/// }
+/// ```
+///
+/// If the command failed, this has `"err": "<message>"` instead of `"ok"`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Invocation {
+
+    /// Name of the Kotlin command, e.g. `readDir`.
+    pub command: String,
+
+    /// Arguments of the command serialized as JSON.
+    pub payload: Value,
+
+    /// Result of the command.
+    #[serde(flatten)]
+    pub result: InvocationResult,
+}
+
+/// Result of [`Invocation`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum InvocationResult {
+
+    /// Result of the command serialized as JSON.
+    Ok(Value),
+
+    /// Error message of the command.
+    Err(String),
+}
+
+impl Invocation {
+
+    pub fn ok(command: impl Into<String>, payload: Value, response: Value) -> Self {
+        Self { command: command.into(), payload, result: InvocationResult::Ok(response) }
+    }
+
+    pub fn err(command: impl Into<String>, payload: Value, message: impl Into<String>) -> Self {
+        Self { command: command.into(), payload, result: InvocationResult::Err(message.into()) }
+    }
+
+    /// Parses a transcript, which is a JSON array of [`Invocation`].
+    pub fn from_transcript_json(json: impl AsRef<str>) -> Result<Vec<Self>> {
+        serde_json::from_str(json.as_ref()).map_err(Into::into)
+    }
+
+    /// Serializes invocations as a transcript, which is a JSON array of [`Invocation`].
+    pub fn to_transcript_json(invocations: &[Self]) -> Result<String> {
+        serde_json::to_string_pretty(invocations).map_err(Into::into)
+    }
+}
+
+
+/// [`AndroidFsBackend`] that records every invocation passed to the inner backend.
+///
+/// The recorded invocations can be saved as a transcript
+/// and used later by [`ReplayBackend`] for golden tests.
+///
+/// # Examples
+/// ```no_run
+/// use tauri_plugin_android_fs::backend::{HostBackend, Invocation, RecordingBackend};
+///
+/// let backend = std::sync::Arc::new(RecordingBackend::new(HostBackend::new("/tmp/afs-sandbox")));
+///
+/// // Passes `backend.clone()` to `tauri_plugin_android_fs::init_with_backend` and calls APIs.
+///
+/// let transcript = Invocation::to_transcript_json(&backend.invocations()).unwrap();
+/// std::fs::write("transcript.json", transcript).unwrap();
+/// ```
+pub struct RecordingBackend<B: AndroidFsBackend> {
+    inner: B,
+    invocations: std::sync::Mutex<Vec<Invocation>>,
+}
+
+impl<B: AndroidFsBackend> RecordingBackend<B> {
+
+    pub fn new(inner: B) -> Self {
+        Self { inner, invocations: std::sync::Mutex::new(Vec::new()) }
+    }
+
+    pub fn inner(&self) -> &B {
+        &self.inner
+    }
+
+    /// Returns the invocations recorded so far, in order.
+    pub fn invocations(&self) -> Vec<Invocation> {
+        self.invocations.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Removes and returns the invocations recorded so far, in order.
+    pub fn take_invocations(&self) -> Vec<Invocation> {
+        std::mem::take(&mut *self.invocations.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+}
+
+impl<B: AndroidFsBackend> AndroidFsBackend for RecordingBackend<B> {
+
+    fn invoke(&self, command: &str, payload: Value) -> Result<Value> {
+        let result = self.inner.invoke(command, payload.clone());
+
+        self.invocations.lock().unwrap_or_else(|e| e.into_inner()).push(Invocation {
+            command: command.to_string(),
+            payload,
+            result: match &result {
+                Ok(value) => InvocationResult::Ok(value.clone()),
+                Err(err) => InvocationResult::Err(err.to_string()),
+            },
+        });
+
+        result
+    }
+}
+
+
+/// [`AndroidFsBackend`] that answers invocations from a scripted list of [`Invocation`].
+///
+/// Every invocation must match the next expected one exactly,
+/// including the command name and the whole payload.
+/// This is intended for golden tests of the contract between the Rust and Kotlin sides of this plugin,
+/// so that changes of the payload shape are detected without a device.
+///
+/// Since some APIs ignore errors of the backend,
+/// use [`ReplayBackend::finish`] at the end of the test to check that all went as expected.
+///
+/// # Note
+/// Responses containing file descriptors, such as `getFileDescriptor`,
+/// must have valid raw fd numbers whose ownership can be transferred to the caller.
+///
+/// # Examples
+/// ```no_run
+/// use tauri_plugin_android_fs::backend::ReplayBackend;
+/// use serde_json::json;
+///
+/// let backend = std::sync::Arc::new(ReplayBackend::new([])
+///     .expect(
+///         "getName",
+///         json!({ "uri": { "uri": "content://media/external/file/1", "documentTopTreeUri": null } }),
+///         json!({ "name": "a.txt" }),
+///     ));
+///
+/// // Passes `backend.clone()` to `tauri_plugin_android_fs::init_with_backend` and calls APIs.
+///
+/// backend.finish().unwrap();
+/// ```
+pub struct ReplayBackend {
+    state: std::sync::Mutex<ReplayState>,
+}
+
+struct ReplayState {
+    expected: VecDeque<Invocation>,
+    mismatches: Vec<String>,
+}
+
+impl ReplayBackend {
+
+    /// Creates a new backend that answers `invocations` in order.
+    pub fn new(invocations: impl IntoIterator<Item = Invocation>) -> Self {
+        Self {
+            state: std::sync::Mutex::new(ReplayState {
+                expected: invocations.into_iter().collect(),
+                mismatches: Vec::new(),
+            })
+        }
+    }
+
+    /// Creates a new backend from a transcript, which is a JSON array of [`Invocation`].
+    pub fn from_transcript_json(json: impl AsRef<str>) -> Result<Self> {
+        Ok(Self::new(Invocation::from_transcript_json(json)?))
+    }
+
+    /// Appends an invocation that succeeds with `response`.
+    pub fn expect(self, command: impl Into<String>, payload: Value, response: Value) -> Self {
+        self.push(Invocation::ok(command, payload, response))
+    }
+
+    /// Appends an invocation that fails with `message`.
+    pub fn expect_err(self, command: impl Into<String>, payload: Value, message: impl Into<String>) -> Self {
+        self.push(Invocation::err(command, payload, message))
+    }
+
+    /// Returns the number of expected invocations that have not been invoked yet.
+    pub fn remaining(&self) -> usize {
+        self.lock().expected.len()
+    }
+
+    /// Checks that every invocation matched and all expected invocations were consumed.
+    pub fn finish(&self) -> Result<()> {
+        let state = self.lock();
+
+        if let Some(mismatch) = state.mismatches.first() {
+            return Err(Error::with(mismatch.clone()))
+        }
+        if let Some(next) = state.expected.front() {
+            return Err(Error::with(format!(
+                "{} expected invocation(s) remain, next is `{}` with {}",
+                state.expected.len(),
+                next.command,
+                next.payload
+            )))
+        }
+
+        Ok(())
+    }
+
+    fn push(self, invocation: Invocation) -> Self {
+        self.lock().expected.push_back(invocation);
+        self
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, ReplayState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl AndroidFsBackend for ReplayBackend {
+
+    fn invoke(&self, command: &str, payload: Value) -> Result<Value> {
+        let mut state = self.lock();
+
+        let mismatch = match state.expected.pop_front() {
+            Some(next) if next.command == command && next.payload == payload => {
+                return match next.result {
+                    InvocationResult::Ok(value) => Ok(value),
+                    InvocationResult::Err(msg) => Err(Error::with(msg)),
+                }
+            },
+            Some(next) => format!(
+                "unexpected invocation: expected `{}` with {}, but got `{command}` with {payload}",
+                next.command,
+                next.payload,
+            ),
+            None => format!(
+                "unexpected invocation: no more invocations expected, but got `{command}` with {payload}"
+            ),
+        };
+
+        state.mismatches.push(mismatch.clone());
+        Err(Error::with(mismatch))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_transcript_round_trip() {
+        let invocations = vec![
+            Invocation::ok("getName", json!({ "uri": "a" }), json!({ "name": "a.txt" })),
+            Invocation::err("getLen", json!({ "uri": "a" }), "not found"),
+        ];
+
+        let json = Invocation::to_transcript_json(&invocations).unwrap();
+        assert_eq!(Invocation::from_transcript_json(&json).unwrap(), invocations);
+
+        let value: Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value[0]["ok"], json!({ "name": "a.txt" }));
+        assert_eq!(value[1]["err"], json!("not found"));
+    }
+
+    #[test]
+    fn test_replay_mismatch() {
+        let backend = ReplayBackend::new([])
+            .expect("getName", json!({ "uri": "a" }), json!({ "name": "a.txt" }))
+            .expect("getLen", json!({ "uri": "a" }), json!({ "len": 1 }));
+
+        assert_eq!(backend.invoke("getName", json!({ "uri": "a" })).unwrap(), json!({ "name": "a.txt" }));
+        assert!(backend.invoke("getLen", json!({ "uri": "b" })).is_err());
+        assert!(backend.invoke("getLen", json!({ "uri": "a" })).is_err());
+        assert!(backend.finish().is_err());
+    }
+
+    #[test]
+    fn test_record() {
+        let backend = RecordingBackend::new(ReplayBackend::new([])
+            .expect_err("getName", json!({ "uri": "a" }), "not found"));
+
+        assert!(backend.invoke("getName", json!({ "uri": "a" })).is_err());
+        assert_eq!(backend.take_invocations(), vec![
+            Invocation::err("getName", json!({ "uri": "a" }), "not found")
+        ]);
+        assert!(backend.invocations().is_empty());
+        backend.inner().finish().unwrap();
+    }
+}
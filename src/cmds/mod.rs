@@ -676,11 +676,14 @@ pub async fn open_read_file_stream<R: tauri::Runtime>(
                 let file = api.open_file_readable(&uri).await?;
     
                 tauri::async_runtime::spawn_blocking(move || {
-                    let res = FileChunkReader::new(file, None);
+                    let mut res = FileChunkReader::new(file, None);
+                    // パイプなどシークできないファイルでは開いた直後の位置を 0 とする
+                    let position = res.position().unwrap_or(0);
+                    let len = res.file_len();
                     let res: FileResource = std::sync::Mutex::new(res);
                     let id = resources.add(res)?;
 
-                    ReadFileStreamEventOutput::Open(id).try_into()
+                    ReadFileStreamEventOutput::Open { id, position, len }.try_into()
                 }).await?
             },
             ReadFileStreamEventInput::Read { id, len } => {
@@ -693,6 +696,26 @@ pub async fn open_read_file_stream<R: tauri::Runtime>(
                     ReadFileStreamEventOutput::Read(data).try_into()
                 }).await?
             },
+            ReadFileStreamEventInput::ReadAt { id, offset, len } => {
+                tauri::async_runtime::spawn_blocking(move || -> Result<_> {
+                    let data = resources
+                        .get::<FileResource>(id)?
+                        .lock()?
+                        .read_chunk_at(offset, len)?;
+                
+                    ReadFileStreamEventOutput::Read(data).try_into()
+                }).await?
+            },
+            ReadFileStreamEventInput::Seek { id, offset, whence } => {
+                tauri::async_runtime::spawn_blocking(move || -> Result<_> {
+                    let position = resources
+                        .get::<FileResource>(id)?
+                        .lock()?
+                        .seek(offset, whence)?;
+                
+                    ReadFileStreamEventOutput::Seek(position).try_into()
+                }).await?
+            },
             ReadFileStreamEventInput::Close { id } => {
                 tauri::async_runtime::spawn_blocking(move || {
                     resources.close(id)?;
@@ -721,7 +744,7 @@ pub async fn open_read_text_file_lines_stream<R: tauri::Runtime>(
         let resources = std::sync::Arc::clone(&resources);
 
         match event {
-            ReadTextFileLinesStreamEventInput::Open { uri, label, max_line_len, ignore_bom } => {
+            ReadTextFileLinesStreamEventInput::Open { uri, label, max_line_len, ignore_bom, follow_interval_ms, start_line } => {
                 let uri = uri.try_into_content_or_safe_file_scheme_uri()?;
                 if let Some(path) = uri.to_path() {
                     validate_path_permission(&path, &app, &cmd_scope, &global_scope)?;
@@ -730,6 +753,13 @@ pub async fn open_read_text_file_lines_stream<R: tauri::Runtime>(
                 let api = app.android_fs_async();
                 let file = api.open_file_readable(&uri).await?;
 
+                // 異なるエンコーディングで作成された索引は改行の位置が異なるので使わない
+                let index = match start_line {
+                    0 => None,
+                    _ => api.load_line_index(&uri).await?
+                        .filter(|index| encoding_for_label(&label).is_ok_and(|e| e.name() == index.encoding())),
+                };
+
                 tauri::async_runtime::spawn_blocking(move || {
                     let bom = match ignore_bom {
                         true => None,
@@ -738,7 +768,17 @@ pub async fn open_read_text_file_lines_stream<R: tauri::Runtime>(
                     let line_breaks = line_breaks_for_encoding_label(&label);
                     let max_line_len = std::num::NonZeroU64::new(max_line_len);
 
-                    let res = FileTextLinesReader::new(file, max_line_len, line_breaks, bom, None);
+                    let mut res = FileTextLinesReader::new(file, max_line_len, line_breaks, bom, None);
+                    res.seek_to_line(start_line, index.as_ref())?;
+                    if let Some(interval_ms) = follow_interval_ms {
+                        if interval_ms == 0 {
+                            return Err(Error::invalid_value("followIntervalMs: must not be zero"))
+                        }
+                        res.set_follow(LinesFollow {
+                            uri,
+                            interval: std::time::Duration::from_millis(interval_ms),
+                        });
+                    }
                     let res: FileReaderResource = std::sync::Mutex::new(res);
                     let id = resources.add(res)?;
 
@@ -746,16 +786,116 @@ pub async fn open_read_text_file_lines_stream<R: tauri::Runtime>(
                 }).await?
             }
             ReadTextFileLinesStreamEventInput::Read { id, len } => {
+                // 置き換えの検知にはファイルを開き直す必要があるため、行の追記を確認する間隔より頻度を抑える
+                const MIN_REOPEN_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
+
+                let res = resources.get::<FileReaderResource>(id)?;
+                let mut reopened_at = std::time::Instant::now();
+
+                loop {
+                    let r = std::sync::Arc::clone(&res);
+                    let (lines, follow) = tauri::async_runtime::spawn_blocking(move || -> Result<_> {
+                        let mut r = r.lock()?;
+                        let lines = r.read_lines_framed(len)?;
+                        let follow = r.follow().map(|f| (f.uri.clone(), f.interval));
+                        Ok((lines, follow))
+                    }).await??;
+
+                    // 空のデータは EOF を示すため、追従する場合は行が追記されるまで待つ
+                    let Some((uri, interval)) = follow.filter(|_| lines.is_empty()) else {
+                        return ReadTextFileLinesStreamEventOutput::Read(lines).try_into()
+                    };
+
+                    async_utils::sleep(interval).await;
+
+                    // 待っている間にストリームが閉じられた場合は EOF とする
+                    if resources.get::<FileReaderResource>(id).is_err() {
+                        return ReadTextFileLinesStreamEventOutput::Read(Vec::new()).try_into()
+                    }
+
+                    // ファイルが切り詰められた場合は先頭から読み直す
+                    let r = std::sync::Arc::clone(&res);
+                    let restarted = tauri::async_runtime::spawn_blocking(move || {
+                        r.lock()?.restart_if_truncated()
+                    }).await??;
+
+                    if restarted || reopened_at.elapsed() < interval.max(MIN_REOPEN_INTERVAL) {
+                        continue
+                    }
+                    reopened_at = std::time::Instant::now();
+
+                    // ファイルが置き換えられた場合は新しいファイルを先頭から読み直す
+                    let Ok(reopened) = app.android_fs_async().open_file_readable(&uri).await else {
+                        continue
+                    };
+                    let r = std::sync::Arc::clone(&res);
+                    tauri::async_runtime::spawn_blocking(move || {
+                        r.lock()?.restart_if_replaced(reopened)
+                    }).await??;
+                }
+            }
+            ReadTextFileLinesStreamEventInput::Close { id } => {
+                tauri::async_runtime::spawn_blocking(move || {
+                    resources.close(id)?;
+                    ReadTextFileLinesStreamEventOutput::Close(()).try_into()
+                }).await?
+            }
+        }  
+    }
+}
+
+#[tauri::command]
+pub async fn open_read_delimited_records_stream<R: tauri::Runtime>(
+    event: ReadDelimitedRecordsStreamEventInput,
+    app: tauri::AppHandle<R>,
+    cmd_scope: tauri::ipc::CommandScope<AfsScope>,
+    global_scope: tauri::ipc::GlobalScope<AfsScope>,
+    resources: FileStreamResourcesState<'_, R>,
+) -> Result<tauri::ipc::Response> {
+
+    #[cfg(not(target_os = "android"))] {
+        Err(Error::NOT_ANDROID)
+    }
+    #[cfg(target_os = "android")] {
+        type FileReaderResource = std::sync::Mutex<FileDelimitedRecordsReader>;
+
+        let resources = std::sync::Arc::clone(&resources);
+
+        match event {
+            ReadDelimitedRecordsStreamEventInput::Open { uri, label, delimiter, csv, max_record_len, ignore_bom } => {
+                let uri = uri.try_into_content_or_safe_file_scheme_uri()?;
+                if let Some(path) = uri.to_path() {
+                    validate_path_permission(&path, &app, &cmd_scope, &global_scope)?;
+                }
+
+                let api = app.android_fs_async();
+                let file = api.open_file_readable(&uri).await?;
+
+                tauri::async_runtime::spawn_blocking(move || {
+                    let bom = match ignore_bom {
+                        true => None,
+                        false => bom_for_encoding_label(&label)
+                    };
+                    let max_record_len = std::num::NonZeroU64::new(max_record_len);
+
+                    let res = FileDelimitedRecordsReader::new(file, &label, delimiter, csv, max_record_len, bom)?;
+                    let res: FileReaderResource = std::sync::Mutex::new(res);
+                    let id = resources.add(res)?;
+
+                    ReadTextFileLinesStreamEventOutput::Open(id).try_into()
+                }).await?
+            }
+            ReadDelimitedRecordsStreamEventInput::Read { id, len } => {
                 tauri::async_runtime::spawn_blocking(move || -> Result<_> {
-                    let lines = resources
+                    let records = resources
                         .get::<FileReaderResource>(id)?
                         .lock()?
-                        .read_lines_framed(len)?;
+                        .read_records_framed(len)?;
                  
-                    ReadTextFileLinesStreamEventOutput::Read(lines).try_into()
+                    ReadTextFileLinesStreamEventOutput::Read(records).try_into()
                 }).await?
             }
-            ReadTextFileLinesStreamEventInput::Close { id } => {
+            ReadDelimitedRecordsStreamEventInput::Close { id } => {
                 tauri::async_runtime::spawn_blocking(move || {
                     resources.close(id)?;
                     ReadTextFileLinesStreamEventOutput::Close(()).try_into()
@@ -774,14 +914,15 @@ async fn write_file_stream<R: tauri::Runtime, K: Send + Sync + 'static>(
     resources: PluginResourcesState<'_, R, K>,
 ) -> Result<WriteFileStreamEventOutput> {
 
-    use std::io::Write as _;
     use crate::api::api_async::ProgressNotificationGuard as ProgressNotificationGuard;
 
     type FileResource<R> = std::sync::Mutex<FileResourceInner<R>>;
     
     struct FileResourceInner<R: tauri::Runtime> {
-        file: std::fs::File,
+        writer: FileChunkWriter,
+        atomic: Option<AtomicWrite>,
         noti: Option<std::sync::Arc<Noti<R>>>,
+        text_encoder: Option<TextEncoder>,
     }
 
     struct Noti<R: tauri::Runtime> {
@@ -809,7 +950,28 @@ async fn write_file_stream<R: tauri::Runtime, K: Send + Sync + 'static>(
             }
 
             let api = app.android_fs_async();
-            let file = api.open_file_writable(&uri).await?;
+            let (file, atomic) = match (options.atomic, options.append) {
+                (true, true) => return Err(Error::with("atomic and append options cannot be used together")),
+                (true, false) => api.impls().open_file_writable_atomically(&uri).await.map(|(file, atomic)| (file, Some(atomic)))?,
+                (false, true) => (api.impls().open_file_appendable(&uri).await?, None),
+                (false, false) => (api.open_file_writable(&uri).await?, None),
+            };
+
+            let text_encoder = match options.has_text_options() {
+                true => Some(TextEncoder::new(options.encoding.as_deref(), options.line_ending)?),
+                false => None,
+            };
+
+            // BOM はデータより前に一度だけ書き込む
+            let bom = match text_encoder.as_ref().filter(|_| options.bom) {
+                Some(encoder) => {
+                    let bom = encoder.bom()?;
+                    // 既存のテキストに追記する場合は書き込まない
+                    let is_continuation = options.append && 0 < file.metadata()?.len();
+                    (!is_continuation).then_some(bom)
+                },
+                None => None,
+            };
 
             let use_noti = 
                 options.notification.is_some() &&
@@ -885,19 +1047,37 @@ async fn write_file_stream<R: tauri::Runtime, K: Send + Sync + 'static>(
             };
 
             tauri::async_runtime::spawn_blocking(move || {
-                let res = FileResourceInner { file, noti };
+                let mut writer = FileChunkWriter::new(file, options.append);
+                if let Some(bom) = bom {
+                    writer.write_chunk(bom)?;
+                    if let Some(noti) = &noti {
+                        noti.written.fetch_add(bom.len() as u64, std::sync::atomic::Ordering::SeqCst);
+                    }
+                }
+                let res = FileResourceInner { writer, atomic, noti, text_encoder };
                 let res: FileResource<R> = std::sync::Mutex::new(res);
                 let id = resources.add(res)?;
                 Ok(WriteFileStreamEventOutput::Open { id, supports_raw_ipc_request_body })
             }).await?
         },
-        WriteFileStreamEventInput::Write { id, data } => {
+        WriteFileStreamEventInput::Write { id, data, offset, text } => {
             tauri::async_runtime::spawn_blocking(move || {
-                let noti = {
+                let (data, noti) = {
                     let res = resources.get::<FileResource<R>>(id)?;
                     let mut locked_res = res.lock()?;
-                    locked_res.file.write_all(&data)?;
-                    locked_res.noti.as_ref().map(std::sync::Arc::clone)
+                    // エンコーダーは前の書き込みの状態を持つため、位置を指定して書き込めない
+                    if offset.is_some() && locked_res.text_encoder.is_some() {
+                        return Err(Error::with("cannot write at an offset to a stream writing encoded text"))
+                    }
+                    let data = match (text, locked_res.text_encoder.as_mut()) {
+                        (true, Some(encoder)) => encoder.encode(&data)?,
+                        _ => data,
+                    };
+                    match offset {
+                        Some(offset) => locked_res.writer.write_chunk_at(offset, &data)?,
+                        None => locked_res.writer.write_chunk(&data)?,
+                    }
+                    (data, locked_res.noti.as_ref().map(std::sync::Arc::clone))
                 };
 
                 if let Some(noti) = noti {
@@ -933,10 +1113,65 @@ async fn write_file_stream<R: tauri::Runtime, K: Send + Sync + 'static>(
                 Ok(WriteFileStreamEventOutput::Write(()))
             }).await?
         },
+        WriteFileStreamEventInput::Seek { id, offset, whence } => {
+            tauri::async_runtime::spawn_blocking(move || {
+                let res = resources.get::<FileResource<R>>(id)?;
+                let mut res = res.lock()?;
+                // エンコーダーが保留している出力が移動先に書き込まれてしまうため、シークできない
+                if res.text_encoder.is_some() {
+                    return Err(Error::with("cannot seek a stream writing encoded text"))
+                }
+                let position = res.writer.seek(offset, whence)?;
+
+                Ok(WriteFileStreamEventOutput::Seek(position))
+            }).await?
+        },
+        WriteFileStreamEventInput::SetLen { id, len } => {
+            tauri::async_runtime::spawn_blocking(move || {
+                resources
+                    .get::<FileResource<R>>(id)?
+                    .lock()?
+                    .writer
+                    .set_len(len)?;
+
+                Ok(WriteFileStreamEventOutput::SetLen(()))
+            }).await?
+        },
         WriteFileStreamEventInput::Close { id, error } => {
             tauri::async_runtime::spawn_blocking(move || {
                 if let Ok(res) = resources.take::<FileResource<R>>(id) {
                     let mut res = res.lock()?;
+                    let mut flush_result = Ok(());
+                    if let Some(mut encoder) = res.text_encoder.take() {
+                        // 保留中の CR やエンコーダーの状態を戻すためのエスケープシーケンスを書き込む
+                        if !error {
+                            let res = &mut *res;
+                            flush_result = encoder.finish().and_then(|data| {
+                                res.writer.write_chunk(&data)?;
+                                if let Some(noti) = &res.noti {
+                                    noti.written.fetch_add(data.len() as u64, std::sync::atomic::Ordering::SeqCst);
+                                }
+                                Ok(())
+                            });
+                        }
+                    }
+                    let error = error || flush_result.is_err();
+                    if let Some(atomic) = res.atomic.take() {
+                        // 一時ファイルに書き込んでいるので、成功した場合のみ対象のファイルと置き換える
+                        // 置き換えに失敗しても、通知の更新は行ってからエラーを返す
+                        let api = app.android_fs();
+                        match error {
+                            true => api.impls().abort_atomic_write(atomic),
+                            false => flush_result = match res.writer.file().try_clone() {
+                                Ok(file) => api.impls().commit_atomic_write(file, atomic),
+                                Err(err) => {
+                                    api.impls().abort_atomic_write(atomic);
+                                    Err(err.into())
+                                },
+                            },
+                        }
+                    }
+                    let error = error || flush_result.is_err();
                     if let Some(noti) = res.noti.take() {
                         if !error {
                             let written = noti.written.load(std::sync::atomic::Ordering::SeqCst);
@@ -959,6 +1194,7 @@ async fn write_file_stream<R: tauri::Runtime, K: Send + Sync + 'static>(
                             );
                         }
                     }
+                    flush_result?;
                 }
 
                 Ok(WriteFileStreamEventOutput::Close(()))
@@ -1040,6 +1276,67 @@ pub async fn read_file<R: tauri::Runtime>(
     }
 }
 
+#[tauri::command]
+pub async fn read_last_lines<R: tauri::Runtime>(
+    uri: AfsUriOrFsPath,
+    n: usize,
+    label: String,
+    ignore_bom: bool,
+    app: tauri::AppHandle<R>,
+    cmd_scope: tauri::ipc::CommandScope<AfsScope>,
+    global_scope: tauri::ipc::GlobalScope<AfsScope>,
+) -> Result<tauri::ipc::Response> {
+
+    #[cfg(not(target_os = "android"))] {
+        Err(Error::NOT_ANDROID)
+    }
+    #[cfg(target_os = "android")] {
+        let uri = uri.try_into_content_or_safe_file_scheme_uri()?;
+        if let Some(path) = uri.to_path() {
+            validate_path_permission(path, &app, &cmd_scope, &global_scope)?;
+        }
+
+        let line_breaks = line_breaks_for_encoding_label(&label);
+        let markers = TextMarkers {
+            lf: line_breaks.lf,
+            cr: line_breaks.cr,
+            bom: match ignore_bom {
+                true => None,
+                false => bom_for_encoding_label(&label),
+            },
+        };
+
+        let lines = app.android_fs_async().impls().read_last_lines_raw(&uri, n, markers).await?;
+        Ok(tauri::ipc::Response::new(frame_raw_lines(lines)))
+    }
+}
+
+#[tauri::command]
+pub async fn build_line_index<R: tauri::Runtime>(
+    uri: AfsUriOrFsPath,
+    interval: u64,
+    label: String,
+    app: tauri::AppHandle<R>,
+    cmd_scope: tauri::ipc::CommandScope<AfsScope>,
+    global_scope: tauri::ipc::GlobalScope<AfsScope>,
+) -> Result<u64> {
+
+    #[cfg(not(target_os = "android"))] {
+        Err(Error::NOT_ANDROID)
+    }
+    #[cfg(target_os = "android")] {
+        let uri = uri.try_into_content_or_safe_file_scheme_uri()?;
+        if let Some(path) = uri.to_path() {
+            validate_path_permission(path, &app, &cmd_scope, &global_scope)?;
+        }
+
+        let api = app.android_fs_async();
+        let index = api.build_line_index(&uri, interval, Some(&label)).await?;
+        api.save_line_index(&uri, &index).await?;
+        Ok(index.line_count())
+    }
+}
+
 #[tauri::command]
 pub async fn read_file_as_base64<R: tauri::Runtime>(
     uri: AfsUriOrFsPath,
@@ -1231,6 +1528,181 @@ pub async fn copy_file<R: tauri::Runtime>(
     }
 }
 
+#[tauri::command]
+pub async fn hash_file<R: tauri::Runtime>(
+    uri: AfsUriOrFsPath,
+    algorithm: HashAlgorithm,
+    notification: Option<ProgressNotificationSettings>,
+    app: tauri::AppHandle<R>,
+    cmd_scope: tauri::ipc::CommandScope<AfsScope>,
+    global_scope: tauri::ipc::GlobalScope<AfsScope>,
+) -> Result<String> {
+
+    #[cfg(not(target_os = "android"))] {
+        Err(Error::NOT_ANDROID)
+    }
+    #[cfg(target_os = "android")] {
+        let uri = uri.try_into_content_or_safe_file_scheme_uri()?;
+        if let Some(path) = uri.to_path() {
+            validate_path_permission(path, &app, &cmd_scope, &global_scope)?;
+        }
+
+        let api = app.android_fs_async();
+
+        let Some(noti) = CmdProgressNotification::start(&app, notification, &uri).await? else {
+            let digest = api.hash(&uri, algorithm).await?;
+            return Ok(encode_hex(&digest))
+        };
+
+        let mut file = api.open_file_readable(&uri).await?;
+
+        let (digest, noti) = tauri::async_runtime::spawn_blocking(move || -> Result<_> {
+            let len = file.metadata().ok().filter(|m| m.is_file()).map(|m| m.len());
+            let mut noti = noti;
+            let mut hasher = Hasher::new(algorithm);
+            let mut read = 0;
+            let mut dest = CountWriter::new(&mut hasher, |l: usize| {
+                read += l as u64;
+                noti.update(read, len);
+                Ok(())
+            });
+            std::io::copy(&mut file, &mut dest)?;
+            Ok((hasher.finalize(), noti))
+        }).await??;
+
+        noti.complete(None);
+
+        Ok(encode_hex(&digest))
+    }
+}
+
+#[tauri::command]
+pub async fn create_zip<R: tauri::Runtime>(
+    src_uris: Vec<AfsUriOrFsPath>,
+    dest_uri: AfsUriOrFsPath,
+    options: CreateZipCommandOptions,
+    notification: Option<ProgressNotificationSettings>,
+    app: tauri::AppHandle<R>,
+    cmd_scope: tauri::ipc::CommandScope<AfsScope>,
+    global_scope: tauri::ipc::GlobalScope<AfsScope>,
+) -> Result<()> {
+
+    #[cfg(not(target_os = "android"))] {
+        Err(Error::NOT_ANDROID)
+    }
+    #[cfg(target_os = "android")] {
+        let mut src_uris_ = Vec::with_capacity(src_uris.len());
+        for src_uri in src_uris {
+            let src_uri = src_uri.try_into_content_or_safe_file_scheme_uri()?;
+            if let Some(path) = src_uri.to_path() {
+                validate_path_permission(path, &app, &cmd_scope, &global_scope)?;
+            }
+            src_uris_.push(src_uri);
+        }
+        let src_uris = src_uris_;
+        let dest_uri = dest_uri.try_into_content_or_safe_file_scheme_uri()?;
+        if let Some(dest_path) = dest_uri.to_path() {
+            validate_path_permission(&dest_path, &app, &cmd_scope, &global_scope)?;
+
+            if options.create && !dest_path.try_exists()? {
+                std::fs::File::create(&dest_path)?;
+            }
+        }
+        let options = options.zip;
+
+        let api = app.android_fs_async();
+
+        let Some(mut noti) = CmdProgressNotification::start(&app, notification, &dest_uri).await? else {
+            return api.create_zip(&src_uris, &dest_uri, options).await
+        };
+
+        // アーカイブ全体のサイズは事前に分からないので、元のファイルの合計サイズに対する進捗を表示する
+        api.create_zip_with_progress(&src_uris, &dest_uri, options, |done, total| {
+            noti.update(done, Some(total))
+        }).await?;
+
+        let share_src = match dest_uri.is_content_scheme() {
+            true => Some(&dest_uri),
+            false => None,
+        };
+        noti.complete(share_src);
+
+        Ok(())
+    }
+}
+
+#[tauri::command]
+pub async fn extract_zip<R: tauri::Runtime>(
+    src_uri: AfsUriOrFsPath,
+    dest_uri: AfsUriOrFsPath,
+    options: ExtractZipOptions,
+    notification: Option<ProgressNotificationSettings>,
+    app: tauri::AppHandle<R>,
+    cmd_scope: tauri::ipc::CommandScope<AfsScope>,
+    global_scope: tauri::ipc::GlobalScope<AfsScope>,
+) -> Result<Vec<serde_json::Value>> {
+
+    #[cfg(not(target_os = "android"))] {
+        Err(Error::NOT_ANDROID)
+    }
+    #[cfg(target_os = "android")] {
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct ExtractZipEntryForJs {
+            name: String,
+            is_dir: bool,
+            byte_length: u64,
+            status: &'static str,
+            uri: Option<FileUri>,
+            relative_path: Option<String>,
+            error: Option<String>,
+        }
+
+        fn to_js(entry: ExtractZipEntry) -> Result<serde_json::Value> {
+            let (status, uri, relative_path, error) = match entry.result {
+                Ok(ExtractZipOutcome::Extracted { dest, relative_path }) => {
+                    ("Extracted", Some(dest), Some(relative_path.to_string_lossy().into_owned()), None)
+                },
+                Ok(_) => ("Skipped", None, None, None),
+                Err(err) => ("Failed", None, None, Some(err.to_string())),
+            };
+            let entry = ExtractZipEntryForJs {
+                name: entry.name,
+                is_dir: entry.is_dir,
+                byte_length: entry.len,
+                status,
+                uri,
+                relative_path,
+                error,
+            };
+            Ok(serde_json::to_value(entry)?)
+        }
+
+        let src_uri = src_uri.try_into_content_or_safe_file_scheme_uri()?;
+        if let Some(path) = src_uri.to_path() {
+            validate_path_permission(path, &app, &cmd_scope, &global_scope)?;
+        }
+        let dest_uri = dest_uri.try_into_content_or_safe_file_scheme_uri()?;
+        if let Some(path) = dest_uri.to_path() {
+            validate_path_permission(path, &app, &cmd_scope, &global_scope)?;
+        }
+
+        let api = app.android_fs_async();
+
+        let Some(mut noti) = CmdProgressNotification::start(&app, notification, &src_uri).await? else {
+            return api.extract_zip(&src_uri, &dest_uri, options).await?.into_iter().map(to_js).collect()
+        };
+
+        let entries = api.extract_zip_with_progress(&src_uri, &dest_uri, options, |done, total| {
+            noti.update(done, Some(total))
+        }).await?;
+
+        noti.complete(None);
+
+        entries.into_iter().map(to_js).collect()
+    }
+}
+
 #[tauri::command]
 pub async fn truncate_file<R: tauri::Runtime>(
     uri: AfsUriOrFsPath,
@@ -1253,6 +1725,7 @@ pub async fn read_dir<R: tauri::Runtime>(
     uri: AfsUriOrFsPath,
     offset: Option<u64>,
     limit: Option<u64>,
+    options: Option<ReadDirOptions>,
     app: tauri::AppHandle<R>
 ) -> Result<Vec<impl Serialize>> {
 
@@ -1260,56 +1733,406 @@ pub async fn read_dir<R: tauri::Runtime>(
         Result::<Vec<String>>::Err(Error::NOT_ANDROID)
     }
     #[cfg(target_os = "android")] {
-        #[derive(Serialize)]
-        #[serde(tag = "type")]
-        enum EntryMetadataWithUri {
-            File {
-                name: String,
-                uri: FileUri,
+        let uri = uri.try_into_content_uri()?;
+        let api = app.android_fs_async();
+        let options = ReadDirOptions {
+            entry_options: EntryOptions::ALL,
+            ..options.unwrap_or_default()
+        };
+        let start = offset.unwrap_or(0);
+        let end = limit.map(|limit| start.saturating_add(limit));
+        let entries = match end {
+            Some(end) => api.read_dir_with_options_and_range(&uri, options, start..end).await?,
+            None => api.read_dir_with_options_and_range(&uri, options, start..).await?,
+        };
 
-                #[serde(rename = "lastModified")]
-                last_modified: f64,
+        entries.into_iter().map(EntryMetadataWithUri::try_from).collect::<Result<Vec<_>>>()
+    }
+}
 
-                #[serde(rename = "byteLength")]
-                len: u64,
+#[tauri::command]
+pub async fn open_read_dir_stream<R: tauri::Runtime>(
+    event: ReadDirStreamEventInput,
+    app: tauri::AppHandle<R>,
+    cmd_scope: tauri::ipc::CommandScope<AfsScope>,
+    global_scope: tauri::ipc::GlobalScope<AfsScope>,
+    resources: FileStreamResourcesState<'_, R>,
+) -> Result<ReadDirStreamEventOutput> {
 
-                #[serde(rename = "mimeType")]
-                mime_type: String,
+    #[cfg(not(target_os = "android"))] {
+        Err(Error::NOT_ANDROID)
+    }
+    #[cfg(target_os = "android")] {
+        type DirResource<R> = std::sync::Mutex<crate::api::api_sync::ReadDir<R>>;
+
+        let resources = std::sync::Arc::clone(&resources);
+
+        match event {
+            ReadDirStreamEventInput::Open { uri, page_size } => {
+                let uri = uri.try_into_content_or_safe_file_scheme_uri()?;
+                if let Some(path) = uri.to_path() {
+                    validate_path_permission(&path, &app, &cmd_scope, &global_scope)?;
+                }
+
+                let api = app.android_fs();
+                let res: DirResource<R> = std::sync::Mutex::new(api.read_dir_paged(&uri, EntryOptions::ALL, page_size)?);
+                let id = resources.add(res)?;
+
+                Ok(ReadDirStreamEventOutput::Open(id))
             },
-            Dir {
-                name: String,
-                uri: FileUri,
+            ReadDirStreamEventInput::Read { id } => {
+                tauri::async_runtime::spawn_blocking(move || -> Result<_> {
+                    let entries = resources
+                        .get::<DirResource<R>>(id)?
+                        .lock()?
+                        .next_page()
+                        .transpose()?
+                        .unwrap_or_default();
 
-                #[serde(rename = "lastModified")]
-                last_modified: f64,
+                    let mut buffer = Vec::with_capacity(entries.len());
+                    for entry in entries {
+                        buffer.push(serde_json::to_value(EntryMetadataWithUri::try_from(entry)?)?);
+                    }
+
+                    Ok(ReadDirStreamEventOutput::Read(buffer))
+                }).await?
+            },
+            ReadDirStreamEventInput::Close { id } => {
+                tauri::async_runtime::spawn_blocking(move || {
+                    resources.close(id)?;
+                    Ok(ReadDirStreamEventOutput::Close(()))
+                }).await?
+            },
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn walk_dir<R: tauri::Runtime>(
+    uri: AfsUriOrFsPath,
+    options: WalkOptions,
+    on_entries: tauri::ipc::Channel<Vec<serde_json::Value>>,
+    app: tauri::AppHandle<R>,
+    cmd_scope: tauri::ipc::CommandScope<AfsScope>,
+    global_scope: tauri::ipc::GlobalScope<AfsScope>,
+) -> Result<u64> {
+
+    #[cfg(not(target_os = "android"))] {
+        Err(Error::NOT_ANDROID)
+    }
+    #[cfg(target_os = "android")] {
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct WalkEntryWithUri {
+            #[serde(flatten)]
+            entry: EntryMetadataWithUri,
+            depth: u32,
+            relative_path: String,
+        }
+
+        let uri = uri.try_into_content_or_safe_file_scheme_uri()?;
+        if let Some(path) = uri.to_path() {
+            validate_path_permission(path, &app, &cmd_scope, &global_scope)?;
+        }
+
+        let batch_len = usize::try_from(options.page_size).unwrap_or(usize::MAX).max(1);
+        let options = WalkOptions { entry_options: EntryOptions::ALL, ..options };
+        let api = app.android_fs_async();
+        let mut walker = api.walk_dir(&uri, options)?;
+        let mut buffer = Vec::new();
+        let mut count = 0;
+
+        while let Some(item) = walker.next_entry().await {
+            let WalkEntry { entry, depth, relative_path, .. } = item?;
+            let entry = WalkEntryWithUri {
+                entry: entry.try_into()?,
+                depth,
+                relative_path: relative_path.to_string_lossy().into_owned(),
+            };
+            buffer.push(serde_json::to_value(entry)?);
+            count += 1;
+
+            if batch_len <= buffer.len() {
+                on_entries.send(std::mem::take(&mut buffer))?;
             }
         }
+        if !buffer.is_empty() {
+            on_entries.send(buffer)?;
+        }
+
+        // フロントエンドはこの数のエントリを受け取るまで待機する
+        Ok(count)
+    }
+}
+
+#[tauri::command]
+pub async fn find<R: tauri::Runtime>(
+    uri: AfsUriOrFsPath,
+    pattern: String,
+    app: tauri::AppHandle<R>,
+    cmd_scope: tauri::ipc::CommandScope<AfsScope>,
+    global_scope: tauri::ipc::GlobalScope<AfsScope>,
+) -> Result<Vec<serde_json::Value>> {
+
+    #[cfg(not(target_os = "android"))] {
+        Err(Error::NOT_ANDROID)
+    }
+    #[cfg(target_os = "android")] {
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct FoundEntryWithUri {
+            #[serde(flatten)]
+            entry: EntryMetadataWithUri,
+            relative_path: String,
+        }
+
+        let uri = uri.try_into_content_or_safe_file_scheme_uri()?;
+        if let Some(path) = uri.to_path() {
+            validate_path_permission(path, &app, &cmd_scope, &global_scope)?;
+        }
 
-        let uri = uri.try_into_content_uri()?;
         let api = app.android_fs_async();
-        let start = offset.unwrap_or(0);
-        let end = limit.map(|limit| start.saturating_add(limit));
-        let entries = match end {
-            Some(end) => api.read_dir_with_range(&uri, start..end).await?,
-            None => api.read_dir_with_range(&uri, start..).await?,
+        let mut buffer = Vec::new();
+
+        for (entry, relative_path) in api.find(&uri, &pattern).await? {
+            let entry = FoundEntryWithUri {
+                entry: entry.try_into()?,
+                relative_path: relative_path.to_string_lossy().into_owned(),
+            };
+            buffer.push(serde_json::to_value(entry)?);
+        }
+
+        Ok(buffer)
+    }
+}
+
+#[tauri::command]
+pub async fn get_dir_stats<R: tauri::Runtime>(
+    uri: AfsUriOrFsPath,
+    options: DirStatsOptions,
+    app: tauri::AppHandle<R>,
+    cmd_scope: tauri::ipc::CommandScope<AfsScope>,
+    global_scope: tauri::ipc::GlobalScope<AfsScope>,
+) -> Result<serde_json::Value> {
+
+    #[cfg(not(target_os = "android"))] {
+        Err(Error::NOT_ANDROID)
+    }
+    #[cfg(target_os = "android")] {
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct DirStatsForJs {
+            byte_length: u64,
+            file_count: u64,
+            dir_count: u64,
+            mime_types: std::collections::BTreeMap<String, MimeTypeStatsForJs>,
+        }
+
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct MimeTypeStatsForJs {
+            byte_length: u64,
+            file_count: u64,
+        }
+
+        let uri = uri.try_into_content_or_safe_file_scheme_uri()?;
+        if let Some(path) = uri.to_path() {
+            validate_path_permission(path, &app, &cmd_scope, &global_scope)?;
+        }
+
+        let api = app.android_fs_async();
+        let stats = api.dir_stats_with_options(&uri, options, || false).await?;
+
+        let stats = DirStatsForJs {
+            byte_length: stats.total_len,
+            file_count: stats.file_count,
+            dir_count: stats.dir_count,
+            mime_types: stats.mime_types
+                .into_iter()
+                .map(|(mime_type, s)| (mime_type, MimeTypeStatsForJs { byte_length: s.total_len, file_count: s.file_count }))
+                .collect(),
         };
 
-        let mut buffer = Vec::new();
+        Ok(serde_json::to_value(stats)?)
+    }
+}
 
-        for entry in entries {
-            let entry = match entry {
-                Entry::File { uri, name, last_modified, len, mime_type } => {
-                    let last_modified = convert_time_to_f64_millis(last_modified)?;
-                    EntryMetadataWithUri::File { name, uri, len, mime_type, last_modified }
-                },
-                Entry::Dir { uri, name, last_modified } => {
-                    let last_modified = convert_time_to_f64_millis(last_modified)?;
-                    EntryMetadataWithUri::Dir { name, uri, last_modified }
-                },
+#[tauri::command]
+pub async fn sync_dir<R: tauri::Runtime>(
+    src_uri: AfsUriOrFsPath,
+    dest_uri: AfsUriOrFsPath,
+    options: SyncOptions,
+    on_progress: tauri::ipc::Channel<serde_json::Value>,
+    app: tauri::AppHandle<R>,
+    cmd_scope: tauri::ipc::CommandScope<AfsScope>,
+    global_scope: tauri::ipc::GlobalScope<AfsScope>,
+) -> Result<Vec<serde_json::Value>> {
+
+    #[cfg(not(target_os = "android"))] {
+        Err(Error::NOT_ANDROID)
+    }
+    #[cfg(target_os = "android")] {
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct SyncActionForJs {
+            r#type: &'static str,
+            relative_path: String,
+        }
+
+        fn to_js(action: &SyncAction) -> SyncActionForJs {
+            let r#type = match action {
+                SyncAction::CreateDir { .. } => "CreateDir",
+                SyncAction::CreateFile { .. } => "CreateFile",
+                SyncAction::UpdateFile { .. } => "UpdateFile",
+                SyncAction::RemoveFile { .. } => "RemoveFile",
+                SyncAction::RemoveDir { .. } => "RemoveDir",
             };
-            buffer.push(entry);
+            SyncActionForJs { r#type, relative_path: action.relative_path().to_string_lossy().into_owned() }
+        }
+
+        let src_uri = src_uri.try_into_content_or_safe_file_scheme_uri()?;
+        if let Some(path) = src_uri.to_path() {
+            validate_path_permission(path, &app, &cmd_scope, &global_scope)?;
+        }
+        let dest_uri = dest_uri.try_into_content_or_safe_file_scheme_uri()?;
+        if let Some(path) = dest_uri.to_path() {
+            validate_path_permission(path, &app, &cmd_scope, &global_scope)?;
+        }
+
+        let api = app.android_fs_async();
+        let actions = api.sync_dir_with_progress(&src_uri, &dest_uri, options, |action, done, total| {
+            let progress = serde_json::json!({ "action": to_js(action), "done": done, "total": total });
+            // 進捗の通知に失敗しても同期は続ける
+            let _ = on_progress.send(progress);
+        }).await?;
+
+        // フロントエンドは dryRun でない場合、この数の進捗を受け取るまで待機する
+        actions.iter()
+            .map(|a| serde_json::to_value(to_js(a)).map_err(Into::into))
+            .collect()
+    }
+}
+
+#[tauri::command]
+pub async fn watch_dir<R: tauri::Runtime>(
+    uri: AfsUriOrFsPath,
+    interval_ms: u64,
+    on_event: tauri::ipc::Channel<serde_json::Value>,
+    app: tauri::AppHandle<R>,
+    webview: tauri::Webview<R>,
+    cmd_scope: tauri::ipc::CommandScope<AfsScope>,
+    global_scope: tauri::ipc::GlobalScope<AfsScope>,
+) -> Result<tauri::ResourceId> {
+
+    #[cfg(not(target_os = "android"))] {
+        Err(Error::NOT_ANDROID)
+    }
+    #[cfg(target_os = "android")] {
+        use tauri::Manager as _;
+
+        fn event_to_js(event: Result<DirWatchEvent>) -> Result<serde_json::Value> {
+            let entry_to_js = EntryMetadataWithUri::try_from;
+
+            Ok(match event {
+                Ok(DirWatchEvent::Created { entry }) => serde_json::json!({ "type": "Created", "entry": entry_to_js(entry)? }),
+                Ok(DirWatchEvent::Removed { entry }) => serde_json::json!({ "type": "Removed", "entry": entry_to_js(entry)? }),
+                Ok(DirWatchEvent::Modified { old, new }) => serde_json::json!({ "type": "Modified", "old": entry_to_js(old)?, "new": entry_to_js(new)? }),
+                Ok(DirWatchEvent::Renamed { old, new }) => serde_json::json!({ "type": "Renamed", "old": entry_to_js(old)?, "new": entry_to_js(new)? }),
+                Ok(_) => return Err(Error::with("unknown event")),
+                Err(err) => serde_json::json!({ "type": "Error", "message": err.to_string() }),
+            })
+        }
+
+        let uri = uri.try_into_content_or_safe_file_scheme_uri()?;
+        if let Some(path) = uri.to_path() {
+            validate_path_permission(path, &app, &cmd_scope, &global_scope)?;
+        }
+
+        let api = app.android_fs_async();
+        let interval = std::time::Duration::from_millis(interval_ms);
+        let guard = api.watch_dir(&uri, interval, move |event| {
+            if let Ok(event) = event_to_js(event) {
+                let _ = on_event.send(event);
+            }
+        }).await?;
+
+        // webview のリソースとして保持し、webview が破棄された際に監視も終了させる
+        Ok(webview.resources_table().add(DirWatchResource(guard)))
+    }
+}
+
+#[tauri::command]
+pub async fn unwatch_dir<R: tauri::Runtime>(
+    id: tauri::ResourceId,
+    webview: tauri::Webview<R>,
+) -> Result<()> {
+
+    #[cfg(not(target_os = "android"))] {
+        Err(Error::NOT_ANDROID)
+    }
+    #[cfg(target_os = "android")] {
+        use tauri::Manager as _;
+
+        let mut resources = webview.resources_table();
+        if resources.has(id) {
+            resources.close(id)?;
+        }
+        Ok(())
+    }
+}
+
+#[tauri::command]
+pub async fn find_duplicates<R: tauri::Runtime>(
+    uris: Vec<AfsUriOrFsPath>,
+    options: FindDuplicatesOptions,
+    on_progress: tauri::ipc::Channel<serde_json::Value>,
+    app: tauri::AppHandle<R>,
+    cmd_scope: tauri::ipc::CommandScope<AfsScope>,
+    global_scope: tauri::ipc::GlobalScope<AfsScope>,
+) -> Result<Vec<serde_json::Value>> {
+
+    #[cfg(not(target_os = "android"))] {
+        Err(Error::NOT_ANDROID)
+    }
+    #[cfg(target_os = "android")] {
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct FileMetadataWithUri {
+            r#type: &'static str,
+            name: String,
+            uri: FileUri,
+            last_modified: f64,
+            byte_length: u64,
+            mime_type: String,
         }
 
+        let mut roots = Vec::with_capacity(uris.len());
+        for uri in uris {
+            let uri = uri.try_into_content_or_safe_file_scheme_uri()?;
+            if let Some(path) = uri.to_path() {
+                validate_path_permission(path, &app, &cmd_scope, &global_scope)?;
+            }
+            roots.push(uri);
+        }
+
+        let api = app.android_fs_async();
+        let groups = api.find_duplicates_with_options(&roots, options, |phase, done, total| {
+            let progress = serde_json::json!({ "phase": phase, "done": done, "total": total });
+            // 進捗の通知に失敗しても検索は続ける
+            let _ = on_progress.send(progress);
+        }).await?;
+
+        let mut buffer = Vec::with_capacity(groups.len());
+        for group in groups {
+            let mut entries = Vec::with_capacity(group.entries.len());
+            for entry in group.entries {
+                if let Entry::File { uri, name, last_modified, len, mime_type } = entry {
+                    let last_modified = convert_time_to_f64_millis(last_modified)?;
+                    entries.push(FileMetadataWithUri { r#type: "File", name, uri, last_modified, byte_length: len, mime_type });
+                }
+            }
+            buffer.push(serde_json::json!({ "byteLength": group.len, "entries": entries }));
+        }
         Ok(buffer)
     }
 }
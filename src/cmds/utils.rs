@@ -136,6 +136,17 @@ pub fn convert_base64_to_data_url(base64: &str, mime_type: &str) -> Result<Strin
     Ok(buffer)
 }
 
+#[cfg(target_os = "android")]
+pub fn encode_hex(bytes: &[u8]) -> String {
+    use std::fmt::Write as _;
+
+    let mut hex = String::with_capacity(bytes.len() * 2);
+    for b in bytes {
+        let _ = write!(hex, "{b:02x}");
+    }
+    hex
+}
+
 #[cfg(target_os = "android")]
 pub struct CountWriter<W, F> {
     writer: W,
@@ -347,16 +358,155 @@ impl Throttler {
     }
 }
 
+/// コマンドの進捗通知。
+/// [`CmdProgressNotification::complete`] を呼ばずに drop された場合は失敗として表示される。
+#[cfg(target_os = "android")]
+pub struct CmdProgressNotification<R: tauri::Runtime> {
+    handler: std::sync::Arc<crate::api::api_async::ProgressNotificationGuard<R>>,
+    settings: std::sync::Arc<ProgressNotificationSettings>,
+    file_name: String,
+    throttler: Throttler,
+    progress: u64,
+}
+
+#[cfg(target_os = "android")]
+impl<R: tauri::Runtime> CmdProgressNotification<R> {
+
+    /// 通知を表示する。
+    /// 設定が無い場合や通知の権限が無い場合は None を返す。
+    /// プレースホルダーのファイル名には `file_uri` の名前を用いる。
+    pub async fn start(
+        app: &tauri::AppHandle<R>,
+        settings: Option<ProgressNotificationSettings>,
+        file_uri: &FileUri,
+    ) -> Result<Option<Self>> {
+
+        let Some(settings) = settings else {
+            return Ok(None)
+        };
+        let api = app.android_fs_async();
+        if !api.utils().request_notification_permission().await? {
+            return Ok(None)
+        }
+
+        let file_name = api.get_name_or_last_path_segment(file_uri).await;
+
+        let resolve_placeholders = |text| resolve_pn_placeholders(
+            text,
+            &file_name,
+            Some(0),
+            settings.expected_byte_length()
+        );
+        let handler = api
+            .utils()
+            .create_progress_notification(
+                settings.icon(),
+                resolve_placeholders(settings.title_progress()).as_deref(),
+                resolve_placeholders(settings.text_progress()).as_deref(),
+                resolve_placeholders(settings.sub_text_progress()).as_deref(),
+                None,
+                None,
+            )
+            .await?;
+
+        let resolve_drop_behavior_fn = |value: Option<String>| {
+            let file_name = file_name.clone();
+            move || resolve_pn_placeholders(
+                value.as_deref(),
+                &file_name,
+                None,
+                None
+            )
+        };
+        handler.set_drop_behavior_to_fail_with(
+            resolve_drop_behavior_fn(settings.title_failure().map(|s| s.to_string())),
+            resolve_drop_behavior_fn(settings.text_failure().map(|s| s.to_string())),
+            resolve_drop_behavior_fn(settings.sub_text_failure().map(|s| s.to_string())),
+        );
+
+        Ok(Some(Self {
+            handler: std::sync::Arc::new(handler),
+            settings: std::sync::Arc::new(settings),
+            file_name,
+            throttler: Throttler::new(std::time::Duration::from_millis(500)),
+            progress: 0,
+        }))
+    }
+
+    /// 進捗を記録し、前回の更新から一定時間が経っている場合は通知を更新する。
+    /// `progress_max` が None の場合は設定された `expectedByteLength` を用いる。
+    pub fn update(&mut self, progress: u64, progress_max: Option<u64>) {
+        self.progress = progress;
+        if !self.throttler.try_acquire() {
+            return
+        }
+
+        let handler = std::sync::Arc::clone(&self.handler);
+        let settings = std::sync::Arc::clone(&self.settings);
+        let file_name = self.file_name.clone();
+        tauri::async_runtime::spawn(async move {
+            let progress = Some(progress);
+            let progress_max = progress_max.or(settings.expected_byte_length());
+            let indeterminate_progress_bar = settings.force_indeterminate_progress_bar();
+            let resolve_placeholders = |text| resolve_pn_placeholders(
+                text,
+                &file_name,
+                progress,
+                progress_max
+            );
+
+            handler.update(
+                resolve_placeholders(settings.title_progress()).as_deref(),
+                resolve_placeholders(settings.text_progress()).as_deref(),
+                resolve_placeholders(settings.sub_text_progress()).as_deref(),
+                if indeterminate_progress_bar { None } else { progress },
+                if indeterminate_progress_bar { None } else { progress_max },
+            ).await
+        });
+    }
+
+    /// 最後に記録された進捗で、通知が完了として表示されるようにする。
+    pub fn complete(self, share_src: Option<&FileUri>) {
+        let resolve_placeholders = |text| resolve_pn_placeholders(
+            text,
+            &self.file_name,
+            Some(self.progress),
+            Some(self.progress)
+        );
+        self.handler.set_drop_behavior_to_complete(
+            resolve_placeholders(self.settings.title_completion()).as_deref(),
+            resolve_placeholders(self.settings.text_completion()).as_deref(),
+            resolve_placeholders(self.settings.sub_text_completion()).as_deref(),
+            share_src
+        );
+    }
+}
+
 #[cfg_attr(not(target_os = "android"), allow(unused))]
 pub enum WriteFileStreamEventInput {
     Open {
         uri: AfsUriOrFsPath,
         supports_raw_ipc_request_body: bool,
-        options: WriteFileStreamEventInputOptions
+        options: Box<WriteFileStreamEventInputOptions>
     },
     Write {
         id: tauri::ResourceId,
         data: Vec<u8>,
+
+        /// `WriteAt` イベントの場合は Some。現在の位置を変えずにこの位置に書き込む。
+        offset: Option<u64>,
+
+        /// data が UTF-8 テキストであり、Open 時に指定されたエンコーディングで書き込むべきかどうか
+        text: bool,
+    },
+    Seek {
+        id: tauri::ResourceId,
+        offset: i64,
+        whence: SeekWhence,
+    },
+    SetLen {
+        id: tauri::ResourceId,
+        len: u64,
     },
     Close {
         id: tauri::ResourceId,
@@ -370,6 +520,51 @@ pub enum WriteFileStreamEventInput {
 pub struct WriteFileStreamEventInputOptions {
     pub create: bool,
     pub notification: Option<ProgressNotificationSettings>,
+
+    #[serde(default)]
+    pub atomic: bool,
+
+    #[serde(default)]
+    pub append: bool,
+
+    /// テキストを書き込む際のエンコーディングの WHATWG ラベル。  
+    /// None の場合は UTF-8。
+    #[serde(default)]
+    pub encoding: Option<String>,
+
+    /// テキストを書き込む前に BOM を書き込むかどうか
+    #[serde(default)]
+    pub bom: bool,
+
+    /// テキスト中の改行コードをこれに統一する。  
+    /// None の場合は変換しない。
+    #[serde(default)]
+    pub line_ending: Option<LineEnding>,
+}
+
+#[cfg(target_os = "android")]
+impl WriteFileStreamEventInputOptions {
+
+    pub fn has_text_options(&self) -> bool {
+        self.encoding.is_some() || self.bom || self.line_ending.is_some()
+    }
+}
+
+#[derive(serde::Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+#[cfg_attr(not(target_os = "android"), allow(unused))]
+pub enum LineEnding {
+    Lf,
+    Crlf,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(not(target_os = "android"), allow(unused))]
+pub struct CreateZipCommandOptions {
+    #[serde(flatten)]
+    pub zip: CreateZipOptions,
+    pub create: bool,
 }
 
 #[cfg(target_os = "android")]
@@ -402,12 +597,17 @@ impl<'a> TryInto<WriteFileStreamEventInput> for tauri::ipc::Request<'a> {
               
                 Ok(WriteFileStreamEventInput::Open { uri, options, supports_raw_ipc_request_body })
             },
-            "Write" => {
+            "Write" | "WriteAt" => {
                 let id = get_header_value("id")?.parse::<u32>()?;
+                let offset = match event_type.as_ref() {
+                    "WriteAt" => Some(get_header_value("offset")?.parse::<u64>()?),
+                    _ => None,
+                };
 
-                let data = match self.body() {
+                let (data, text) = match self.body() {
                     tauri::ipc::InvokeBody::Raw(body) => {
-                        body.clone()
+                        let text = self.headers().get("text").is_some_and(|v| v.as_bytes() == b"true");
+                        (body.clone(), text)
                     },
                     tauri::ipc::InvokeBody::Json(body) => {
                         let format = body
@@ -430,15 +630,28 @@ impl<'a> TryInto<WriteFileStreamEventInput> for tauri::ipc::Request<'a> {
 
                                 let (_, b64) = data.split_at(comma_i + 1);
                                 use base64::engine::Engine;
-                                base64::engine::general_purpose::STANDARD.decode(b64)?
+                                (base64::engine::general_purpose::STANDARD.decode(b64)?, false)
                             },
-                            "textToUtf8" => data.to_string().into_bytes(),
+                            "textToUtf8" => (data.to_string().into_bytes(), true),
                             _ => Err(Error::invalid_value("format"))?
                         }
                     },
                 };
 
-                Ok(WriteFileStreamEventInput::Write { id, data })
+                Ok(WriteFileStreamEventInput::Write { id, data, offset, text })
+            },
+            "Seek" => {
+                let id = get_header_value("id")?.parse::<u32>()?;
+                let offset = get_header_value("offset")?.parse::<i64>()?;
+                let whence = serde_json::from_value(serde_json::Value::String(get_header_value("whence")?.into_owned()))?;
+
+                Ok(WriteFileStreamEventInput::Seek { id, offset, whence })
+            },
+            "SetLen" => {
+                let id = get_header_value("id")?.parse::<u32>()?;
+                let len = get_header_value("len")?.parse::<u64>()?;
+
+                Ok(WriteFileStreamEventInput::SetLen { id, len })
             },
             "Close" => {
                 let id = get_header_value("id")?.parse::<u32>()?;
@@ -462,6 +675,8 @@ pub enum WriteFileStreamEventOutput {
         supports_raw_ipc_request_body: bool
     },
     Write(()),
+    Seek(u64),
+    SetLen(()),
     Close(()),
 }
 
@@ -476,31 +691,78 @@ pub enum ReadFileStreamEventInput {
         id: tauri::ResourceId,
         len: u64
     },
+    ReadAt {
+        id: tauri::ResourceId,
+        offset: u64,
+        len: u64,
+    },
+    Seek {
+        id: tauri::ResourceId,
+        offset: i64,
+        whence: SeekWhence,
+    },
     Close {
         id: tauri::ResourceId,
     },
 }
 
+#[derive(serde::Deserialize, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(not(target_os = "android"), allow(unused))]
+pub enum SeekWhence {
+    Start,
+    Current,
+    End,
+}
+
+#[cfg(any(target_os = "android", all(test, unix)))]
+impl SeekWhence {
+
+    pub fn seek_from(self, offset: i64) -> Result<std::io::SeekFrom> {
+        Ok(match self {
+            SeekWhence::Start => {
+                let offset = u64::try_from(offset)
+                    .map_err(|_| Error::invalid_value(format!("offset: must not be negative when seeking from start: {offset}")))?;
+                std::io::SeekFrom::Start(offset)
+            },
+            SeekWhence::Current => std::io::SeekFrom::Current(offset),
+            SeekWhence::End => std::io::SeekFrom::End(offset),
+        })
+    }
+}
+
 #[cfg_attr(not(target_os = "android"), allow(unused))]
 pub enum ReadFileStreamEventOutput {
-    Open(tauri::ResourceId),
+    Open {
+        id: tauri::ResourceId,
+        position: u64,
+        len: Option<u64>,
+    },
     Read(Vec<u8>),
+    Seek(u64),
     Close(()),
 }
 
-#[cfg(target_os = "android")]
+#[cfg(any(target_os = "android", test))]
 impl TryFrom<ReadFileStreamEventOutput> for tauri::ipc::Response {
     type Error = Error;
 
     fn try_from(v: ReadFileStreamEventOutput) -> Result<tauri::ipc::Response> {
         match v {
-            ReadFileStreamEventOutput::Open(id) => {
-                 let id_bytes = convert_rid_to_bytes(id);
-                 Ok(tauri::ipc::Response::new(id_bytes))
+            ReadFileStreamEventOutput::Open { id, position, len } => {
+                // [id (4 bytes)][position (8 bytes)][has len flag (1 byte)][len (8 bytes)]
+                let mut bytes = convert_rid_to_bytes(id);
+                bytes.extend_from_slice(&position.to_be_bytes());
+                bytes.push(len.is_some() as u8);
+                bytes.extend_from_slice(&len.unwrap_or(0).to_be_bytes());
+                Ok(tauri::ipc::Response::new(bytes))
             },
             ReadFileStreamEventOutput::Read(bytes) => {
                 Ok(tauri::ipc::Response::new(bytes))
             },
+            ReadFileStreamEventOutput::Seek(position) => {
+                Ok(tauri::ipc::Response::new(position.to_be_bytes().to_vec()))
+            },
             ReadFileStreamEventOutput::Close(()) => {
                 Ok(tauri::ipc::Response::new(Vec::new()))
             }
@@ -508,36 +770,142 @@ impl TryFrom<ReadFileStreamEventOutput> for tauri::ipc::Response {
     }
 }
 
+/// フロントエンドに渡す URI 付きのエントリ
+#[derive(serde::Serialize)]
+#[serde(tag = "type")]
+#[cfg_attr(not(target_os = "android"), allow(unused))]
+pub enum EntryMetadataWithUri {
+    File {
+        name: String,
+        uri: FileUri,
+
+        #[serde(rename = "lastModified")]
+        last_modified: f64,
+
+        #[serde(rename = "byteLength")]
+        len: u64,
+
+        #[serde(rename = "mimeType")]
+        mime_type: String,
+    },
+    Dir {
+        name: String,
+        uri: FileUri,
+
+        #[serde(rename = "lastModified")]
+        last_modified: f64,
+    }
+}
+
+#[cfg(target_os = "android")]
+impl TryFrom<Entry> for EntryMetadataWithUri {
+    type Error = crate::Error;
+
+    fn try_from(entry: Entry) -> Result<Self> {
+        Ok(match entry {
+            Entry::File { uri, name, last_modified, len, mime_type } => {
+                let last_modified = convert_time_to_f64_millis(last_modified)?;
+                Self::File { name, uri, len, mime_type, last_modified }
+            },
+            Entry::Dir { uri, name, last_modified } => {
+                let last_modified = convert_time_to_f64_millis(last_modified)?;
+                Self::Dir { name, uri, last_modified }
+            },
+        })
+    }
+}
+
+#[cfg(target_os = "android")]
+impl TryFrom<OptionalEntry> for EntryMetadataWithUri {
+    type Error = crate::Error;
+
+    fn try_from(entry: OptionalEntry) -> Result<Self> {
+        Entry::try_from(entry)?.try_into()
+    }
+}
+
+#[derive(serde::Deserialize)]
+#[serde(tag = "type")]
+#[cfg_attr(not(target_os = "android"), allow(unused))]
+pub enum ReadDirStreamEventInput {
+    Open {
+        uri: AfsUriOrFsPath,
+
+        #[serde(rename = "pageSize")]
+        page_size: u64,
+    },
+    Read {
+        id: tauri::ResourceId,
+    },
+    Close {
+        id: tauri::ResourceId,
+    },
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(untagged)]
+#[cfg_attr(not(target_os = "android"), allow(unused))]
+pub enum ReadDirStreamEventOutput {
+    Open(tauri::ResourceId),
+
+    /// 空の場合は全てのエントリを読み終えたことを示す
+    Read(Vec<serde_json::Value>),
+
+    Close(()),
+}
+
+/// [`DirWatchGuard`] held in the resources table of a webview
+#[cfg(target_os = "android")]
+pub struct DirWatchResource(pub DirWatchGuard);
+
 #[cfg(target_os = "android")]
+impl tauri::Resource for DirWatchResource {}
+
+#[cfg(any(target_os = "android", all(test, unix)))]
+#[cfg_attr(not(target_os = "android"), allow(unused))]
 pub struct FileChunkReader {
     file: std::fs::File,
-    read_limit: Option<u64>,
-    read: u64,
+
+    /// `read_limit` が指定された場合の読み込みを終える位置
+    end: Option<u64>,
+
+    /// 現在の位置。パイプなどシークできないファイルでは開いた直後の位置を 0 とする
+    position: u64,
 }
 
-#[cfg(target_os = "android")]
+#[cfg(any(target_os = "android", all(test, unix)))]
+#[cfg_attr(not(target_os = "android"), allow(unused))]
 impl FileChunkReader {
 
-    pub fn new(file: std::fs::File, read_limit: Option<u64>) -> Self {
+    /// `read_limit` は現在の位置から読み込めるバイト数。
+    /// これは [`Self::read_chunk_at`] や [`Self::seek`] の後も同じ位置で終わる。
+    pub fn new(mut file: std::fs::File, read_limit: Option<u64>) -> Self {
+        use std::io::Seek as _;
+
+        let position = file.stream_position().unwrap_or(0);
         Self {
-            read_limit,
-            read: 0,
+            end: read_limit.map(|l| position.saturating_add(l)),
+            position,
             file,
         }
     }
 
+    /// `offset` から `end` までで読み込めるバイト数
+    fn readable_len(&self, offset: u64, len: u64) -> u64 {
+        match self.end {
+            Some(end) => u64::min(len, end.saturating_sub(offset)),
+            None => len,
+        }
+    }
+
     pub fn read_chunk(&mut self, len: u64) -> Result<Vec<u8>> {
         use std::io::Read as _;
 
-        if self.read_limit.is_some_and(|l| l <= self.read) {
+        let nlimit = self.readable_len(self.position, len);
+        if nlimit == 0 {
             return Ok(Vec::new())
         }
 
-        let mut nlimit = len;
-        if let Some(read_limit) = self.read_limit {
-            nlimit = u64::min(nlimit, read_limit.saturating_sub(self.read));
-        }
-
         let mut buf = Vec::with_capacity(usize::min(nlimit as usize, 2 * 1024 * 1024));
 
         let nread = self.file
@@ -545,10 +913,101 @@ impl FileChunkReader {
             .take(nlimit)
             .read_to_end(&mut buf)?;
 
-        self.read += nread as u64;
+        self.position += nread as u64;
+
+        Ok(buf)
+    }
+
+    /// 現在の位置を変えずに `offset` から最大 `len` バイトを読む。
+    /// EOF か `read_limit` の終わりに達した場合のみ `len` より短くなる。
+    pub fn read_chunk_at(&self, offset: u64, len: u64) -> Result<Vec<u8>> {
+        use std::os::unix::fs::FileExt as _;
+
+        let len = self.readable_len(offset, len);
+        let mut buf = Vec::with_capacity(usize::min(len as usize, 2 * 1024 * 1024));
+        let mut chunk = vec![0; usize::min(len as usize, 64 * 1024)];
+
+        while (buf.len() as u64) < len {
+            let want = u64::min(chunk.len() as u64, len - buf.len() as u64) as usize;
+            match self.file.read_at(&mut chunk[..want], offset + buf.len() as u64) {
+                Ok(0) => break,
+                Ok(n) => buf.extend_from_slice(&chunk[..n]),
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e.into()),
+            }
+        }
 
         Ok(buf)
     }
+
+    pub fn seek(&mut self, offset: i64, whence: SeekWhence) -> Result<u64> {
+        use std::io::Seek as _;
+        self.position = self.file.seek(whence.seek_from(offset)?)?;
+        Ok(self.position)
+    }
+
+    pub fn position(&mut self) -> Result<u64> {
+        use std::io::Seek as _;
+        Ok(self.file.stream_position()?)
+    }
+
+    /// ファイルの長さ。パイプなどで分からない場合は None。
+    pub fn file_len(&self) -> Option<u64> {
+        self.file.metadata().ok()
+            .filter(|m| m.is_file())
+            .map(|m| m.len())
+    }
+}
+
+#[cfg(any(target_os = "android", all(test, unix)))]
+#[cfg_attr(not(target_os = "android"), allow(unused))]
+pub struct FileChunkWriter {
+    file: std::fs::File,
+
+    /// 追記用に開かれたかどうか。
+    /// O_APPEND では位置を指定しても常に末尾に書き込まれるので、WriteAt と Seek はエラーとする。
+    append: bool,
+}
+
+#[cfg(any(target_os = "android", all(test, unix)))]
+#[cfg_attr(not(target_os = "android"), allow(unused))]
+impl FileChunkWriter {
+
+    pub fn new(file: std::fs::File, append: bool) -> Self {
+        Self { file, append }
+    }
+
+    pub fn file(&self) -> &std::fs::File {
+        &self.file
+    }
+
+    pub fn write_chunk(&mut self, data: &[u8]) -> Result<()> {
+        use std::io::Write as _;
+        Ok(self.file.write_all(data)?)
+    }
+
+    /// 現在の位置を変えずに `offset` に書き込む。
+    pub fn write_chunk_at(&self, offset: u64, data: &[u8]) -> Result<()> {
+        use std::os::unix::fs::FileExt as _;
+
+        if self.append {
+            return Err(Error::with("cannot write at an offset to a stream opened for appending"))
+        }
+        Ok(self.file.write_all_at(data, offset)?)
+    }
+
+    pub fn seek(&mut self, offset: i64, whence: SeekWhence) -> Result<u64> {
+        use std::io::Seek as _;
+
+        if self.append {
+            return Err(Error::with("cannot seek a stream opened for appending"))
+        }
+        Ok(self.file.seek(whence.seek_from(offset)?)?)
+    }
+
+    pub fn set_len(&self, len: u64) -> Result<()> {
+        Ok(self.file.set_len(len)?)
+    }
 }
 
 #[derive(serde::Deserialize)]
@@ -564,6 +1023,14 @@ pub enum ReadTextFileLinesStreamEventInput {
 
         #[serde(rename = "ignoreBOM")]
         ignore_bom: bool,
+
+        /// Some の場合、EOF に達してもストリームを終了せず、この間隔で追記された行を待つ。
+        #[serde(default, rename = "followIntervalMs")]
+        follow_interval_ms: Option<u64>,
+
+        /// 読み込みを始める行 (0 始まり)。保存された行の索引があれば、それを用いてシークする。
+        #[serde(default, rename = "startLine")]
+        start_line: u64,
     },
     Read {
         id: tauri::ResourceId,
@@ -601,7 +1068,49 @@ impl TryFrom<ReadTextFileLinesStreamEventOutput> for tauri::ipc::Response {
     }
 }
 
-#[cfg(target_os = "android")]
+#[derive(serde::Deserialize)]
+#[serde(tag = "type")]
+#[cfg_attr(not(target_os = "android"), allow(unused))]
+pub enum ReadDelimitedRecordsStreamEventInput {
+    Open {
+        uri: AfsUriOrFsPath,
+        label: String,
+        delimiter: RecordDelimiter,
+        csv: bool,
+
+        #[serde(rename = "maxRecordByteLength")]
+        max_record_len: u64,
+
+        #[serde(rename = "ignoreBOM")]
+        ignore_bom: bool,
+    },
+    Read {
+        id: tauri::ResourceId,
+        len: u64,
+    },
+    Close {
+        id: tauri::ResourceId,
+    },
+}
+
+#[derive(serde::Deserialize)]
+#[serde(tag = "type")]
+#[cfg_attr(not(target_os = "android"), allow(unused))]
+pub enum RecordDelimiter {
+
+    /// ファイルのエンコーディングに変換して用いるテキスト
+    Text {
+        text: String,
+    },
+
+    /// そのまま用いるバイト列
+    Bytes {
+        bytes: Vec<u8>,
+    },
+}
+
+#[cfg(any(target_os = "android", all(test, unix)))]
+#[cfg_attr(not(target_os = "android"), allow(unused))]
 pub struct FileTextLinesReader {
     file: std::io::BufReader<std::fs::File>,
     max_line_len: Option<std::num::NonZeroU64>,
@@ -609,10 +1118,22 @@ pub struct FileTextLinesReader {
     bom: Option<&'static [u8]>,
     bom_handled: bool,
     read_limit: Option<u64>,
-    read: u64
+    read: u64,
+    follow: Option<LinesFollow>,
 }
 
-#[cfg(target_os = "android")]
+/// `tail -f` のように EOF の後に追記される行を待つための設定
+#[cfg(any(target_os = "android", all(test, unix)))]
+#[cfg_attr(not(target_os = "android"), allow(unused))]
+pub struct LinesFollow {
+
+    /// 置き換えを検知するために開き直すファイル
+    pub uri: FileUri,
+    pub interval: std::time::Duration,
+}
+
+#[cfg(any(target_os = "android", all(test, unix)))]
+#[cfg_attr(not(target_os = "android"), allow(unused))]
 impl FileTextLinesReader {
 
     pub fn new(
@@ -630,8 +1151,121 @@ impl FileTextLinesReader {
             bom,
             read_limit,
             bom_handled: false,
-            read: 0
+            read: 0,
+            follow: None,
+        }
+    }
+
+    /// EOF に達しても終了せずに追記される行を待つようにする。  
+    /// この場合、改行で終わっていない最後の行は書き込み途中とみなし、改行が追記されるまで読み込まない。
+    pub fn set_follow(&mut self, follow: LinesFollow) {
+        self.follow = Some(follow);
+    }
+
+    pub fn follow(&self) -> Option<&LinesFollow> {
+        self.follow.as_ref()
+    }
+
+    /// ファイルが切り詰められた場合に先頭から読み直す。
+    /// 
+    /// 開いているファイルが読み込んだ位置より短くなったかどうかで判定する。
+    pub fn restart_if_truncated(&mut self) -> Result<bool> {
+        use std::io::Seek as _;
+
+        let is_truncated = self.file.get_ref().metadata().ok()
+            .filter(|m| m.is_file())
+            .is_some_and(|m| m.len() < self.read);
+
+        if !is_truncated {
+            return Ok(false)
+        }
+
+        self.file.rewind()?;
+        self.read = 0;
+        self.bom_handled = false;
+        Ok(true)
+    }
+
+    /// ファイルが別のファイルに置き換えられた場合に、それを先頭から読み直す。
+    /// 
+    /// `reopened` は同じ URI を開き直したファイルであり、
+    /// 開いているファイルとデバイス ID と inode 番号が異なる場合に置き換えられたとみなす。
+    /// これらを取得できない場合は置き換えられていないとみなす。
+    pub fn restart_if_replaced(&mut self, reopened: std::fs::File) -> Result<bool> {
+        use std::os::unix::fs::MetadataExt as _;
+
+        let id = |file: &std::fs::File| file.metadata().ok()
+            .filter(|m| m.is_file())
+            .map(|m| (m.dev(), m.ino()));
+
+        let is_replaced = match (id(self.file.get_ref()), id(&reopened)) {
+            (Some(current), Some(reopened)) => current != reopened,
+            _ => false,
+        };
+
+        if !is_replaced {
+            return Ok(false)
         }
+
+        self.file = std::io::BufReader::new(reopened);
+        self.read = 0;
+        self.bom_handled = false;
+        Ok(true)
+    }
+
+    /// `line` 行目 (0 始まり) から読み込むようにする。
+    /// 
+    /// `index` が与えられた場合は、それが示す位置にシークしてから残りの行を読み飛ばす。
+    /// そうでない場合は先頭から読み飛ばす。
+    /// 行の数え方は [`scan_line_index`] と同じであり、改行は符号単位に揃った位置でのみ検出する。
+    pub fn seek_to_line(&mut self, line: u64, index: Option<&LineIndex>) -> Result<()> {
+        use std::io::{Read as _, Seek as _};
+
+        const CHUNK_LEN: usize = 64 * 1024;
+
+        if line == 0 {
+            return Ok(())
+        }
+
+        let (offset, mut skip) = match index {
+            Some(index) => index.position(line),
+            None => (0, line),
+        };
+        self.file.seek(std::io::SeekFrom::Start(offset))?;
+        self.read = offset;
+        self.bom_handled = true;
+
+        // 長い行でメモリを使いすぎないよう、一定の長さずつ読み捨てる
+        let lf = self.line_breaks.lf;
+        let unit = lf.len();
+        let mut buf = vec![0; CHUNK_LEN + unit];
+        let mut rest = 0;
+        while 0 < skip {
+            let n = self.file.read(&mut buf[rest..(rest + CHUNK_LEN)])?;
+            if n == 0 {
+                break
+            }
+            self.read += n as u64;
+
+            let len = rest + n;
+            let aligned = len / unit * unit;
+            for i in (0..aligned).step_by(unit) {
+                if &buf[i..(i + unit)] == lf {
+                    skip -= 1;
+                    if skip == 0 {
+                        // 読み過ぎた分を戻す
+                        let over = len - (i + unit);
+                        self.file.seek_relative(-(over as i64))?;
+                        self.read -= over as u64;
+                        break
+                    }
+                }
+            }
+
+            buf.copy_within(aligned..len, 0);
+            rest = len - aligned;
+        }
+        Ok(())
     }
 
     /// この関数が返す bytes は以下の形式のレコードが連続したものであり、
@@ -707,7 +1341,7 @@ impl FileTextLinesReader {
             let nread = read_until_bytes(
                 &mut self.file.by_ref().take(nlimit),
                 &mut buf,
-                &self.line_breaks.lf
+                self.line_breaks.lf
             )?;
                     
             self.read += nread as u64;
@@ -717,15 +1351,27 @@ impl FileTextLinesReader {
                 break
             }
 
+            // 追従する場合、EOF で途切れた改行のない行は書き込み途中かもしれないため、
+            // 読み込まなかったことにして改行が追記されるのを待つ。
+            if self.follow.is_some() 
+                && (nread as u64) < nlimit 
+                && !buf[line_offset..].ends_with(self.line_breaks.lf) 
+            {
+                self.file.seek_relative(-(nread as i64))?;
+                self.read -= nread as u64;
+                buf.truncate(offset);
+                break
+            }
+
             let mut line_len = nread;
             let mut line_break_type = LINE_BREAK_NULL;
 
             // 最後が EOL ('\n', '\r\n') で終わっていれば削除する。
-            if self.line_breaks.lf.len() <= line_len && buf.ends_with(&self.line_breaks.lf) {
+            if self.line_breaks.lf.len() <= line_len && buf.ends_with(self.line_breaks.lf) {
                 buf.truncate(buf.len() - self.line_breaks.lf.len());
                 line_len -= self.line_breaks.lf.len();
                 line_break_type = LINE_BREAK_LF;
-                if self.line_breaks.cr.len() <= line_len && buf.ends_with(&self.line_breaks.cr) {
+                if self.line_breaks.cr.len() <= line_len && buf.ends_with(self.line_breaks.cr) {
                     buf.truncate(buf.len() - self.line_breaks.cr.len());
                     line_len -= self.line_breaks.cr.len();
                     line_break_type = LINE_BREAK_CRLF;
@@ -781,8 +1427,353 @@ impl FileTextLinesReader {
     }
 }
 
-/// label は `(new TextDecoder(encoding)).encoding` などで正規化された小文字のテキスト
+/// [`FileTextLinesReader::read_lines_framed`] と同じ形式で行を連結する。
 #[cfg(target_os = "android")]
+pub fn frame_raw_lines(lines: Vec<RawLine>) -> Vec<u8> {
+    const FLAG_OK: u8 = 0;
+    const LINE_BREAK_NULL: u8 = 0;
+    const LINE_BREAK_LF: u8 = 1;
+    const LINE_BREAK_CRLF: u8 = 2;
+
+    let len = lines.iter().map(|l| 10 + l.bytes.len()).sum();
+    let mut buf = Vec::with_capacity(len);
+    for line in lines {
+        let line_break_type = match line.line_break {
+            RawLineBreak::None => LINE_BREAK_NULL,
+            RawLineBreak::Lf => LINE_BREAK_LF,
+            RawLineBreak::Crlf => LINE_BREAK_CRLF,
+        };
+
+        buf.push(FLAG_OK);
+        buf.push(line_break_type);
+        buf.extend_from_slice(&u64::to_be_bytes(line.bytes.len() as u64));
+        buf.extend_from_slice(&line.bytes);
+    }
+    buf
+}
+
+#[cfg(any(target_os = "android", all(test, unix, afs_impls)))]
+#[cfg_attr(not(target_os = "android"), allow(unused))]
+pub struct FileDelimitedRecordsReader {
+    file: std::io::BufReader<std::fs::File>,
+    max_record_len: Option<std::num::NonZeroU64>,
+
+    /// CSV の場合はフィールドの区切り、そうでない場合はレコードの区切り
+    delimiter: Vec<u8>,
+    csv: Option<CsvUnits>,
+
+    /// エンコーディングの符号単位のバイト数。
+    /// 区切りはこの単位に揃った位置でのみ検出する。
+    unit_len: usize,
+    bom: Option<&'static [u8]>,
+    bom_handled: bool,
+}
+
+#[cfg(any(target_os = "android", all(test, unix, afs_impls)))]
+#[cfg_attr(not(target_os = "android"), allow(unused))]
+struct CsvUnits {
+    quote: Vec<u8>,
+    cr: Vec<u8>,
+    lf: Vec<u8>,
+}
+
+#[cfg(any(target_os = "android", all(test, unix, afs_impls)))]
+#[cfg_attr(not(target_os = "android"), allow(unused))]
+struct DelimitedRecord {
+    fields: Vec<Vec<u8>>,
+    terminator: u8,
+}
+
+#[cfg(any(target_os = "android", all(test, unix, afs_impls)))]
+#[cfg_attr(not(target_os = "android"), allow(unused))]
+#[derive(PartialEq, Eq)]
+enum CsvState {
+    FieldStart,
+    Unquoted,
+    Quoted,
+    QuoteInQuoted,
+}
+
+#[cfg(any(target_os = "android", all(test, unix, afs_impls)))]
+#[cfg_attr(not(target_os = "android"), allow(unused))]
+impl FileDelimitedRecordsReader {
+
+    const TERMINATOR_NULL: u8 = 0;
+    const TERMINATOR_DELIMITER_OR_LF: u8 = 1;
+    const TERMINATOR_CRLF: u8 = 2;
+
+    /// label は `(new TextDecoder(encoding)).encoding` などで正規化された小文字のテキスト
+    pub fn new(
+        file: std::fs::File,
+        label: &str,
+        delimiter: RecordDelimiter,
+        csv: bool,
+        max_record_len: Option<std::num::NonZeroU64>,
+        bom: Option<&'static [u8]>,
+    ) -> Result<Self> {
+
+        let encode = |text: &str| -> Result<Vec<u8>> {
+            let mut encoder = TextEncoder::new(Some(label), None)?;
+            let mut bytes = encoder.encode(text.as_bytes())?;
+            bytes.extend_from_slice(&encoder.finish()?);
+            Ok(bytes)
+        };
+
+        let unit_len = match label {
+            "utf-16le" | "utf-16be" => 2,
+            _ => 1,
+        };
+
+        let delimiter = match delimiter {
+            RecordDelimiter::Text { text } => encode(&text)?,
+            RecordDelimiter::Bytes { bytes } => bytes,
+        };
+        if delimiter.is_empty() {
+            return Err(Error::with("delimiter must not be empty"))
+        }
+
+        let csv = match csv {
+            true => {
+                // ISO-2022-JP は 2 バイト文字の中に '"' や ',' と同じバイトが現れるため、バイト単位では解析できない
+                if label == "iso-2022-jp" {
+                    return Err(Error::with("CSV is not supported for ISO-2022-JP"))
+                }
+                if delimiter.len() != unit_len {
+                    return Err(Error::with("CSV delimiter must be a single ASCII character"))
+                }
+
+                let line_breaks = line_breaks_for_encoding_label(label);
+                Some(CsvUnits {
+                    quote: encode("\"")?,
+                    cr: line_breaks.cr.to_vec(),
+                    lf: line_breaks.lf.to_vec(),
+                })
+            },
+            false => None,
+        };
+
+        Ok(Self {
+            file: std::io::BufReader::new(file),
+            max_record_len,
+            delimiter,
+            csv,
+            unit_len,
+            bom,
+            bom_handled: false,
+        })
+    }
+
+    /// この関数が返す bytes は以下の形式のレコードが連続したものであり、
+    /// 各レコードが分断されることはない。
+    /// 
+    /// - err flag (u8, 0 = ok, 1 = err)
+    /// - terminator type (u8, 0 = null, 1 = delimiter or '\n', 2 = '\r\n')
+    /// - field count (u64, big endian)
+    /// - 以下をフィールドの数だけ繰り返す
+    ///     - field bytes len (u64, big endian)
+    ///     - field bytes (variable bytes)
+    /// 
+    /// CSV でない場合、フィールドはレコード全体の 1 つのみとなる。
+    /// 
+    /// err flag が 1 の場合、そのレコードでエラーが発生したことを示す。
+    /// その場合、フィールドは utf-8 形式のエラーメッセージの 1 つのみであり、
+    /// この呼び出しでの最後のレコードとなる。
+    /// 
+    /// エラー発生後の呼び出しの挙動は未定義。
+    pub fn read_records_framed(&mut self, threshold: u64) -> Result<Vec<u8>> {
+        const FLAG_OK: u8 = 0;
+        const FLAG_ERR: u8 = 1;
+
+        fn push_frame(buf: &mut Vec<u8>, err_flag: u8, terminator: u8, fields: &[Vec<u8>]) {
+            buf.push(err_flag);
+            buf.push(terminator);
+            buf.extend_from_slice(&u64::to_be_bytes(fields.len() as u64));
+            for field in fields {
+                buf.extend_from_slice(&u64::to_be_bytes(field.len() as u64));
+                buf.extend_from_slice(field);
+            }
+        }
+
+        let mut buf = Vec::with_capacity(usize::min(threshold as usize, 2 * 1024 * 1024));
+        loop {
+            let record = match self.csv.is_some() {
+                true => self.read_csv_record(),
+                false => self.read_record(),
+            };
+
+            match record {
+                Ok(Some(record)) => {
+                    push_frame(&mut buf, FLAG_OK, record.terminator, &record.fields);
+
+                    if threshold <= (buf.len() as u64) {
+                        break
+                    }
+                },
+                Ok(None) => break,
+                Err(err) => {
+                    let err_msg_bytes = err.to_string().into_bytes();
+                    push_frame(&mut buf, FLAG_ERR, Self::TERMINATOR_NULL, &[err_msg_bytes]);
+                    break
+                },
+            }
+        }
+
+        Ok(buf)
+    }
+
+    fn read_record(&mut self) -> Result<Option<DelimitedRecord>> {
+        self.skip_bom()?;
+
+        // 区切りの途中かもしれないため、制限の判定に含めないバイト数
+        let tail_len = self.delimiter.len().saturating_sub(self.unit_len);
+        let mut record = Vec::new();
+        let mut unit = Vec::with_capacity(self.unit_len);
+
+        loop {
+            if !read_unit(&mut self.file, self.unit_len, &mut unit)? {
+                return Ok(match record.is_empty() {
+                    true => None,
+                    false => Some(DelimitedRecord { 
+                        fields: vec![record], 
+                        terminator: Self::TERMINATOR_NULL 
+                    }),
+                })
+            }
+
+            record.extend_from_slice(&unit);
+
+            if record.ends_with(&self.delimiter) && (record.len() - self.delimiter.len()) % self.unit_len == 0 {
+                record.truncate(record.len() - self.delimiter.len());
+                return Ok(Some(DelimitedRecord { 
+                    fields: vec![record], 
+                    terminator: Self::TERMINATOR_DELIMITER_OR_LF 
+                }))
+            }
+
+            self.check_record_len(record.len().saturating_sub(tail_len))?;
+        }
+    }
+
+    /// RFC 4180 に従って 1 レコードを読み込む。
+    /// ただし、改行として LF のみも受け付け、閉じた引用符の後の文字はそのままフィールドに含める。
+    fn read_csv_record(&mut self) -> Result<Option<DelimitedRecord>> {
+        self.skip_bom()?;
+
+        let csv = self.csv.as_ref().ok_or_else(|| Error::with("not CSV"))?;
+        let mut fields = Vec::new();
+        let mut field = Vec::new();
+        let mut state = CsvState::FieldStart;
+        let mut pending_cr = false;
+        let mut record_len = 0;
+        let mut unit = Vec::with_capacity(self.unit_len);
+
+        loop {
+            if !read_unit(&mut self.file, self.unit_len, &mut unit)? {
+                if record_len == 0 {
+                    return Ok(None)
+                }
+                if state == CsvState::Quoted {
+                    return Err(Error::with("unterminated quoted field in CSV"))
+                }
+                if pending_cr {
+                    field.extend_from_slice(&csv.cr);
+                }
+                fields.push(field);
+                return Ok(Some(DelimitedRecord { fields, terminator: Self::TERMINATOR_NULL }))
+            }
+
+            record_len += unit.len();
+
+            if std::mem::take(&mut pending_cr) {
+                if unit == csv.lf {
+                    fields.push(field);
+                    return Ok(Some(DelimitedRecord { fields, terminator: Self::TERMINATOR_CRLF }))
+                }
+                field.extend_from_slice(&csv.cr);
+                state = CsvState::Unquoted;
+            }
+
+            match state {
+                CsvState::Quoted => {
+                    match unit == csv.quote {
+                        true => state = CsvState::QuoteInQuoted,
+                        false => field.extend_from_slice(&unit),
+                    }
+                },
+                CsvState::QuoteInQuoted if unit == csv.quote => {
+                    field.extend_from_slice(&unit);
+                    state = CsvState::Quoted;
+                },
+                CsvState::FieldStart if unit == csv.quote => {
+                    state = CsvState::Quoted;
+                },
+                _ => {
+                    if unit == self.delimiter {
+                        fields.push(std::mem::take(&mut field));
+                        state = CsvState::FieldStart;
+                    }
+                    else if unit == csv.lf {
+                        fields.push(field);
+                        return Ok(Some(DelimitedRecord { fields, terminator: Self::TERMINATOR_DELIMITER_OR_LF }))
+                    }
+                    else if unit == csv.cr {
+                        pending_cr = true;
+                    }
+                    else {
+                        field.extend_from_slice(&unit);
+                        state = CsvState::Unquoted;
+                    }
+                },
+            }
+
+            // CRLF の CR かもしれないものは制限に含めない
+            let pending_len = if pending_cr { unit.len() } else { 0 };
+            self.check_record_len(record_len - pending_len)?;
+        }
+    }
+
+    fn skip_bom(&mut self) -> Result<()> {
+        use std::io::BufRead as _;
+
+        if !self.bom_handled {
+            self.bom_handled = true;
+            if let Some(bom) = self.bom {
+                if self.file.fill_buf()?.starts_with(bom) {
+                    self.file.consume(bom.len());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn check_record_len(&self, len: usize) -> Result<()> {
+        if self.max_record_len.is_some_and(|i| i.get() < len as u64) {
+            return Err(Error::with("record length limit exceeded"));
+        }
+        Ok(())
+    }
+}
+
+/// 1 符号単位を読み込む。EOF の場合は false を返す。
+/// ファイルの末尾が符号単位の途中で終わっている場合は、その残りのバイトを 1 単位とする。
+#[cfg(any(target_os = "android", all(test, unix, afs_impls)))]
+#[cfg_attr(not(target_os = "android"), allow(unused))]
+fn read_unit(
+    r: &mut impl std::io::BufRead,
+    unit_len: usize,
+    unit: &mut Vec<u8>,
+) -> Result<bool> {
+
+    use std::io::Read as _;
+
+    unit.clear();
+    r.by_ref().take(unit_len as u64).read_to_end(unit)?;
+    Ok(!unit.is_empty())
+}
+
+/// label は `(new TextDecoder(encoding)).encoding` などで正規化された小文字のテキスト
+#[cfg(any(target_os = "android", all(test, unix, afs_impls)))]
+#[cfg_attr(not(target_os = "android"), allow(unused))]
 pub fn bom_for_encoding_label(label: &str) -> Option<&'static [u8]> {
     // WEB 標準で定義されているエンコーディングのうち
     // UTF-8, UTF-16 LE/BE のみが BOM を持つ。
@@ -794,14 +1785,16 @@ pub fn bom_for_encoding_label(label: &str) -> Option<&'static [u8]> {
     }
 }
 
-#[cfg(target_os = "android")]
+#[cfg(any(target_os = "android", all(test, unix)))]
+#[cfg_attr(not(target_os = "android"), allow(unused))]
 pub struct LineBreaks {
     pub lf: &'static [u8],
     pub cr: &'static [u8],
 }
 
 /// label は `(new TextDecoder(encoding)).encoding` などで正規化された小文字のテキスト
-#[cfg(target_os = "android")]
+#[cfg(any(target_os = "android", all(test, unix)))]
+#[cfg_attr(not(target_os = "android"), allow(unused))]
 pub fn line_breaks_for_encoding_label(label: &str) -> LineBreaks {
     // WEB 標準で定義されているエンコーディングのうち
     // UTF-16 LE/BE, ISO 2022-JP が ASCII 互換ではない。
@@ -816,13 +1809,183 @@ pub fn line_breaks_for_encoding_label(label: &str) -> LineBreaks {
             cr: &[0x00, 0x0D],
         },
         _ => LineBreaks {
-            lf: &[b'\n'],
-            cr: &[b'\r'],
+            lf: b"\n",
+            cr: b"\r",
         },
     }
 }
 
-#[cfg(target_os = "android")]
+/// 書き込みストリームに渡された UTF-8 テキストを、
+/// 指定されたエンコーディング、改行コードのバイト列に変換する。
+/// BOM は出力しないため、必要な場合は [`TextEncoder::bom`] を最初に書き込む。
+#[cfg(any(target_os = "android", all(test, unix, afs_impls)))]
+#[cfg_attr(not(target_os = "android"), allow(unused))]
+pub struct TextEncoder {
+    encoding: &'static encoding_rs::Encoding,
+    inner: TextEncoderInner,
+    line_ending: Option<LineEnding>,
+
+    /// 直前のチャンクが CR で終わっていた場合は true。
+    /// CRLF がチャンクを跨いでいる可能性があるため、次のチャンクまで出力を保留する。
+    pending_cr: bool,
+}
+
+#[cfg(any(target_os = "android", all(test, unix, afs_impls)))]
+#[cfg_attr(not(target_os = "android"), allow(unused))]
+enum TextEncoderInner {
+    // WEB 標準では UTF-16 はデコードのみが定義されており、
+    // encoding_rs もエンコーダーを UTF-8 として扱うため自前で変換する。
+    Utf16Le,
+    Utf16Be,
+    Other(encoding_rs::Encoder),
+}
+
+#[cfg(any(target_os = "android", all(test, unix, afs_impls)))]
+#[cfg_attr(not(target_os = "android"), allow(unused))]
+impl TextEncoder {
+
+    /// - `label`: WHATWG のエンコーディングラベル。None の場合は UTF-8。
+    /// - `line_ending`: 改行コードをこれに統一する。None の場合は変換しない。
+    pub fn new(
+        label: Option<&str>,
+        line_ending: Option<LineEnding>,
+    ) -> Result<Self> {
+
+        let encoding = match label {
+            Some(label) => encoding_for_label(label)?,
+            None => encoding_rs::UTF_8,
+        };
+
+        let inner = if encoding == encoding_rs::UTF_16LE {
+            TextEncoderInner::Utf16Le
+        }
+        else if encoding == encoding_rs::UTF_16BE {
+            TextEncoderInner::Utf16Be
+        }
+        else {
+            TextEncoderInner::Other(encoding.new_encoder())
+        };
+
+        Ok(Self { encoding, inner, line_ending, pending_cr: false })
+    }
+
+    /// エンコーディングの BOM を返す。BOM を持たないエンコーディングの場合はエラー。
+    pub fn bom(&self) -> Result<&'static [u8]> {
+        bom_for_encoding_label(&self.encoding.name().to_ascii_lowercase())
+            .ok_or_else(|| Error::with(format!("{} has no BOM", self.encoding.name())))
+    }
+
+    /// UTF-8 のテキストを変換する。
+    pub fn encode(&mut self, text: &[u8]) -> Result<Vec<u8>> {
+        self.encode_str(std::str::from_utf8(text)?, false)
+    }
+
+    /// 保留中の出力を返す。この後に [`TextEncoder::encode`] を呼んではいけない。
+    pub fn finish(&mut self) -> Result<Vec<u8>> {
+        self.encode_str("", true)
+    }
+
+    fn encode_str(&mut self, text: &str, last: bool) -> Result<Vec<u8>> {
+        let text = self.normalize_line_endings(text, last);
+        let mut out = Vec::with_capacity(text.len());
+
+        match &mut self.inner {
+            TextEncoderInner::Utf16Le => {
+                for u in text.encode_utf16() {
+                    out.extend_from_slice(&u.to_le_bytes());
+                }
+            },
+            TextEncoderInner::Utf16Be => {
+                for u in text.encode_utf16() {
+                    out.extend_from_slice(&u.to_be_bytes());
+                }
+            },
+            TextEncoderInner::Other(encoder) => {
+                let mut src = text.as_ref();
+
+                loop {
+                    let additional = encoder
+                        .max_buffer_length_from_utf8_without_replacement(src.len())
+                        .unwrap_or(src.len());
+
+                    out.reserve(additional);
+
+                    let (result, read) = encoder.encode_from_utf8_to_vec_without_replacement(src, &mut out, last);
+                    src = &src[read..];
+
+                    match result {
+                        encoding_rs::EncoderResult::InputEmpty => break,
+                        encoding_rs::EncoderResult::OutputFull => continue,
+                        encoding_rs::EncoderResult::Unmappable(c) => {
+                            return Err(Error::with(format!(
+                                "cannot encode {c:?} (U+{:04X}) in {}",
+                                c as u32,
+                                encoder.encoding().name()
+                            )))
+                        },
+                    }
+                }
+            },
+        }
+
+        Ok(out)
+    }
+
+    fn normalize_line_endings<'a>(&mut self, text: &'a str, last: bool) -> std::borrow::Cow<'a, str> {
+        let Some(line_ending) = self.line_ending else {
+            return std::borrow::Cow::Borrowed(text)
+        };
+        let new_line = match line_ending {
+            LineEnding::Lf => "\n",
+            LineEnding::Crlf => "\r\n",
+        };
+
+        let mut out = String::with_capacity(text.len() + 16);
+        let mut rest = text;
+
+        if std::mem::take(&mut self.pending_cr) {
+            match rest.strip_prefix('\n') {
+                Some(r) => {
+                    out.push_str(new_line);
+                    rest = r;
+                },
+                None if rest.is_empty() && !last => {
+                    self.pending_cr = true;
+                },
+                None => out.push('\r'),
+            }
+        }
+
+        while let Some(i) = rest.find(['\r', '\n']) {
+            out.push_str(&rest[..i]);
+
+            if rest.as_bytes()[i] == b'\n' {
+                out.push_str(new_line);
+                rest = &rest[(i + 1)..];
+            }
+            else if rest[(i + 1)..].starts_with('\n') {
+                out.push_str(new_line);
+                rest = &rest[(i + 2)..];
+            }
+            else if i + 1 == rest.len() && !last {
+                // CRLF がチャンクを跨いでいるかもしれない
+                self.pending_cr = true;
+                rest = "";
+            }
+            else {
+                // 単独の CR は改行として扱わない
+                out.push('\r');
+                rest = &rest[(i + 1)..];
+            }
+        }
+
+        out.push_str(rest);
+        std::borrow::Cow::Owned(out)
+    }
+}
+
+#[cfg(any(target_os = "android", all(test, unix)))]
+#[cfg_attr(not(target_os = "android"), allow(unused))]
 fn read_until_bytes(
     r: &mut impl std::io::BufRead,
     buf: &mut Vec<u8>,
@@ -846,7 +2009,7 @@ fn read_until_bytes(
     }
 }
 
-#[cfg(target_os = "android")]
+#[cfg(any(target_os = "android", test))]
 pub fn convert_rid_to_bytes(rid: tauri::ResourceId) -> Vec<u8> {
     rid.to_be_bytes().to_vec()
 }
@@ -1063,4 +2226,304 @@ impl tauri::ipc::ScopeObject for AfsScope {
             Err(err) => Err(err.into()),
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    /// 内容が `contents` の一時ファイルを作成して開く。
+    fn temp_file(name: &str, contents: &[u8]) -> std::fs::File {
+        let path = std::env::temp_dir().join(format!("tauri-plugin-android-fs-{}-{name}", std::process::id()));
+        std::fs::write(&path, contents).unwrap();
+        let file = std::fs::OpenOptions::new().read(true).write(true).open(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        file
+    }
+
+    #[test]
+    fn test_file_chunk_reader_read_at_and_seek() {
+        let mut reader = FileChunkReader::new(temp_file("chunk", b"0123456789"), None);
+
+        assert_eq!(reader.read_chunk(3).unwrap(), b"012");
+        // ReadAt は現在の位置を変えない
+        assert_eq!(reader.read_chunk_at(7, 10).unwrap(), b"789");
+        assert_eq!(reader.read_chunk_at(10, 1).unwrap(), b"");
+        assert_eq!(reader.read_chunk(2).unwrap(), b"34");
+
+        assert_eq!(reader.seek(-2, SeekWhence::End).unwrap(), 8);
+        assert_eq!(reader.read_chunk(10).unwrap(), b"89");
+        assert_eq!(reader.seek(-5, SeekWhence::Current).unwrap(), 5);
+        assert_eq!(reader.seek(1, SeekWhence::Start).unwrap(), 1);
+        assert_eq!(reader.read_chunk(1).unwrap(), b"1");
+        assert!(reader.seek(-1, SeekWhence::Start).is_err());
+    }
+
+    #[test]
+    fn test_file_chunk_reader_read_limit() {
+        use std::io::Seek as _;
+
+        let mut file = temp_file("chunk-limit", b"0123456789");
+        file.seek(std::io::SeekFrom::Start(2)).unwrap();
+        // 2..6 のみ読み込める
+        let mut reader = FileChunkReader::new(file, Some(4));
+
+        assert_eq!(reader.read_chunk_at(0, 10).unwrap(), b"012345");
+        assert_eq!(reader.read_chunk_at(4, 10).unwrap(), b"45");
+        assert_eq!(reader.read_chunk_at(6, 10).unwrap(), b"");
+        assert_eq!(reader.read_chunk(3).unwrap(), b"234");
+        assert_eq!(reader.read_chunk(3).unwrap(), b"5");
+        assert_eq!(reader.read_chunk(3).unwrap(), b"");
+
+        // シーク後も同じ位置で終わる
+        assert_eq!(reader.seek(3, SeekWhence::Start).unwrap(), 3);
+        assert_eq!(reader.read_chunk(10).unwrap(), b"345");
+        assert_eq!(reader.seek(0, SeekWhence::End).unwrap(), 10);
+        assert_eq!(reader.read_chunk(10).unwrap(), b"");
+    }
+
+    #[test]
+    fn test_file_chunk_writer_write_at_seek_and_set_len() {
+        use std::os::unix::fs::FileExt as _;
+
+        let read = |writer: &FileChunkWriter| {
+            let len = writer.file().metadata().unwrap().len() as usize;
+            let mut buf = vec![0; len];
+            writer.file().read_exact_at(&mut buf, 0).unwrap();
+            buf
+        };
+        let mut writer = FileChunkWriter::new(temp_file("writer", b""), false);
+
+        writer.write_chunk(b"abc").unwrap();
+        // WriteAt は現在の位置を変えず、末尾より後ろの隙間は 0 で埋められる
+        writer.write_chunk_at(5, b"xy").unwrap();
+        writer.write_chunk(b"d").unwrap();
+        assert_eq!(read(&writer), b"abcd\0xy");
+
+        assert_eq!(writer.seek(1, SeekWhence::Start).unwrap(), 1);
+        writer.write_chunk(b"B").unwrap();
+        assert_eq!(writer.seek(-1, SeekWhence::End).unwrap(), 6);
+        writer.write_chunk(b"Y").unwrap();
+        assert_eq!(writer.seek(-3, SeekWhence::Current).unwrap(), 4);
+        assert_eq!(read(&writer), b"aBcd\0xY");
+
+        // SetLen は現在の位置を変えない
+        writer.set_len(3).unwrap();
+        assert_eq!(read(&writer), b"aBc");
+        writer.write_chunk(b"e").unwrap();
+        assert_eq!(read(&writer), b"aBc\0e");
+        writer.set_len(7).unwrap();
+        assert_eq!(read(&writer), b"aBc\0e\0\0");
+    }
+
+    #[test]
+    fn test_file_chunk_writer_append() {
+        use std::os::unix::fs::FileExt as _;
+
+        let path = std::env::temp_dir().join(format!("tauri-plugin-android-fs-{}-writer-append", std::process::id()));
+        std::fs::write(&path, b"abc").unwrap();
+        let file = std::fs::OpenOptions::new().append(true).open(&path).unwrap();
+        let mut writer = FileChunkWriter::new(file, true);
+
+        // O_APPEND では位置を指定しても末尾に書き込まれるので、エラーとする
+        assert!(writer.write_chunk_at(0, b"x").is_err());
+        assert!(writer.seek(0, SeekWhence::Start).is_err());
+        writer.write_chunk(b"de").unwrap();
+        writer.set_len(4).unwrap();
+        writer.write_chunk(b"f").unwrap();
+
+        let mut buf = [0; 5];
+        std::fs::File::open(&path).unwrap().read_exact_at(&mut buf, 0).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(&buf, b"abcdf");
+    }
+
+    #[test]
+    fn test_read_file_stream_open_layout() {
+        use tauri::ipc::IpcResponse as _;
+
+        let bytes = |output: ReadFileStreamEventOutput| {
+            let response = tauri::ipc::Response::try_from(output).unwrap();
+            match response.body().unwrap() {
+                tauri::ipc::InvokeResponseBody::Raw(bytes) => bytes,
+                body => panic!("unexpected body: {body:?}"),
+            }
+        };
+
+        assert_eq!(
+            bytes(ReadFileStreamEventOutput::Open { id: 0x01020304, position: 5, len: Some(0x0a0b) }),
+            [
+                [1, 2, 3, 4].as_slice(),
+                &[0, 0, 0, 0, 0, 0, 0, 5],
+                &[1],
+                &[0, 0, 0, 0, 0, 0, 0x0a, 0x0b],
+            ].concat()
+        );
+        assert_eq!(
+            bytes(ReadFileStreamEventOutput::Open { id: 7, position: 0, len: None }),
+            [
+                [0, 0, 0, 7].as_slice(),
+                &[0; 8],
+                &[0],
+                &[0; 8],
+            ].concat()
+        );
+        assert_eq!(bytes(ReadFileStreamEventOutput::Seek(9)), [0, 0, 0, 0, 0, 0, 0, 9]);
+    }
+
+    #[cfg(afs_impls)]
+    #[test]
+    fn test_text_encoder_bom_and_utf16() {
+        let mut encoder = TextEncoder::new(Some("utf-16le"), None).unwrap();
+        assert_eq!(encoder.bom().unwrap(), b"\xFF\xFE");
+        // BOM は出力に含まれない
+        assert_eq!(encoder.encode("aあ".as_bytes()).unwrap(), b"a\x00\x42\x30");
+        assert_eq!(encoder.finish().unwrap(), b"");
+
+        let mut encoder = TextEncoder::new(Some("utf-16be"), None).unwrap();
+        assert_eq!(encoder.bom().unwrap(), b"\xFE\xFF");
+        assert_eq!(encoder.encode("a😀".as_bytes()).unwrap(), b"\x00a\xD8\x3D\xDE\x00");
+
+        assert_eq!(TextEncoder::new(None, None).unwrap().bom().unwrap(), b"\xEF\xBB\xBF");
+
+        let mut encoder = TextEncoder::new(Some("shift_jis"), None).unwrap();
+        assert!(encoder.bom().is_err());
+        assert!(encoder.encode("😀".as_bytes()).is_err());
+    }
+
+    #[cfg(afs_impls)]
+    #[test]
+    fn test_text_encoder_line_endings() {
+        let encode_all = |line_ending, chunks: &[&str]| {
+            let mut encoder = TextEncoder::new(Some("utf-8"), Some(line_ending)).unwrap();
+            let mut out = Vec::new();
+            for chunk in chunks {
+                out.extend(encoder.encode(chunk.as_bytes()).unwrap());
+            }
+            out.extend(encoder.finish().unwrap());
+            String::from_utf8(out).unwrap()
+        };
+
+        assert_eq!(encode_all(LineEnding::Crlf, &["a\nb\r\nc\rd"]), "a\r\nb\r\nc\rd");
+        assert_eq!(encode_all(LineEnding::Lf, &["a\r\nb\nc\r"]), "a\nb\nc\r");
+
+        // チャンクを跨ぐ CRLF も 1 つの改行として扱う
+        assert_eq!(encode_all(LineEnding::Crlf, &["a\r", "\nb"]), "a\r\nb");
+        assert_eq!(encode_all(LineEnding::Lf, &["a\r", "", "\nb"]), "a\nb");
+        assert_eq!(encode_all(LineEnding::Lf, &["a\r", "b\r"]), "a\rb\r");
+
+        let mut encoder = TextEncoder::new(Some("utf-16le"), Some(LineEnding::Crlf)).unwrap();
+        assert_eq!(encoder.encode(b"a\n").unwrap(), b"a\x00\x0D\x00\x0A\x00");
+    }
+
+    #[cfg(afs_impls)]
+    #[test]
+    fn test_delimited_records_reader_csv() {
+        // (err flag, terminator, fields) の列に戻す
+        fn parse(mut bytes: &[u8]) -> Vec<(u8, u8, Vec<Vec<u8>>)> {
+            fn take<'a>(bytes: &mut &'a [u8], len: usize) -> &'a [u8] {
+                let (head, rest) = bytes.split_at(len);
+                *bytes = rest;
+                head
+            }
+            fn take_len(bytes: &mut &[u8]) -> usize {
+                u64::from_be_bytes(take(bytes, 8).try_into().unwrap()) as usize
+            }
+
+            let mut records = Vec::new();
+            while !bytes.is_empty() {
+                let header = take(&mut bytes, 2);
+                let fields = (0..take_len(&mut bytes))
+                    .map(|_| {
+                        let len = take_len(&mut bytes);
+                        take(&mut bytes, len).to_vec()
+                    })
+                    .collect();
+                records.push((header[0], header[1], fields));
+            }
+            records
+        }
+        let read = |name, contents: &[u8], label| {
+            let mut reader = FileDelimitedRecordsReader::new(
+                temp_file(name, contents),
+                label,
+                RecordDelimiter::Text { text: ",".into() },
+                true,
+                None,
+                bom_for_encoding_label(label),
+            ).unwrap();
+            parse(&reader.read_records_framed(u64::MAX).unwrap())
+        };
+        let utf16le = |text: &str| text.encode_utf16().flat_map(u16::to_le_bytes).collect::<Vec<_>>();
+        let fields = |fields: &[&str]| fields.iter().map(|f| f.as_bytes().to_vec()).collect::<Vec<_>>();
+
+        assert_eq!(
+            read("csv", b"\xEF\xBB\xBFa,\"b,c\",\"d\"\"e\"\r\n\"f\ng\",h\nx\ry,", "utf-8"),
+            [
+                (0, 2, fields(&["a", "b,c", "d\"e"])),
+                (0, 1, fields(&["f\ng", "h"])),
+                (0, 0, fields(&["x\ry", ""])),
+            ]
+        );
+
+        assert_eq!(
+            read("csv-utf16", &utf16le("a,\"b,\r\nc\"\r\n"), "utf-16le"),
+            [(0, 2, vec![utf16le("a"), utf16le("b,\r\nc")])]
+        );
+
+        // 閉じられていない引用符はエラーとなり、それまでのレコードは読み込まれる
+        assert_eq!(
+            read("csv-unterminated", b"a,b\n\"c,d\ne", "utf-8"),
+            [
+                (0, 1, fields(&["a", "b"])),
+                (1, 0, fields(&["unterminated quoted field in CSV"])),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_text_lines_reader_restart_if_truncated_or_replaced() {
+        use std::os::unix::fs::FileExt as _;
+
+        // (line break type, line) の列に戻す
+        fn parse(bytes: &[u8]) -> Vec<(u8, Vec<u8>)> {
+            let mut lines = Vec::new();
+            let mut rest = bytes;
+            while !rest.is_empty() {
+                assert_eq!(rest[0], 0);
+                let len = u64::from_be_bytes(rest[2..10].try_into().unwrap()) as usize;
+                lines.push((rest[1], rest[10..(10 + len)].to_vec()));
+                rest = &rest[(10 + len)..];
+            }
+            lines
+        }
+
+        let file = temp_file("lines-follow", b"a\nb\nc");
+        let writer = file.try_clone().unwrap();
+        let line_breaks = line_breaks_for_encoding_label("utf-8");
+        let mut reader = FileTextLinesReader::new(file, None, line_breaks, None, None);
+        reader.set_follow(LinesFollow {
+            uri: FileUri::from_path("/lines-follow"),
+            interval: std::time::Duration::from_millis(10),
+        });
+
+        // 改行で終わっていない最後の行は改行が追記されるまで読み込まない
+        assert_eq!(parse(&reader.read_lines_framed(u64::MAX).unwrap()), [(1, b"a".to_vec()), (1, b"b".to_vec())]);
+        assert!(!reader.restart_if_truncated().unwrap());
+        writer.write_all_at(b"\n", 5).unwrap();
+        assert_eq!(parse(&reader.read_lines_framed(u64::MAX).unwrap()), [(1, b"c".to_vec())]);
+        assert!(!reader.restart_if_truncated().unwrap());
+
+        // 切り詰められた場合は先頭から読み直す
+        writer.set_len(0).unwrap();
+        writer.write_all_at(b"x\n", 0).unwrap();
+        assert!(reader.restart_if_truncated().unwrap());
+        assert_eq!(parse(&reader.read_lines_framed(u64::MAX).unwrap()), [(1, b"x".to_vec())]);
+
+        // 開き直したファイルが別のファイルであれば、長さに関わらず置き換えられたとみなす
+        assert!(!reader.restart_if_replaced(writer.try_clone().unwrap()).unwrap());
+        assert!(reader.restart_if_replaced(temp_file("lines-follow-new", b"0123456789\n")).unwrap());
+        assert!(!reader.restart_if_truncated().unwrap());
+        assert_eq!(parse(&reader.read_lines_framed(u64::MAX).unwrap()), [(1, b"0123456789".to_vec())]);
+    }
+}
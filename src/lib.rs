@@ -10,6 +10,9 @@ mod utils;
 
 pub mod api;
 
+#[cfg(feature = "custom-backend")]
+pub mod backend;
+
 use utils::*;
 
 pub use api::models::*;
@@ -29,17 +32,62 @@ pub use api::consts::*;
 /// }
 /// ```
 pub fn init<R: tauri::Runtime>() -> tauri::plugin::TauriPlugin<R, Option<config::Config>> {
+    build_plugin(None)
+}
+
+/// Initializes the plugin with the specified backend.  
+/// 
+/// All invocations that would otherwise be sent to the Kotlin side of this plugin 
+/// are handled by `backend` instead, 
+/// so the [`api::api_sync`] and [`api::api_async`] APIs can also be used on non-Android platforms, 
+/// e.g. for unit tests on CI.  
+/// 
+/// This is available only on Unix-like platforms and Android.
+/// On other platforms, `backend` is ignored and all APIs return an error.
+/// 
+/// # Usage
+/// ```no_run
+/// use tauri_plugin_android_fs::backend::HostBackend;
+/// 
+/// tauri::Builder::default()
+///     .plugin(tauri_plugin_android_fs::init_with_backend(HostBackend::new("./sandbox")))
+///     .run(tauri::generate_context!())
+///     .expect("error while running tauri application");
+/// ```
+#[cfg(feature = "custom-backend")]
+pub fn init_with_backend<R: tauri::Runtime>(
+    backend: impl backend::AndroidFsBackend
+) -> tauri::plugin::TauriPlugin<R, Option<config::Config>> {
+
+    build_plugin(Some(api::Handle::with_backend(std::sync::Arc::new(backend))))
+}
+
+fn build_plugin<R: tauri::Runtime>(
+    handle: Option<api::Handle<R>>
+) -> tauri::plugin::TauriPlugin<R, Option<config::Config>> {
+
     let builder = tauri::plugin::Builder::<R, Option<config::Config>>::new("android-fs")
-        .setup(|app, api| {
+        .setup(move |app, api| {
             use tauri::Manager as _;
 
-            #[cfg(target_os = "android")] {
-                let handle = api.register_android_plugin("com.plugin.android_fs", "AndroidFsPlugin")?;
-                let afs_sync = crate::api::api_sync::AndroidFs { handle: handle.clone() };
-                let afs_async = crate::api::api_async::AndroidFs { handle: handle.clone() };
-                app.manage(afs_sync);
-                app.manage(afs_async);
+            let handle = match handle {
+                Some(handle) => handle,
+
+                #[cfg(target_os = "android")]
+                None => api::Handle::with_plugin(
+                    api.register_android_plugin("com.plugin.android_fs", "AndroidFsPlugin")?
+                ),
 
+                #[cfg(not(target_os = "android"))]
+                None => api::Handle::unavailable(),
+            };
+
+            let afs_sync = crate::api::api_sync::AndroidFs { handle: handle.clone() };
+            let afs_async = crate::api::api_async::AndroidFs { handle };
+            app.manage(afs_sync);
+            app.manage(afs_async);
+
+            #[cfg(target_os = "android")] {
                 #[cfg(feature = "commands")] {
                     app.manage(cmds::new_file_stream_resources_state(app.app_handle().clone()));
                     app.manage(cmds::new_file_writer_resources_state(app.app_handle().clone()));
@@ -49,12 +97,6 @@ pub fn init<R: tauri::Runtime>() -> tauri::plugin::TauriPlugin<R, Option<config:
                     app.manage(protocols::new_config_state(api.config().as_ref(), app));
                 }
             }
-            #[cfg(not(target_os = "android"))] {
-                let afs_sync = crate::api::api_sync::AndroidFs::<R> { handle: Default::default() };
-                let afs_async = crate::api::api_async::AndroidFs::<R> { handle: Default::default() };
-                app.manage(afs_sync);
-                app.manage(afs_async);
-            }
 
             Ok(())
         });
@@ -102,6 +144,20 @@ pub fn init<R: tauri::Runtime>() -> tauri::plugin::TauriPlugin<R, Option<config:
             cmds::copy_file,
             cmds::truncate_file,
             cmds::read_dir,
+            cmds::open_read_dir_stream,
+            cmds::walk_dir,
+            cmds::find,
+            cmds::get_dir_stats,
+            cmds::sync_dir,
+            cmds::watch_dir,
+            cmds::unwatch_dir,
+            cmds::find_duplicates,
+            cmds::hash_file,
+            cmds::create_zip,
+            cmds::extract_zip,
+            cmds::open_read_delimited_records_stream,
+            cmds::read_last_lines,
+            cmds::build_line_index,
             cmds::rename_file,
             cmds::rename_dir,
             cmds::remove_file,
@@ -1,11 +1,20 @@
 #[allow(unused_imports)]
 use crate::*;
 
-#[cfg(target_os = "android")]
+#[cfg(afs_impls)]
 mod async_sleep;
+#[cfg(afs_impls)]
+mod text;
+#[cfg(afs_impls)]
+mod zip;
 
+#[cfg(afs_impls)]
+pub use text::*;
+#[cfg(afs_impls)]
+pub use self::zip::*;
 
-#[cfg(target_os = "android")]
+
+#[cfg(afs_impls)]
 #[sync_async::sync_async]
 pub mod utils {
     use super::*;
@@ -127,7 +136,7 @@ pub fn encode_android_uri_component(input: impl AsRef<str>) -> String {
     percent_encoding::utf8_percent_encode(input.as_ref(), SAFE).to_string()
 }
 
-#[cfg(target_os = "android")]
+#[cfg(afs_impls)]
 pub fn range_to_offset_and_len(range: impl std::ops::RangeBounds<u64>) -> (u128, Option<u128>) {
     use std::ops::Bound::{Included, Excluded, Unbounded};
 
@@ -144,12 +153,12 @@ pub fn range_to_offset_and_len(range: impl std::ops::RangeBounds<u64>) -> (u128,
     (offset, len)
 }
 
-#[cfg(target_os = "android")]
+#[cfg(afs_impls)]
 pub fn saturate_u128_to_u64(val: u128) -> u64 {
     u128::min(val, u64::MAX as u128) as u64
 }
 
-#[cfg(target_os = "android")]
+#[cfg(afs_impls)]
 pub fn validate_relative_path(path: &std::path::Path) -> Result<&std::path::Path> {
     for component in path.components() {
         use std::path::Component::*;
@@ -172,7 +181,7 @@ pub fn validate_relative_path(path: &std::path::Path) -> Result<&std::path::Path
 // - https://docs.rs/tokio/1.47.1/src/tokio/util/as_ref.rs.html
 // - Copyright (c) Tokio Contributors
 // - Licensed under the MIT License
-#[cfg(target_os = "android")]
+#[cfg(afs_impls)]
 pub fn upgrade_bytes_ref<B: AsRef<[u8]>>(buf: B) -> Vec<u8> {
 
     // Based on code from Tokio crate ver. 1.47.1
@@ -236,14 +245,14 @@ pub fn upgrade_bytes_ref<B: AsRef<[u8]>>(buf: B) -> Vec<u8> {
     buf.as_ref().to_owned()
 }
 
-#[cfg(target_os = "android")]
+#[cfg(afs_impls)]
 pub struct BoundedHashMap<K, V> {
     map: std::collections::HashMap<K, V>,
     order: std::collections::VecDeque<K>,
     bound: usize,
 }
 
-#[cfg(target_os = "android")]
+#[cfg(afs_impls)]
 impl<K: Eq + std::hash::Hash + Clone, V> BoundedHashMap<K, V> {
 
     pub fn with_bound(bound: usize) -> Self {
@@ -278,4 +287,73 @@ impl<K: Eq + std::hash::Hash + Clone, V> BoundedHashMap<K, V> {
     {
         self.map.get(key)
     }
-}
\ No newline at end of file
+}
+
+/// ディレクトリツリー内の相対パスに対する glob パターン。
+/// `*` はパスの区切りにマッチせず、`**` は0個以上のディレクトリにマッチする。
+#[cfg(afs_impls)]
+pub struct RelativePathPattern {
+    whole: glob::Pattern,
+    components: Vec<Option<glob::Pattern>>,
+}
+
+#[cfg(afs_impls)]
+impl RelativePathPattern {
+
+    const MATCH_OPTIONS: glob::MatchOptions = glob::MatchOptions {
+        case_sensitive: true,
+        require_literal_separator: true,
+        require_literal_leading_dot: false,
+    };
+
+    pub fn new(pattern: &str) -> Result<Self> {
+        let pattern = pattern.trim_start_matches("./");
+        if pattern.is_empty() || pattern.starts_with('/') {
+            return Err(Error::invalid_value(format!("pattern: must be a non-empty relative path: {pattern}")))
+        }
+
+        let invalid_pattern = |e: glob::PatternError| Error::with(format!("invalid pattern: {e}"));
+        let whole = glob::Pattern::new(pattern).map_err(invalid_pattern)?;
+        let components = pattern
+            .split('/')
+            .filter(|c| !c.is_empty())
+            .map(|c| match c {
+                "**" => Ok(None),
+                c => glob::Pattern::new(c).map(Some).map_err(invalid_pattern),
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self { whole, components })
+    }
+
+    pub fn matches(&self, path: &std::path::Path) -> bool {
+        self.whole.matches_path_with(path, Self::MATCH_OPTIONS)
+    }
+
+    /// マッチし得るパスの最大の深さ。`**` を含む場合は None。
+    pub fn max_depth(&self) -> Option<u32> {
+        match self.components.iter().all(Option::is_some) {
+            true => u32::try_from(self.components.len()).ok(),
+            false => None,
+        }
+    }
+
+    /// `dir` 以下にマッチし得るエントリが存在し得るかどうか。
+    pub fn may_match_descendant_of(&self, dir: &std::path::Path) -> bool {
+        let mut patterns = self.components.iter();
+
+        for name in dir.iter() {
+            match patterns.next() {
+                Some(None) => return true,
+                Some(Some(pattern)) => {
+                    if !pattern.matches_with(&name.to_string_lossy(), Self::MATCH_OPTIONS) {
+                        return false
+                    }
+                },
+                None => return false,
+            }
+        }
+
+        patterns.next().is_some()
+    }
+}
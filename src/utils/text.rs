@@ -0,0 +1,293 @@
+use crate::*;
+use std::io::{Read, Seek, SeekFrom};
+
+
+/// エンコーディングにおける改行と BOM のバイト列
+pub struct TextMarkers {
+    pub lf: &'static [u8],
+    pub cr: &'static [u8],
+    pub bom: Option<&'static [u8]>,
+}
+
+impl TextMarkers {
+
+    pub fn for_encoding(encoding: &'static encoding_rs::Encoding) -> Self {
+        // WEB 標準で定義されているエンコーディングのうち
+        // UTF-16 LE/BE のみが ASCII と異なる改行コードを持ち、
+        // UTF-8, UTF-16 LE/BE のみが BOM を持つ。
+        if encoding == encoding_rs::UTF_16LE {
+            Self { lf: &[0x0A, 0x00], cr: &[0x0D, 0x00], bom: Some(b"\xFF\xFE") }
+        }
+        else if encoding == encoding_rs::UTF_16BE {
+            Self { lf: &[0x00, 0x0A], cr: &[0x00, 0x0D], bom: Some(b"\xFE\xFF") }
+        }
+        else if encoding == encoding_rs::UTF_8 {
+            Self { lf: b"\n", cr: b"\r", bom: Some(b"\xEF\xBB\xBF") }
+        }
+        else {
+            Self { lf: b"\n", cr: b"\r", bom: None }
+        }
+    }
+}
+
+/// 改行を除いた 1 行のバイト列
+#[cfg_attr(not(target_os = "android"), allow(unused))]
+pub struct RawLine {
+    pub bytes: Vec<u8>,
+    pub line_break: RawLineBreak,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RawLineBreak {
+    None,
+    Lf,
+    Crlf,
+}
+
+/// WHATWG のエンコーディングラベルからエンコーディングを取得する。
+pub fn encoding_for_label(label: &str) -> Result<&'static encoding_rs::Encoding> {
+    encoding_rs::Encoding::for_label(label.trim().as_bytes())
+        .filter(|e| *e != encoding_rs::REPLACEMENT)
+        .ok_or_else(|| Error::with(format!("unsupported encoding: {label}")))
+}
+
+/// ファイルの末尾から後ろ向きに改行を探し、最後の `n` 行を読み込む。
+///
+/// 読み込むのは最後の `n` 行の分のみであり、ファイル全体を走査することはない。
+/// ファイルの末尾の改行は最後の行の改行として扱い、その後に空の行があるとはみなさない。
+pub fn read_last_lines(
+    file: &mut (impl Read + Seek),
+    n: usize,
+    markers: &TextMarkers,
+) -> Result<Vec<RawLine>> {
+
+    const CHUNK_LEN: u64 = 64 * 1024;
+
+    if n == 0 {
+        return Ok(Vec::new())
+    }
+
+    // 改行はこの単位に揃った位置でのみ検出する
+    let lf = markers.lf;
+    let unit = lf.len() as u64;
+    let len = file.seek(SeekFrom::End(0))?;
+
+    // 最後の行の改行は行の区切りとして数えない
+    let mut end = len;
+    if unit <= len && len % unit == 0 {
+        let mut last = vec![0; lf.len()];
+        file.seek(SeekFrom::Start(len - unit))?;
+        file.read_exact(&mut last)?;
+        if last == lf {
+            end = len - unit;
+        }
+    }
+
+    // 後ろから n 個目の改行の直後が最初の行の先頭となる
+    let mut start = 0;
+    let mut found = 0;
+    let mut pos = end;
+    let mut chunk = Vec::new();
+    'scan: while 0 < pos {
+        let chunk_start = pos.saturating_sub(CHUNK_LEN) / unit * unit;
+        chunk.resize((pos - chunk_start) as usize, 0);
+        file.seek(SeekFrom::Start(chunk_start))?;
+        file.read_exact(&mut chunk)?;
+
+        let candidates = chunk.len().saturating_add(1).saturating_sub(lf.len());
+        for i in (0..candidates).step_by(lf.len()).rev() {
+            if chunk[i..].starts_with(lf) {
+                found += 1;
+                if found == n {
+                    start = chunk_start + i as u64 + unit;
+                    break 'scan
+                }
+            }
+        }
+
+        pos = chunk_start;
+    }
+
+    let mut data = Vec::new();
+    file.seek(SeekFrom::Start(start))?;
+    file.read_to_end(&mut data)?;
+
+    let mut lines = Vec::new();
+    let mut rest = &data[..];
+    if start == 0 {
+        if let Some(bom) = markers.bom {
+            rest = rest.strip_prefix(bom).unwrap_or(rest);
+        }
+    }
+
+    while !rest.is_empty() {
+        let candidates = rest.len().saturating_add(1).saturating_sub(lf.len());
+        let lf_pos = (0..candidates)
+            .step_by(lf.len())
+            .find(|&i| rest[i..].starts_with(lf));
+
+        let Some(i) = lf_pos else {
+            lines.push(RawLine { bytes: rest.to_vec(), line_break: RawLineBreak::None });
+            break
+        };
+
+        let line = &rest[..i];
+        let line = match line.strip_suffix(markers.cr) {
+            Some(line) => RawLine { bytes: line.to_vec(), line_break: RawLineBreak::Crlf },
+            None => RawLine { bytes: line.to_vec(), line_break: RawLineBreak::Lf },
+        };
+        lines.push(line);
+        rest = &rest[(i + lf.len())..];
+    }
+
+    Ok(lines)
+}
+
+/// ファイルの更新日時を UNIX エポックからのミリ秒で返す。取得できない場合は `None`。
+pub fn file_modified_ms(metadata: &std::fs::Metadata) -> Option<u64> {
+    metadata.modified().ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_millis() as u64)
+        .filter(|ms| *ms != 0)
+}
+
+/// ファイルを先頭から 1 度だけ走査し、`interval` 行ごとに行の先頭のバイト位置を記録する。
+///
+/// 行の数え方は [`read_last_lines`] と同じであり、
+/// 改行はエンコーディングの符号単位に揃った位置でのみ検出する。
+pub fn scan_line_index(
+    file: &mut impl Read,
+    interval: u64,
+    markers: &TextMarkers,
+) -> Result<(Vec<u64>, u64, u64)> {
+
+    const CHUNK_LEN: usize = 64 * 1024;
+
+    if interval == 0 {
+        return Err(Error::invalid_value("interval: must not be zero"))
+    }
+
+    let lf = markers.lf;
+    let unit = lf.len();
+
+    let mut offsets = vec![0];
+    let mut line_breaks = 0u64;
+    let mut line_end = 0u64;
+    let mut pos = 0u64;
+    let mut buf = vec![0; CHUNK_LEN + unit];
+    let mut rest = 0;
+    loop {
+        let n = file.read(&mut buf[rest..(rest + CHUNK_LEN)])?;
+        if n == 0 {
+            break
+        }
+
+        // 符号単位の途中で途切れた分は次の読み込みに回す
+        let len = rest + n;
+        let aligned = len / unit * unit;
+        for i in (0..aligned).step_by(unit) {
+            if &buf[i..(i + unit)] == lf {
+                line_breaks += 1;
+                line_end = pos + (i + unit) as u64;
+                if line_breaks % interval == 0 {
+                    offsets.push(line_end);
+                }
+            }
+        }
+
+        buf.copy_within(aligned..len, 0);
+        rest = len - aligned;
+        pos += aligned as u64;
+    }
+
+    let file_len = pos + rest as u64;
+
+    // 末尾の改行の後には行がないため、ファイルの末尾を指す位置は除く
+    if file_len == line_end && 1 < offsets.len() && offsets.last() == Some(&file_len) {
+        offsets.pop();
+    }
+    let line_count = match file_len == line_end {
+        true => line_breaks,
+        false => line_breaks + 1,
+    };
+
+    Ok((offsets, line_count, file_len))
+}
+
+const LINE_INDEX_MAGIC: &[u8; 8] = b"AFSLIDX1";
+
+/// 行の索引をサイドカーファイルの形式に変換する。
+///
+/// 以下を big endian で連結したものであり、
+/// 文字列は u64 の長さとバイト列で表す。
+///
+/// - magic (8 bytes)
+/// - uri (string)
+/// - encoding (string)
+/// - interval, file len, file modified ms (0 = unknown), line count (u64)
+/// - offsets len (u64) と offsets (u64)
+pub fn encode_line_index(uri: &FileUri, index: &LineIndex) -> Vec<u8> {
+    let mut buf = Vec::with_capacity(64 + uri.uri.len() + 8 * index.offsets.len());
+    let put_bytes = |buf: &mut Vec<u8>, bytes: &[u8]| {
+        buf.extend_from_slice(&(bytes.len() as u64).to_be_bytes());
+        buf.extend_from_slice(bytes);
+    };
+
+    buf.extend_from_slice(LINE_INDEX_MAGIC);
+    put_bytes(&mut buf, uri.uri.as_bytes());
+    put_bytes(&mut buf, index.encoding.as_bytes());
+    for v in [
+        index.interval,
+        index.file_len,
+        index.file_modified_ms.unwrap_or(0),
+        index.line_count,
+        index.offsets.len() as u64,
+    ] {
+        buf.extend_from_slice(&v.to_be_bytes());
+    }
+    for offset in &index.offsets {
+        buf.extend_from_slice(&offset.to_be_bytes());
+    }
+    buf
+}
+
+/// [`encode_line_index`] の形式から行の索引を復元する。  
+/// 壊れている場合や、異なる URI の索引である場合は `None` を返す。
+pub fn decode_line_index(uri: &FileUri, bytes: &[u8]) -> Option<LineIndex> {
+    fn take<'b>(rest: &mut &'b [u8], len: usize) -> Option<&'b [u8]> {
+        if rest.len() < len {
+            return None
+        }
+        let (head, tail) = rest.split_at(len);
+        *rest = tail;
+        Some(head)
+    }
+    fn take_u64(rest: &mut &[u8]) -> Option<u64> {
+        take(rest, 8).map(|b| u64::from_be_bytes(b.try_into().unwrap()))
+    }
+    fn take_bytes<'b>(rest: &mut &'b [u8]) -> Option<&'b [u8]> {
+        let len = usize::try_from(take_u64(rest)?).ok()?;
+        take(rest, len)
+    }
+
+    let rest = &mut bytes.strip_prefix(LINE_INDEX_MAGIC)?;
+    if take_bytes(rest)? != uri.uri.as_bytes() {
+        return None
+    }
+    let encoding = String::from_utf8(take_bytes(rest)?.to_vec()).ok()?;
+    let interval = take_u64(rest)?;
+    let file_len = take_u64(rest)?;
+    let file_modified_ms = Some(take_u64(rest)?).filter(|ms| *ms != 0);
+    let line_count = take_u64(rest)?;
+    let offsets_len = usize::try_from(take_u64(rest)?).ok()?;
+    let offsets = take(rest, offsets_len.checked_mul(8)?)?
+        .chunks_exact(8)
+        .map(|b| u64::from_be_bytes(b.try_into().unwrap()))
+        .collect::<Vec<_>>();
+
+    if interval == 0 || offsets.first() != Some(&0) || !rest.is_empty() {
+        return None
+    }
+
+    Some(LineIndex { interval, encoding, file_len, file_modified_ms, line_count, offsets })
+}
@@ -0,0 +1,78 @@
+use crate::*;
+
+
+/// 最終更新日時を DOS 形式の日時にする。
+/// DOS 形式はタイムゾーンを持たないので UTC とし、範囲外の場合は 1980-01-01 00:00:00 にする。
+pub fn zip_date_time(time: std::time::SystemTime) -> zip::DateTime {
+    let time = chrono::DateTime::<chrono::Utc>::from(time).naive_utc();
+    zip::DateTime::try_from(time).unwrap_or_default()
+}
+
+/// エントリーの名前を展開先からの相対パスにする。
+/// 展開先の外を指す名前 (zip slip) はエラーにする。
+pub fn zip_entry_relative_path(name: &str) -> Result<std::path::PathBuf> {
+    let invalid = |reason: &str| Error::with(format!("unsafe path in zip archive: {reason}: {name}"));
+
+    // Windows で作られたアーカイブは区切りに \ を使うことがある
+    let name = name.replace('\\', "/");
+    let name = name.trim_end_matches('/');
+    if name.is_empty() {
+        return Err(invalid("empty path"))
+    }
+    if name.starts_with('/') {
+        return Err(invalid("must not start with root directory"))
+    }
+
+    let mut path = std::path::PathBuf::new();
+    for component in name.split('/') {
+        match component {
+            "" => return Err(invalid("must not contain empty component")),
+            "." => return Err(invalid("must not contain current directory, i.e., '.'")),
+            ".." => return Err(invalid("must not contain parent directory, i.e., '..'")),
+            c if c.contains('\0') => return Err(invalid("must not contain NUL")),
+            c if c.contains(':') => return Err(invalid("must not contain ':'")),
+            c => path.push(c),
+        }
+    }
+    validate_relative_path(&path)?;
+    Ok(path)
+}
+
+/// ZIP アーカイブのセントラルディレクトリに記録されたエントリー。
+pub struct ZipArchiveEntry {
+    pub index: usize,
+    pub name: String,
+    pub is_dir: bool,
+    pub is_symlink: bool,
+    pub is_encrypted: bool,
+    pub len: u64,
+    pub last_modified: std::time::SystemTime,
+}
+
+impl ZipArchiveEntry {
+
+    pub fn new<R: std::io::Read>(index: usize, file: &zip::read::ZipFile<'_, R>) -> Self {
+        // DOS 形式の日時はタイムゾーンを持たず 2 秒単位なので、拡張フィールドがあればそれを優先する
+        let extended_timestamp = file.extra_data_fields().find_map(|field| match field {
+            zip::ExtraField::ExtendedTimestamp(t) => t.mod_time(),
+            _ => None,
+        });
+        let last_modified = match extended_timestamp {
+            Some(secs) => std::time::UNIX_EPOCH + std::time::Duration::from_secs(secs as u64),
+            None => file.last_modified()
+                .and_then(|t| chrono::NaiveDateTime::try_from(t).ok())
+                .map(|t| t.and_utc().into())
+                .unwrap_or(std::time::UNIX_EPOCH),
+        };
+
+        Self {
+            index,
+            name: file.name().to_string(),
+            is_dir: file.is_dir(),
+            is_symlink: file.is_symlink(),
+            is_encrypted: file.encrypted(),
+            len: file.size(),
+            last_modified,
+        }
+    }
+}
@@ -0,0 +1,115 @@
+//! Golden tests of the contract between the Rust and Kotlin sides of this plugin.
+//!
+//! Each transcript in `tests/golden` is the list of commands and payloads
+//! that the Kotlin side is expected to receive, with the responses it returns.
+
+#![cfg(all(unix, feature = "custom-backend"))]
+
+use std::sync::Arc;
+use serde_json::json;
+use tauri::test::MockRuntime;
+use tauri_plugin_android_fs::{*, backend::*};
+
+
+fn replay(transcript: &str) -> (tauri::App<MockRuntime>, Arc<ReplayBackend>) {
+    replay_invocations(Invocation::from_transcript_json(transcript).unwrap())
+}
+
+fn replay_invocations(invocations: Vec<Invocation>) -> (tauri::App<MockRuntime>, Arc<ReplayBackend>) {
+    let backend = Arc::new(ReplayBackend::new(invocations));
+    let app = tauri::test::mock_builder()
+        .plugin(tauri_plugin_android_fs::init_with_backend(Arc::clone(&backend)))
+        .build(tauri::test::mock_context(tauri::test::noop_assets()))
+        .unwrap();
+
+    (app, backend)
+}
+
+#[test]
+fn read_dir() {
+    let (app, backend) = replay(include_str!("golden/read_dir.json"));
+    let api = app.android_fs();
+    let dir = FileUri {
+        uri: "content://com.android.externalstorage.documents/tree/primary%3ADocuments/document/primary%3ADocuments".into(),
+        document_top_tree_uri: Some("content://com.android.externalstorage.documents/tree/primary%3ADocuments".into()),
+    };
+
+    let entries = api.read_dir(&dir).unwrap();
+    assert_eq!(entries.len(), 2);
+    assert!(entries[0].is_file());
+    assert_eq!(entries[0].name(), "a.txt");
+    assert_eq!(entries[0].file_len(), Some(5));
+    assert_eq!(entries[0].file_mime_type(), Some("text/plain"));
+    assert_eq!(entries[0].uri().document_top_tree_uri, dir.document_top_tree_uri);
+    assert_eq!(
+        entries[0].last_modified(),
+        std::time::UNIX_EPOCH + std::time::Duration::from_millis(1700000000000)
+    );
+    assert!(entries[1].is_dir());
+    assert_eq!(entries[1].name(), "sub");
+
+    let entries = api.read_dir_with_range(&dir, 10..15).unwrap();
+    assert!(entries.is_empty());
+
+    backend.finish().unwrap();
+}
+
+#[test]
+fn get_file_descriptor_with_fallback() {
+    use std::io::{Read as _, Seek as _, Write as _};
+    use std::os::fd::IntoRawFd as _;
+
+    let path = std::env::temp_dir().join(format!("afs-golden-{}", std::process::id()));
+    let fd = std::fs::File::options()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(&path)
+        .unwrap()
+        .into_raw_fd();
+
+    // fd はテストごとに異なるため、記録されたものを実際に開いたものに置き換える
+    let mut invocations = Invocation::from_transcript_json(include_str!("golden/get_file_descriptor_with_fallback.json")).unwrap();
+    if let InvocationResult::Ok(response) = &mut invocations[0].result {
+        response["fd"] = json!(fd);
+    }
+
+    let (app, backend) = replay_invocations(invocations);
+    let api = app.android_fs();
+
+    let (mut file, mode) = api.open_file_with_fallback(
+        &FileUri::from_uri("content://media/external_primary/file/1"),
+        [FileAccessMode::ReadWriteTruncate, FileAccessMode::WriteTruncate]
+    ).unwrap();
+    assert_eq!(mode, FileAccessMode::WriteTruncate);
+
+    file.write_all(b"hello").unwrap();
+    file.rewind().unwrap();
+    let mut contents = String::new();
+    file.read_to_string(&mut contents).unwrap();
+    assert_eq!(contents, "hello");
+
+    let result = api.open_file_with_fallback(
+        &FileUri::from_uri("content://media/external_primary/file/2"),
+        [FileAccessMode::Read]
+    );
+    assert!(result.is_err());
+
+    backend.finish().unwrap();
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn create_new_media_store_file() {
+    let (app, backend) = replay(include_str!("golden/create_new_media_store_file.json"));
+    let storage = app.android_fs().public_storage();
+
+    let uri = storage.create_new_file(None, PublicImageDir::Pictures, "MyApp/a.png", Some("image/png")).unwrap();
+    assert_eq!(uri, FileUri::from_uri("content://media/external_primary/images/media/1"));
+
+    let uri = storage.create_new_file_with_pending(None, PublicGeneralPurposeDir::Download, "MyApp/b.txt", None).unwrap();
+    assert_eq!(uri, FileUri::from_uri("content://media/external_primary/file/2"));
+
+    backend.finish().unwrap();
+}
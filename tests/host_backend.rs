@@ -0,0 +1,970 @@
+//! Tests of the APIs composed of multiple commands, run on [`HostBackend`].
+
+#![cfg(all(unix, feature = "custom-backend"))]
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tauri::test::MockRuntime;
+use tauri_plugin_android_fs::{*, backend::*};
+
+
+struct Sandbox {
+    app: tauri::App<MockRuntime>,
+    backend: Arc<HostBackend>,
+}
+
+impl Sandbox {
+
+    fn new(name: &str) -> Self {
+        let root = std::env::temp_dir().join(format!("afs-host-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+
+        let backend = Arc::new(HostBackend::new(root));
+        let app = tauri::test::mock_builder()
+            .plugin(tauri_plugin_android_fs::init_with_backend(Arc::clone(&backend)))
+            .build(tauri::test::mock_context(tauri::test::noop_assets()))
+            .unwrap();
+
+        Self { app, backend }
+    }
+
+    fn api(&self) -> &api::api_sync::AndroidFs<MockRuntime> {
+        self.app.android_fs()
+    }
+
+    /// Creates files and directories under `Documents` of the primary storage volume
+    /// and returns the tree URI of it.
+    /// Paths ending with `/` are created as directories.
+    fn documents(&self, files: &[(&str, &str)]) -> FileUri {
+        let dir = self.path("Documents");
+        std::fs::create_dir_all(&dir).unwrap();
+        for (path, contents) in files {
+            match path.strip_suffix('/') {
+                Some(path) => std::fs::create_dir_all(dir.join(path)).unwrap(),
+                None => {
+                    let path = dir.join(path);
+                    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+                    std::fs::write(&path, contents).unwrap();
+                }
+            }
+        }
+        self.backend.tree_uri(None, "Documents")
+    }
+
+    fn path(&self, relative_path: impl AsRef<Path>) -> PathBuf {
+        self.backend.root().join("storage/emulated/0").join(relative_path)
+    }
+}
+
+impl Drop for Sandbox {
+
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(self.backend.root());
+    }
+}
+
+fn relative_paths(entries: &[WalkEntry]) -> Vec<String> {
+    entries.iter().map(|e| e.relative_path.to_string_lossy().into_owned()).collect()
+}
+
+fn sorted(mut paths: Vec<String>) -> Vec<String> {
+    paths.sort();
+    paths
+}
+
+#[test]
+fn walk_dir() {
+    let sandbox = Sandbox::new("walk-dir");
+    let root = sandbox.documents(&[
+        ("a.txt", "a"),
+        ("b.png", "b"),
+        ("sub/c.txt", "c"),
+        ("sub/deep/d.txt", "d"),
+        ("empty/", ""),
+    ]);
+    let api = sandbox.api();
+
+    let options = WalkOptions { page_size: 1, ..Default::default() };
+    let entries = api.walk_dir(&root, options).unwrap().collect_entries().unwrap();
+    assert_eq!(sorted(relative_paths(&entries)), [
+        "a.txt", "b.png", "empty", "sub", "sub/c.txt", "sub/deep", "sub/deep/d.txt"
+    ]);
+    for entry in &entries {
+        let uri = entry.entry.uri().unwrap();
+        assert_eq!(uri.document_top_tree_uri, root.document_top_tree_uri);
+        assert_eq!(entry.depth as usize, entry.relative_path.components().count());
+    }
+
+    // PreOrder ではディレクトリが子より先に、PostOrder では後に返される
+    let position = |entries: &[WalkEntry], path: &str| {
+        entries.iter().position(|e| e.relative_path == Path::new(path)).unwrap()
+    };
+    assert!(position(&entries, "sub") < position(&entries, "sub/c.txt"));
+    let options = WalkOptions { order: WalkOrder::PostOrder, ..Default::default() };
+    let entries = api.walk_dir(&root, options).unwrap().collect_entries().unwrap();
+    assert!(position(&entries, "sub/deep/d.txt") < position(&entries, "sub/deep"));
+    assert!(position(&entries, "sub/deep") < position(&entries, "sub"));
+
+    let options = WalkOptions { max_depth: Some(1), ..Default::default() };
+    let entries = api.walk_dir(&root, options).unwrap().collect_entries().unwrap();
+    assert_eq!(sorted(relative_paths(&entries)), ["a.txt", "b.png", "empty", "sub"]);
+
+    let options = WalkOptions { name_pattern: Some("*.txt".into()), ..Default::default() };
+    let entries = api.walk_dir(&root, options).unwrap().collect_entries().unwrap();
+    assert_eq!(sorted(relative_paths(&entries)), ["a.txt", "sub/c.txt", "sub/deep/d.txt"]);
+
+    let options = WalkOptions { mime_types: vec!["image/*".into()], ..Default::default() };
+    let entries = api.walk_dir(&root, options).unwrap().collect_entries().unwrap();
+    assert_eq!(relative_paths(&entries), ["b.png"]);
+
+    let options = WalkOptions {
+        entry_type: Some(WalkEntryType::Dir),
+        entry_options: EntryOptions::NONE,
+        ..Default::default()
+    };
+    let entries = api.walk_dir(&root, options).unwrap().collect_entries().unwrap();
+    assert_eq!(sorted(relative_paths(&entries)), ["empty", "sub", "sub/deep"]);
+    assert!(entries.iter().all(|e| e.entry.uri().is_none() && e.entry.name().is_none()));
+
+    let options = WalkOptions { order: WalkOrder::PostOrder, page_size: 1, ..Default::default() };
+    let expected = api.walk_dir(&root, options.clone()).unwrap().collect_entries().unwrap();
+    let mut stream = sandbox.app.android_fs_async().walk_dir(&root, options).unwrap();
+    let entries = tauri::async_runtime::block_on(async {
+        let mut entries = Vec::new();
+        while let Some(entry) = std::future::poll_fn(|cx| {
+            futures_core::Stream::poll_next(std::pin::Pin::new(&mut stream), cx)
+        }).await {
+            entries.push(entry.unwrap());
+        }
+        entries
+    });
+    assert_eq!(relative_paths(&entries), relative_paths(&expected));
+}
+
+#[test]
+fn walk_dir_unreadable_dir() {
+    let sandbox = Sandbox::new("walk-dir-unreadable");
+    let root = sandbox.documents(&[
+        ("a.txt", "a"),
+        ("sub/", ""),
+    ]);
+    // 壊れたシンボリックリンクのメタデータを読めないため、sub の読み込みは失敗する
+    std::os::unix::fs::symlink(sandbox.path("Documents/missing"), sandbox.path("Documents/sub/broken")).unwrap();
+    let api = sandbox.api();
+
+    // どちらの順序でも、読めなかったディレクトリ自身は返される
+    for order in [WalkOrder::PreOrder, WalkOrder::PostOrder] {
+        let options = WalkOptions { order, ..Default::default() };
+        let (entries, errors): (Vec<_>, Vec<_>) = api.walk_dir(&root, options).unwrap().partition(|e| e.is_ok());
+        let entries = entries.into_iter().map(|e| e.unwrap()).collect::<Vec<_>>();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(sorted(relative_paths(&entries)), ["a.txt", "sub"]);
+    }
+}
+
+#[test]
+fn read_dir_paged() {
+    let sandbox = Sandbox::new("read-dir-paged");
+    let root = sandbox.documents(&[
+        ("a.txt", "a"),
+        ("b.txt", "b"),
+        ("c.txt", "c"),
+        ("d.txt", "d"),
+        ("sub/e.txt", "e"),
+    ]);
+    let api = sandbox.api();
+    let names = |entries: &[OptionalEntry]| {
+        entries.iter().map(|e| e.name().unwrap().to_string()).collect::<Vec<_>>()
+    };
+
+    let entries = api.read_dir_paged(&root, EntryOptions::ALL, 2).unwrap()
+        .collect::<Result<Vec<_>>>()
+        .unwrap();
+    assert_eq!(names(&entries), ["a.txt", "b.txt", "c.txt", "d.txt", "sub"]);
+
+    let mut pages = api.read_dir_paged(&root, EntryOptions::URI_AND_NAME, 2).unwrap();
+    let mut page_lens = Vec::new();
+    while let Some(page) = pages.next_page() {
+        page_lens.push(page.unwrap().len());
+    }
+    assert_eq!(page_lens, [2, 2, 1]);
+
+    assert!(api.read_dir_paged(&root, EntryOptions::ALL, 0).is_err());
+
+    let file = api.resolve_file_uri(&root, "a.txt").unwrap();
+    let mut entries = api.read_dir_paged(&file, EntryOptions::ALL, 2).unwrap();
+    assert!(entries.next().unwrap().is_err());
+    assert!(entries.next().is_none());
+
+    let mut stream = sandbox.app.android_fs_async().read_dir_paged(&root, EntryOptions::ALL, 3).unwrap();
+    let entries = tauri::async_runtime::block_on(async {
+        let mut entries = Vec::new();
+        while let Some(entry) = std::future::poll_fn(|cx| {
+            futures_core::Stream::poll_next(std::pin::Pin::new(&mut stream), cx)
+        }).await {
+            entries.push(entry.unwrap());
+        }
+        entries
+    });
+    assert_eq!(names(&entries), ["a.txt", "b.txt", "c.txt", "d.txt", "sub"]);
+}
+
+#[test]
+fn read_dir_with_sort_and_filter() {
+    let sandbox = Sandbox::new("read-dir-sort");
+    let root = sandbox.documents(&[
+        ("img10.png", "1234"),
+        ("img2.png", "1"),
+        ("IMG1.jpg", "12"),
+        ("notes.txt", "123"),
+        ("sub/", ""),
+    ]);
+    let api = sandbox.api();
+    let names = |options: ReadDirOptions, range: std::ops::Range<u64>| {
+        api.read_dir_with_options_and_range(&root, options, range).unwrap()
+            .into_iter()
+            .map(|e| e.name().unwrap().to_string())
+            .collect::<Vec<_>>()
+    };
+
+    let options = ReadDirOptions {
+        sort_by: vec![ReadDirSort::ascending(ReadDirSortKey::DirsFirst), ReadDirSort::ascending(ReadDirSortKey::Name)],
+        ..Default::default()
+    };
+    assert_eq!(names(options.clone(), 0..10), ["sub", "IMG1.jpg", "img2.png", "img10.png", "notes.txt"]);
+    assert_eq!(names(options, 1..3), ["IMG1.jpg", "img2.png"]);
+
+    let options = ReadDirOptions {
+        sort_by: vec![ReadDirSort::descending(ReadDirSortKey::Len)],
+        entry_type: Some(WalkEntryType::File),
+        ..Default::default()
+    };
+    assert_eq!(names(options, 0..10), ["img10.png", "notes.txt", "IMG1.jpg", "img2.png"]);
+
+    let options = ReadDirOptions {
+        sort_by: vec![ReadDirSort::ascending(ReadDirSortKey::Name)],
+        mime_types: vec!["image/*".into()],
+        name_contains: Some("img1".into()),
+        ..Default::default()
+    };
+    assert_eq!(names(options, 0..10), ["IMG1.jpg", "img10.png"]);
+
+    let options = ReadDirOptions { name_pattern: Some("*.png".into()), ..Default::default() };
+    assert_eq!(sorted(names(options, 0..10)), ["img10.png", "img2.png"]);
+
+    // ソートに必要なフィールドは指定されていなくても取得される
+    let options = ReadDirOptions {
+        entry_options: EntryOptions::URI_ONLY,
+        sort_by: vec![ReadDirSort::ascending(ReadDirSortKey::Name)],
+        ..Default::default()
+    };
+    let entries = api.read_dir_with_options(&root, options).unwrap();
+    assert_eq!(entries.len(), 5);
+    assert!(entries.iter().all(|e| e.uri().is_some() && e.name().is_none()));
+    assert_eq!(entries[0].uri(), api.resolve_file_uri(&root, "IMG1.jpg").ok().as_ref());
+}
+
+#[test]
+fn watch_dir() {
+    let sandbox = Sandbox::new("watch-dir");
+    let root = sandbox.documents(&[("a.txt", "a"), ("b.txt", "b")]);
+    let api = sandbox.api();
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    let interval = std::time::Duration::from_millis(10);
+    let guard = api.watch_dir(&root, interval, move |event| { let _ = tx.send(event); }).unwrap();
+    let next_event = || rx.recv_timeout(std::time::Duration::from_secs(5)).unwrap().unwrap();
+
+    std::fs::write(sandbox.path("Documents/c.txt"), "c").unwrap();
+    match next_event() {
+        DirWatchEvent::Created { entry } => assert_eq!(entry.name(), "c.txt"),
+        event => panic!("unexpected event: {event:?}"),
+    }
+
+    // 書き込みの途中が読まれることがあるため、書き込み後の長さになるまで待つ
+    std::fs::write(sandbox.path("Documents/a.txt"), "aa").unwrap();
+    loop {
+        match next_event() {
+            DirWatchEvent::Modified { new, .. } if new.file_len() == Some(2) => break,
+            DirWatchEvent::Modified { new, .. } => assert_eq!(new.name(), "a.txt"),
+            event => panic!("unexpected event: {event:?}"),
+        }
+    }
+
+    std::fs::rename(sandbox.path("Documents/b.txt"), sandbox.path("Documents/d.txt")).unwrap();
+    match next_event() {
+        DirWatchEvent::Renamed { old, new } => {
+            assert_eq!(old.name(), "b.txt");
+            assert_eq!(new.name(), "d.txt");
+        },
+        event => panic!("unexpected event: {event:?}"),
+    }
+
+    std::fs::remove_file(sandbox.path("Documents/c.txt")).unwrap();
+    match next_event() {
+        DirWatchEvent::Removed { entry } => assert_eq!(entry.name(), "c.txt"),
+        event => panic!("unexpected event: {event:?}"),
+    }
+
+    // ガードを破棄すると監視スレッドが終了し、送信側も破棄される
+    guard.stop();
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+    loop {
+        match rx.recv_timeout(std::time::Duration::from_secs(5)) {
+            Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => break,
+            _ => assert!(std::time::Instant::now() < deadline),
+        }
+    }
+
+    assert!(api.watch_dir(&root, std::time::Duration::ZERO, |_| {}).is_err());
+}
+
+#[test]
+fn find() {
+    let sandbox = Sandbox::new("find");
+    let root = sandbox.documents(&[
+        ("a.jpg", "a"),
+        ("photos/b.jpg", "b"),
+        ("photos/2024/c.jpg", "c"),
+        ("photos/2024/d.png", "d"),
+        ("videos/e.jpg", "e"),
+    ]);
+    let api = sandbox.api();
+
+    let find = |pattern: &str| {
+        let entries = api.find(&root, pattern).unwrap();
+        for (entry, _) in &entries {
+            assert_eq!(entry.uri().document_top_tree_uri, root.document_top_tree_uri);
+        }
+        sorted(entries.into_iter().map(|(_, path)| path.to_string_lossy().into_owned()).collect())
+    };
+
+    assert_eq!(find("*.jpg"), ["a.jpg"]);
+    assert_eq!(find("photos/**/*.jpg"), ["photos/2024/c.jpg", "photos/b.jpg"]);
+    assert_eq!(find("**/*.jpg"), ["a.jpg", "photos/2024/c.jpg", "photos/b.jpg", "videos/e.jpg"]);
+    assert_eq!(find("*/2024"), ["photos/2024"]);
+    assert!(find("music/**").is_empty());
+
+    assert!(api.find(&root, "/photos/*").is_err());
+    assert!(api.find(&root, "[").is_err());
+}
+
+#[test]
+fn copy_dir() {
+    let sandbox = Sandbox::new("copy-dir");
+    let src = sandbox.documents(&[
+        ("a.txt", "a"),
+        ("sub/b.txt", "b"),
+        ("sub/deep/c.txt", "c"),
+        ("empty/", ""),
+    ]);
+    let api = sandbox.api();
+
+    // ピッカーで選ばれたツリーからアプリ専用ディレクトリへ、異なる provider 間でコピーする
+    let dest_path = api.private_storage().resolve_path(PrivateDir::Data).unwrap().join("copy");
+    std::fs::create_dir_all(&dest_path).unwrap();
+    let dest = FileUri::from_path(&dest_path);
+
+    let copy = |on_conflict| {
+        let entries = api.copy_dir(&src, &dest, CopyDirOptions { on_conflict }).unwrap();
+        let mut outcomes = entries.into_iter()
+            .map(|e| (e.relative_path.to_string_lossy().into_owned(), e.result.unwrap()))
+            .collect::<Vec<_>>();
+        outcomes.sort_by(|a, b| a.0.cmp(&b.0));
+        outcomes
+    };
+
+    let outcomes = copy(CopyConflictPolicy::Fail);
+    assert_eq!(outcomes.len(), 6);
+    assert!(outcomes.iter().all(|(path, o)| {
+        matches!(o, CopyDirOutcome::Copied { .. }) && o.relative_path() == Some(Path::new(path))
+    }));
+    assert_eq!(std::fs::read_to_string(dest_path.join("sub/deep/c.txt")).unwrap(), "c");
+    assert!(dest_path.join("empty").is_dir());
+
+    std::fs::write(sandbox.path("Documents/a.txt"), "A").unwrap();
+
+    let outcomes = copy(CopyConflictPolicy::Skip);
+    assert!(matches!(outcomes[0], (ref p, CopyDirOutcome::Skipped) if p == "a.txt"));
+    assert!(matches!(outcomes[2], (ref p, CopyDirOutcome::Merged { .. }) if p == "sub"));
+    assert_eq!(std::fs::read_to_string(dest_path.join("a.txt")).unwrap(), "a");
+
+    let outcomes = copy(CopyConflictPolicy::Overwrite);
+    assert!(matches!(outcomes[0].1, CopyDirOutcome::Overwritten { .. }));
+    assert_eq!(std::fs::read_to_string(dest_path.join("a.txt")).unwrap(), "A");
+
+    let outcomes = copy(CopyConflictPolicy::Rename);
+    assert_eq!(outcomes[0].1.relative_path(), Some(Path::new("a (1).txt")));
+    assert_eq!(outcomes[3].1.relative_path(), Some(Path::new("sub/b (1).txt")));
+    assert_eq!(std::fs::read_to_string(dest_path.join("a (1).txt")).unwrap(), "A");
+
+    assert!(api.copy_dir(&src, &dest, CopyDirOptions::default()).is_err());
+
+    // 自身の内部へはコピーできない
+    let documents = FileUri::from_path(sandbox.path("Documents"));
+    let sub = FileUri::from_path(sandbox.path("Documents/sub"));
+    assert!(api.copy_dir(&documents, &sub, CopyDirOptions::default()).is_err());
+
+    // content URI でも同じディレクトリや内部へのコピーは拒否され、元のファイルは切り詰められない
+    let overwrite = CopyDirOptions { on_conflict: CopyConflictPolicy::Overwrite };
+    let content_sub = api.resolve_dir_uri(&src, "sub").unwrap();
+    assert!(api.copy_dir(&src, &src, overwrite).is_err());
+    assert!(api.copy_dir(&src, &FileUri::from_uri(src.uri.clone()), overwrite).is_err());
+    assert!(api.copy_dir(&src, &content_sub, overwrite).is_err());
+    assert!(api.copy_dir(&content_sub, &src, overwrite).is_ok());
+    assert_eq!(std::fs::read_to_string(sandbox.path("Documents/a.txt")).unwrap(), "A");
+    assert_eq!(std::fs::read_to_string(sandbox.path("Documents/sub/b.txt")).unwrap(), "b");
+}
+
+#[test]
+fn move_entry() {
+    let sandbox = Sandbox::new("move-entry");
+    let root = sandbox.documents(&[
+        ("a.txt", "a"),
+        ("b.txt", "b"),
+        ("sub/c.txt", "c"),
+        ("sub/deep/d.txt", "d"),
+        ("dest/", ""),
+    ]);
+    let api = sandbox.api();
+    let dest = api.resolve_dir_uri(&root, "dest").unwrap();
+
+    // 同じ provider 内ではネイティブに移動される
+    let a = api.resolve_file_uri(&root, "a.txt").unwrap();
+    let moved = api.move_entry(&a, &dest).unwrap();
+    assert_eq!(moved.document_top_tree_uri, root.document_top_tree_uri);
+    assert_eq!(api.read_to_string(&moved).unwrap(), "a");
+    assert!(!sandbox.path("Documents/a.txt").exists());
+
+    // ネイティブな移動のエラーはコピーにフォールバックせずにそのまま返す
+    std::fs::write(sandbox.path("Documents/e.txt"), "e").unwrap();
+    std::fs::write(sandbox.path("Documents/dest/e.txt"), "x").unwrap();
+    let e = api.resolve_file_uri(&root, "e.txt").unwrap();
+    let err = api.move_entry(&e, &dest).unwrap_err();
+    assert!(err.to_string().contains("File already exists"), "{err}");
+    assert_eq!(std::fs::read_to_string(sandbox.path("Documents/e.txt")).unwrap(), "e");
+
+    // 異なる provider 間ではコピーと削除にフォールバックする
+    let private_dir = api.private_storage().resolve_uri(PrivateDir::Data, "").unwrap();
+    let b = api.resolve_file_uri(&root, "b.txt").unwrap();
+    let moved = api.move_entry(&b, &private_dir).unwrap();
+    assert_eq!(api.read_to_string(&moved).unwrap(), "b");
+    assert!(!sandbox.path("Documents/b.txt").exists());
+
+    let sub = api.resolve_dir_uri(&root, "sub").unwrap();
+    let moved = api.move_entry(&sub, &private_dir).unwrap();
+    let moved_path = moved.to_path().unwrap();
+    assert_eq!(std::fs::read_to_string(moved_path.join("c.txt")).unwrap(), "c");
+    assert_eq!(std::fs::read_to_string(moved_path.join("deep/d.txt")).unwrap(), "d");
+    assert!(!sandbox.path("Documents/sub").exists());
+
+    // 同名のエントリがある場合は何も変更しない
+    std::fs::write(sandbox.path("Documents/sub.txt"), "x").unwrap();
+    std::fs::write(moved_path.parent().unwrap().join("sub.txt"), "y").unwrap();
+    let file = api.resolve_file_uri(&root, "sub.txt").unwrap();
+    assert!(api.move_entry(&file, &private_dir).is_err());
+    assert_eq!(std::fs::read_to_string(sandbox.path("Documents/sub.txt")).unwrap(), "x");
+}
+
+#[test]
+fn dir_stats() {
+    let sandbox = Sandbox::new("dir-stats");
+    let root = sandbox.documents(&[
+        ("a.txt", "aaa"),
+        ("b.png", "bb"),
+        ("sub/c.txt", "c"),
+        ("sub/deep/d.png", "dddd"),
+        ("empty/", ""),
+    ]);
+    let api = sandbox.api();
+
+    let stats = api.dir_stats(&root).unwrap();
+    assert_eq!((stats.total_len, stats.file_count, stats.dir_count), (10, 4, 3));
+    assert_eq!(stats.mime_types["text/plain"].total_len, 4);
+    assert_eq!(stats.mime_types["text/plain"].file_count, 2);
+    assert_eq!(stats.mime_types["image/png"].total_len, 6);
+
+    let options = DirStatsOptions { max_depth: Some(1), page_size: 1 };
+    let stats = api.dir_stats_with_options(&root, options, || false).unwrap();
+    assert_eq!((stats.total_len, stats.file_count, stats.dir_count), (5, 2, 2));
+
+    let mut count = 0;
+    let result = api.dir_stats_with_options(&root, DirStatsOptions::default(), || {
+        count += 1;
+        count > 2
+    });
+    assert!(result.is_err());
+    assert_eq!(count, 3);
+}
+
+#[test]
+fn sync_dir() {
+    let sandbox = Sandbox::new("sync-dir");
+    let src = sandbox.documents(&[
+        ("a.txt", "a"),
+        ("sub/b.txt", "b"),
+        ("sub/deep/c.txt", "c"),
+    ]);
+    let api = sandbox.api();
+
+    let dest_path = api.private_storage().resolve_path(PrivateDir::Data).unwrap().join("backup");
+    std::fs::create_dir_all(&dest_path).unwrap();
+    let dest = FileUri::from_path(&dest_path);
+
+    let sync = |options: SyncOptions| {
+        let mut progress = Vec::new();
+        let actions = api.sync_dir_with_progress(&src, &dest, options, |_, done, total| progress.push((done, total))).unwrap();
+        let actions = actions.iter()
+            .map(|a| {
+                let kind = match a {
+                    SyncAction::CreateDir { .. } => "CreateDir",
+                    SyncAction::CreateFile { .. } => "CreateFile",
+                    SyncAction::UpdateFile { .. } => "UpdateFile",
+                    SyncAction::RemoveFile { .. } => "RemoveFile",
+                    SyncAction::RemoveDir { .. } => "RemoveDir",
+                    _ => unreachable!(),
+                };
+                format!("{kind} {}", a.relative_path().display())
+            })
+            .collect::<Vec<_>>();
+        (sorted(actions), progress)
+    };
+
+    let (actions, progress) = sync(SyncOptions { dry_run: true, ..Default::default() });
+    assert_eq!(actions, [
+        "CreateDir sub", "CreateDir sub/deep", "CreateFile a.txt", "CreateFile sub/b.txt", "CreateFile sub/deep/c.txt"
+    ]);
+    assert!(progress.is_empty());
+    assert!(!dest_path.join("a.txt").exists());
+
+    let (_, progress) = sync(SyncOptions::default());
+    assert_eq!(progress, [(1, 5), (2, 5), (3, 5), (4, 5), (5, 5)]);
+    assert_eq!(std::fs::read_to_string(dest_path.join("sub/deep/c.txt")).unwrap(), "c");
+    assert!(sync(SyncOptions::default()).0.is_empty());
+
+    // 長さが同じで古い日時の変更は、内容を比較した場合のみ検出される
+    let past = std::time::SystemTime::now() - std::time::Duration::from_secs(3600);
+    let file = std::fs::File::create(sandbox.path("Documents/a.txt")).unwrap();
+    std::io::Write::write_all(&mut &file, b"A").unwrap();
+    file.set_modified(past).unwrap();
+    std::fs::write(sandbox.path("Documents/sub/b.txt"), "bb").unwrap();
+    assert_eq!(sync(SyncOptions::default()).0, ["UpdateFile sub/b.txt"]);
+    assert_eq!(sync(SyncOptions { compare_contents: true, ..Default::default() }).0, ["UpdateFile a.txt"]);
+    assert_eq!(std::fs::read_to_string(dest_path.join("a.txt")).unwrap(), "A");
+
+    std::fs::write(dest_path.join("extra.txt"), "x").unwrap();
+    std::fs::create_dir_all(dest_path.join("sub/extra")).unwrap();
+    assert!(sync(SyncOptions::default()).0.is_empty());
+    assert_eq!(sync(SyncOptions { delete_extras: true, ..Default::default() }).0, [
+        "RemoveDir sub/extra", "RemoveFile extra.txt"
+    ]);
+    assert!(!dest_path.join("extra.txt").exists());
+    assert!(!dest_path.join("sub/extra").exists());
+
+    // content URI でも同じツリーやその内部への同期は拒否される
+    let options = SyncOptions { delete_extras: true, ..Default::default() };
+    let sub = api.resolve_dir_uri(&src, "sub").unwrap();
+    assert!(api.sync_dir(&src, &src, options).is_err());
+    assert!(api.sync_dir(&src, &sub, options).is_err());
+    assert_eq!(std::fs::read_to_string(sandbox.path("Documents/sub/b.txt")).unwrap(), "bb");
+}
+
+#[test]
+fn find_duplicates() {
+    let big = "x".repeat(5000);
+    let big_other = format!("{}y", "x".repeat(4999));
+    let sandbox = Sandbox::new("find-duplicates");
+    let root = sandbox.documents(&[
+        ("a.txt", "hello"),
+        ("b.txt", "hellp"),
+        ("sub/a-copy.txt", "hello"),
+        ("big1.bin", &big),
+        ("big2.bin", &big_other),
+        ("sub/big-copy.bin", &big),
+        ("empty1.txt", ""),
+        ("sub/empty2.txt", ""),
+    ]);
+    let api = sandbox.api();
+    let sub = api.resolve_dir_uri(&root, "sub").unwrap();
+    let names = |group: &DuplicateGroup| {
+        sorted(group.entries.iter().map(|e| e.name().to_string()).collect())
+    };
+
+    // sub は root に含まれるので、同じファイルを二重に数えない
+    let mut progress = Vec::new();
+    let groups = api.find_duplicates_with_options(&[root.clone(), sub], FindDuplicatesOptions::default(), |phase, done, total| {
+        progress.push((phase, done, total));
+    }).unwrap();
+    assert_eq!(groups.iter().map(|g| (g.len, names(g))).collect::<Vec<_>>(), [
+        (5000, vec!["big-copy.bin".to_string(), "big1.bin".to_string()]),
+        (5, vec!["a-copy.txt".to_string(), "a.txt".to_string()]),
+    ]);
+    assert_eq!(progress.iter().filter(|p| p.0 == FindDuplicatesPhase::PartialHash).count(), 6);
+    assert_eq!(progress.iter().rfind(|p| p.0 == FindDuplicatesPhase::FullHash), Some(&(FindDuplicatesPhase::FullHash, 3, 3)));
+
+    // 別のツリー URI を経由した同じドキュメントも二重に数えない
+    let sub_tree = sandbox.backend.tree_uri(None, "Documents/sub");
+    let groups = api.find_duplicates(&[root.clone(), sub_tree]).unwrap();
+    assert_eq!(groups.len(), 2);
+    assert!(groups.iter().all(|g| g.entries.len() == 2));
+
+    let options = FindDuplicatesOptions { min_len: 0, ..Default::default() };
+    let groups = api.find_duplicates_with_options(&[root], options, |_, _, _| {}).unwrap();
+    assert_eq!(groups.last().map(names), Some(vec!["empty1.txt".to_string(), "empty2.txt".to_string()]));
+}
+
+#[test]
+fn snapshot_tree() {
+    let sandbox = Sandbox::new("snapshot-tree");
+    let root = sandbox.documents(&[
+        ("a.txt", "a"),
+        ("sub/b.txt", "b"),
+        ("sub/deep/c.txt", "c"),
+    ]);
+    let api = sandbox.api();
+
+    let options = SnapshotTreeOptions { hash: true, ..Default::default() };
+    let old = api.snapshot_tree_with_options(&root, options).unwrap();
+    let paths = old.entries.keys().map(|p| p.to_string_lossy().into_owned()).collect::<Vec<_>>();
+    assert_eq!(paths, ["a.txt", "sub", "sub/b.txt", "sub/deep", "sub/deep/c.txt"]);
+    assert!(old.entries.values().all(|e| match e {
+        ManifestEntry::File { hash, .. } => hash.is_some(),
+        _ => true,
+    }));
+    let hash_of = |manifest: &TreeManifest, path: &str| match &manifest.entries[std::path::Path::new(path)] {
+        ManifestEntry::File { hash, .. } => hash.clone(),
+        _ => None,
+    };
+    assert_eq!(
+        hash_of(&old, "a.txt").as_deref(),
+        Some("ca978112ca1bbdcafac231b39a23dc4da786eff8147c4e72b9807785afee48bb")
+    );
+    let crc32 = SnapshotTreeOptions { hash: true, hash_algorithm: HashAlgorithm::Crc32 };
+    let crc32 = api.snapshot_tree_with_options(&root, crc32).unwrap();
+    assert_eq!(hash_of(&crc32, "a.txt").as_deref(), Some("e8b7be43"));
+
+    // 保存して読み込んだマニフェストとも比較できる
+    let old = TreeManifest::from_json_str(old.to_json_string().unwrap()).unwrap();
+    assert!(api.diff_manifests(&old, &api.snapshot_tree_with_options(&root, options).unwrap()).is_empty());
+
+    std::fs::write(sandbox.path("Documents/a.txt"), "b").unwrap();
+    std::fs::remove_file(sandbox.path("Documents/sub/b.txt")).unwrap();
+    std::fs::write(sandbox.path("Documents/sub/d.txt"), "d").unwrap();
+
+    let new = api.snapshot_tree_with_options(&root, options).unwrap();
+    let changes = api.diff_manifests(&old, &new).iter()
+        .map(|c| {
+            let kind = match c {
+                ManifestChange::Added { .. } => "Added",
+                ManifestChange::Removed { .. } => "Removed",
+                ManifestChange::Modified { .. } => "Modified",
+                _ => unreachable!(),
+            };
+            format!("{kind} {}", c.relative_path().display())
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(changes, ["Modified a.txt", "Removed sub/b.txt", "Added sub/d.txt"]);
+
+    let new = api.snapshot_tree(&root).unwrap();
+    assert!(new.entries.values().all(|e| !matches!(e, ManifestEntry::File { hash: Some(_), .. })));
+}
+
+#[test]
+fn write_atomic() {
+    let sandbox = Sandbox::new("write-atomic");
+    let root = sandbox.documents(&[("a.txt", "old contents"), ("b.txt", "old contents")]);
+    let api = sandbox.api();
+    let dir_names = |path: &Path| {
+        let mut names = std::fs::read_dir(path).unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+            .collect::<Vec<_>>();
+        names.sort();
+        names
+    };
+
+    // ツリー内のドキュメントは同じディレクトリの一時ファイルと名前の変更で置き換えられ、URI は変わらない
+    let a = api.resolve_file_uri(&root, "a.txt").unwrap();
+    api.write_atomic(&a, "new").unwrap();
+    assert_eq!(api.read_to_string(&a).unwrap(), "new");
+    assert_eq!(dir_names(&sandbox.path("Documents")), ["a.txt", "b.txt"]);
+
+    // 親が分からないドキュメントはキャッシュディレクトリの一時ファイルからコピーされる
+    let b = FileUri::from_uri(api.resolve_file_uri(&root, "b.txt").unwrap().uri.replace(
+        "/tree/primary%3ADocuments/document/",
+        "/document/",
+    ));
+    api.write_atomic(&b, "new").unwrap();
+    assert_eq!(std::fs::read_to_string(sandbox.path("Documents/b.txt")).unwrap(), "new");
+    let cache = api.private_storage().resolve_path(PrivateDir::Cache).unwrap();
+    assert!(dir_names(&cache).is_empty());
+
+    let dir = api.private_storage().resolve_path(PrivateDir::Data).unwrap();
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("c.txt"), "old contents").unwrap();
+    api.write_atomic(&FileUri::from_path(dir.join("c.txt")), "new").unwrap();
+    assert_eq!(std::fs::read_to_string(dir.join("c.txt")).unwrap(), "new");
+    assert_eq!(dir_names(&dir), ["c.txt"]);
+}
+
+#[test]
+fn hash() {
+    let sandbox = Sandbox::new("hash");
+    let root = sandbox.documents(&[("a.txt", "123456789"), ("b.txt", "abc")]);
+    let api = sandbox.api();
+    let a = api.resolve_file_uri(&root, "a.txt").unwrap();
+    let b = api.resolve_file_uri(&root, "b.txt").unwrap();
+    let hex_of = |uri, algorithm| api.hash(uri, algorithm).unwrap().iter().map(|b| format!("{b:02x}")).collect::<String>();
+    let hex = |algorithm| hex_of(&a, algorithm);
+
+    assert_eq!(hex(HashAlgorithm::Sha256), "15e2b0d3c33891ebb0f1ef609ec419420c20e320ce94c65fbc8c3312448eb225");
+    assert_eq!(hex(HashAlgorithm::Sha1), "f7c3bc1d808e04732adf679965ccc34ca7ae3441");
+    assert_eq!(hex(HashAlgorithm::Md5), "25f9e794323b453885f5181f1b624d0b");
+    assert_eq!(hex(HashAlgorithm::Crc32), "cbf43926");
+    assert_eq!(hex_of(&b, HashAlgorithm::Xxh64), "44bc2cf5ad770999");
+    assert!(api.hash(&root, HashAlgorithm::Sha256).is_err());
+}
+
+#[test]
+fn append() {
+    let sandbox = Sandbox::new("append");
+    let root = sandbox.documents(&[("log.txt", "a\n")]);
+    let api = sandbox.api();
+
+    let log = api.resolve_file_uri(&root, "log.txt").unwrap();
+    api.append(&log, "b\n").unwrap();
+    api.append(&log, "c\n").unwrap();
+    assert_eq!(api.read_to_string(&log).unwrap(), "a\nb\nc\n");
+
+    let dir = api.private_storage().resolve_path(PrivateDir::Data).unwrap();
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = FileUri::from_path(dir.join("log.txt"));
+    api.append(&path, "a\n").unwrap();
+    api.append(&path, "b\n").unwrap();
+    assert_eq!(std::fs::read_to_string(dir.join("log.txt")).unwrap(), "a\nb\n");
+}
+
+#[test]
+fn read_last_lines() {
+    let sandbox = Sandbox::new("read-last-lines");
+    let long = (0..20000).map(|i| format!("line {i}\n")).collect::<String>();
+    let root = sandbox.documents(&[("a.txt", "\u{feff}a\r\nb\n\nc\n"), ("long.log", &long)]);
+    let api = sandbox.api();
+
+    let a = api.resolve_file_uri(&root, "a.txt").unwrap();
+    assert_eq!(api.read_last_lines(&a, 2, None).unwrap(), ["", "c"]);
+    assert_eq!(api.read_last_lines(&a, 10, None).unwrap(), ["a", "b", "", "c"]);
+    assert!(api.read_last_lines(&a, 0, None).unwrap().is_empty());
+
+    // 64 KiB ごとに後ろから読み込むため、複数のチャンクにまたがる
+    let log = api.resolve_file_uri(&root, "long.log").unwrap();
+    let lines = api.read_last_lines(&log, 10000, Some("utf-8")).unwrap();
+    assert_eq!(lines.len(), 10000);
+    assert_eq!(lines[0], "line 10000");
+    assert_eq!(lines[9999], "line 19999");
+
+    // U+0A41 U+2000 は UTF-16LE で 41 0A 00 20 となり、符号単位の境界以外に LF と同じバイト列を含む
+    let utf16 = "\u{feff}x\n\u{0A41}\u{2000}\r\ny".encode_utf16().flat_map(u16::to_le_bytes).collect::<Vec<_>>();
+    std::fs::write(sandbox.path("Documents/utf16.txt"), utf16).unwrap();
+    let utf16 = api.resolve_file_uri(&root, "utf16.txt").unwrap();
+    assert_eq!(api.read_last_lines(&utf16, 5, Some("utf-16le")).unwrap(), ["x", "\u{0A41}\u{2000}", "y"]);
+    assert!(api.read_last_lines(&utf16, 5, Some("unknown")).is_err());
+}
+
+#[test]
+fn line_index() {
+    let sandbox = Sandbox::new("line-index");
+    let long = (0..20000).map(|i| format!("line {i}\n")).collect::<String>();
+    let root = sandbox.documents(&[("long.log", &long), ("a.txt", "a\r\nb")]);
+    let api = sandbox.api();
+    let log = api.resolve_file_uri(&root, "long.log").unwrap();
+
+    let index = api.build_line_index(&log, 1000, None).unwrap();
+    assert_eq!(index.line_count(), 20000);
+    assert_eq!(index.encoding(), "UTF-8");
+    assert_eq!(index.file_len(), long.len() as u64);
+
+    // 記録された位置から残りの行を読み飛ばせば目的の行になる
+    let (offset, skip) = index.position(12345);
+    assert_eq!(skip, 345);
+    assert!(long[offset as usize..].lines().nth(skip as usize) == Some("line 12345"));
+    assert_eq!(index.position(0), (0, 0));
+    assert_eq!(index.position(50000).0, long.find("line 19000").unwrap() as u64);
+
+    let a = api.resolve_file_uri(&root, "a.txt").unwrap();
+    assert_eq!(api.build_line_index(&a, 1, None).unwrap().line_count(), 2);
+    assert!(api.build_line_index(&a, 0, None).is_err());
+
+    // サイドカーは同じファイルの場合のみ読み込まれる
+    assert_eq!(api.load_line_index(&log).unwrap(), None);
+    api.save_line_index(&log, &index).unwrap();
+    assert_eq!(api.load_line_index(&log).unwrap(), Some(index));
+    assert_eq!(api.load_line_index(&a).unwrap(), None);
+
+    // 索引の作成後に変更されたファイルでは使わない
+    api.append(&log, "line 20000\n").unwrap();
+    assert_eq!(api.load_line_index(&log).unwrap(), None);
+}
+
+#[test]
+fn create_zip() {
+    let sandbox = Sandbox::new("create-zip");
+    let root = sandbox.documents(&[("a.txt", "aaaa"), ("sub/b.txt", "bb"), ("sub/empty/", "")]);
+    let api = sandbox.api();
+    let a = api.resolve_file_uri(&root, "a.txt").unwrap();
+    let sub = api.resolve_dir_uri(&root, "sub").unwrap();
+
+    // セントラルディレクトリから (名前, 展開したデータ) を読む
+    let read_zip = || {
+        let zip = std::fs::read(sandbox.path("Documents/sub/out.zip")).unwrap();
+        let u16_at = |i: usize| u16::from_le_bytes([zip[i], zip[i + 1]]) as usize;
+        let u32_at = |i: usize| u32::from_le_bytes(zip[i..i + 4].try_into().unwrap()) as usize;
+
+        let eocd = zip.len() - 22;
+        assert_eq!(u32_at(eocd), 0x06054b50);
+        let mut offset = u32_at(eocd + 16);
+        let mut entries = Vec::new();
+        for _ in 0..u16_at(eocd + 10) {
+            assert_eq!(u32_at(offset), 0x02014b50);
+            let (method, compressed_len, len) = (u16_at(offset + 10), u32_at(offset + 20), u32_at(offset + 24));
+            let (name_len, extra_len, local) = (u16_at(offset + 28), u16_at(offset + 30), u32_at(offset + 42));
+            let name = String::from_utf8(zip[offset + 46..offset + 46 + name_len].to_vec()).unwrap();
+
+            let data_start = local + 30 + u16_at(local + 26) + u16_at(local + 28);
+            let raw = &zip[data_start..data_start + compressed_len];
+            let data = match method {
+                0 => raw.to_vec(),
+                8 => {
+                    let mut data = Vec::with_capacity(len);
+                    flate2::Decompress::new(false).decompress_vec(raw, &mut data, flate2::FlushDecompress::Finish).unwrap();
+                    data
+                },
+                _ => panic!("unexpected method: {method}"),
+            };
+            assert_eq!(data.len(), len);
+            entries.push((name, String::from_utf8(data).unwrap()));
+            offset += 46 + name_len + extra_len;
+        }
+        entries
+    };
+    let expected = [
+        ("a.txt".to_string(), "aaaa".to_string()),
+        ("sub/".to_string(), String::new()),
+        ("sub/b.txt".to_string(), "bb".to_string()),
+        ("sub/empty/".to_string(), String::new()),
+    ];
+
+    // 出力先がソースのディレクトリ内にあってもアーカイブ自身は含まれない
+    let dest = api.create_new_file(&root, "sub/out.zip", Some("application/zip")).unwrap();
+    let mut progress = Vec::new();
+    let options = CreateZipOptions { compression: ZipCompression::Stored };
+    api.create_zip_with_progress(&[a.clone(), sub.clone()], &dest, options, |done, total| progress.push((done, total))).unwrap();
+    assert_eq!(progress, [(4, 6), (6, 6)]);
+    assert_eq!(read_zip(), expected);
+
+    api.create_zip(&[a.clone(), sub.clone()], &dest, CreateZipOptions::default()).unwrap();
+    assert_eq!(read_zip(), expected);
+
+    // ツリーを含まない URI で指定しても同じドキュメントとして除外される
+    let dest = FileUri::from_uri(dest.uri.replace("/tree/primary%3ADocuments/document/", "/document/"));
+    api.create_zip(&[a.clone(), sub], &dest, CreateZipOptions::default()).unwrap();
+    assert_eq!(read_zip(), expected);
+
+    assert!(api.create_zip(&[a.clone(), a], &dest, CreateZipOptions::default()).is_err());
+}
+
+/// Builds a zip archive of stored files without data descriptors.
+fn stored_zip(files: &[(&str, &str)]) -> Vec<u8> {
+    let mut zip = Vec::new();
+    let mut central_directory = Vec::new();
+    for (name, contents) in files {
+        let mut crc = flate2::Crc::new();
+        crc.update(contents.as_bytes());
+
+        let mut header = Vec::new();
+        header.extend_from_slice(&20u16.to_le_bytes()); // version needed
+        header.extend_from_slice(&0u16.to_le_bytes()); // flags
+        header.extend_from_slice(&0u16.to_le_bytes()); // method
+        header.extend_from_slice(&0u32.to_le_bytes()); // time and date
+        header.extend_from_slice(&crc.sum().to_le_bytes());
+        header.extend_from_slice(&(contents.len() as u32).to_le_bytes());
+        header.extend_from_slice(&(contents.len() as u32).to_le_bytes());
+        header.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        header.extend_from_slice(&0u16.to_le_bytes()); // extra length
+
+        central_directory.extend_from_slice(&0x02014b50u32.to_le_bytes());
+        central_directory.extend_from_slice(&20u16.to_le_bytes()); // version made by
+        central_directory.extend_from_slice(&header);
+        central_directory.extend_from_slice(&[0; 10]); // comment length, disk, attributes
+        central_directory.extend_from_slice(&(zip.len() as u32).to_le_bytes());
+        central_directory.extend_from_slice(name.as_bytes());
+
+        zip.extend_from_slice(&0x04034b50u32.to_le_bytes());
+        zip.extend_from_slice(&header);
+        zip.extend_from_slice(name.as_bytes());
+        zip.extend_from_slice(contents.as_bytes());
+    }
+
+    let offset = zip.len() as u32;
+    zip.extend_from_slice(&central_directory);
+    zip.extend_from_slice(&0x06054b50u32.to_le_bytes());
+    zip.extend_from_slice(&[0; 4]); // disks
+    zip.extend_from_slice(&(files.len() as u16).to_le_bytes());
+    zip.extend_from_slice(&(files.len() as u16).to_le_bytes());
+    zip.extend_from_slice(&(central_directory.len() as u32).to_le_bytes());
+    zip.extend_from_slice(&offset.to_le_bytes());
+    zip.extend_from_slice(&0u16.to_le_bytes()); // comment length
+    zip
+}
+
+#[test]
+fn extract_zip() {
+    let sandbox = Sandbox::new("extract-zip");
+    let root = sandbox.documents(&[("src/a.txt", "aaaa"), ("src/sub/b.txt", "bb"), ("src/sub/empty/", ""), ("dest/", "")]);
+    let api = sandbox.api();
+    let src = api.resolve_dir_uri(&root, "src").unwrap();
+    let dest = api.resolve_dir_uri(&root, "dest").unwrap();
+    let zip = api.create_new_file(&root, "src.zip", Some("application/zip")).unwrap();
+    api.create_zip(&[src], &zip, CreateZipOptions::default()).unwrap();
+
+    let results = |entries: Vec<ExtractZipEntry>| entries.into_iter()
+        .map(|e| (e.name, e.result.ok().and_then(|o| o.dest().map(|_| ()))))
+        .collect::<Vec<_>>();
+
+    let mut progress = Vec::new();
+    let entries = api.extract_zip_with_progress(&zip, &dest, ExtractZipOptions::default(), |done, total| progress.push((done, total))).unwrap();
+    assert_eq!(results(entries), [
+        ("src/".to_string(), Some(())),
+        ("src/a.txt".to_string(), Some(())),
+        ("src/sub/".to_string(), Some(())),
+        ("src/sub/b.txt".to_string(), Some(())),
+        ("src/sub/empty/".to_string(), Some(())),
+    ]);
+    assert_eq!(progress, [(4, 6), (6, 6)]);
+    assert_eq!(std::fs::read_to_string(sandbox.path("Documents/dest/src/a.txt")).unwrap(), "aaaa");
+    assert_eq!(std::fs::read_to_string(sandbox.path("Documents/dest/src/sub/b.txt")).unwrap(), "bb");
+    assert!(sandbox.path("Documents/dest/src/sub/empty").is_dir());
+
+    // 展開先の外を指すエントリと、フィルタに一致しないエントリは展開されない
+    std::fs::write(sandbox.path("Documents/evil.zip"), stored_zip(&[
+        ("../evil.txt", "evil"),
+        ("/abs.txt", "evil"),
+        ("ok.txt", "ok"),
+        ("skip.log", "skip"),
+    ])).unwrap();
+    let evil = api.resolve_file_uri(&root, "evil.zip").unwrap();
+    let filtered = api.create_dir_all(&root, "filtered").unwrap();
+    let options = ExtractZipOptions { exclude: vec!["*.log".to_string()], ..Default::default() };
+    let entries = api.extract_zip(&evil, &filtered, options).unwrap();
+    assert!(entries[0].result.is_err());
+    assert!(entries[1].result.is_err());
+    assert!(matches!(entries[2].result, Ok(ExtractZipOutcome::Extracted { .. })));
+    assert!(matches!(entries[3].result, Ok(ExtractZipOutcome::Skipped)));
+    assert!(!sandbox.path("Documents/evil.txt").exists());
+    assert_eq!(std::fs::read_to_string(sandbox.path("Documents/filtered/ok.txt")).unwrap(), "ok");
+
+    // 記録されたサイズの合計が上限を超える場合は何も展開しない
+    let limited = api.create_dir_all(&root, "limited").unwrap();
+    let options = ExtractZipOptions { max_total_len: Some(5), ..Default::default() };
+    assert!(api.extract_zip(&zip, &limited, options).is_err());
+    assert_eq!(std::fs::read_dir(sandbox.path("Documents/limited")).unwrap().count(), 0);
+}